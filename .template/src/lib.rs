@@ -1,11 +1,21 @@
-#![feature(test)]
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(test, feature(test))]
+extern crate alloc;
+#[cfg(test)]
 extern crate test;
 
-#[cfg(debug_assertions)]
-const INPUT: &str = include_str!("../sample.TXT");
+mod gen;
+
+use alloc::format;
+use common::input::normalize;
+use common::{export_plugin, implementations, Day};
 
-#[cfg(not(debug_assertions))]
-const INPUT: &str = include_str!("../input.TXT");
+#[cfg(all(feature = "std", debug_assertions))]
+static INPUT: common::input::PuzzleInput =
+    common::input::PuzzleInput::sample(include_str!("../sample.TXT"));
+#[cfg(all(feature = "std", not(debug_assertions)))]
+static INPUT: common::input::PuzzleInput =
+    common::input::PuzzleInput::personal(env!("CARGO_MANIFEST_DIR"));
 
 type ParsedInput = usize;
 
@@ -21,23 +31,40 @@ fn parse_input(input: &str) -> ParsedInput {
     todo!()
 }
 
-pub fn solve() {
-    let input = parse_input(INPUT);
+#[cfg(feature = "std")]
+pub fn solve() -> Result<(), String> {
+    let input = normalize(INPUT.get()?);
+    let input = parse_input(&input);
 
     let result = solve_part_one(&input);
     println!("Part #1: {}", result);
 
     let result = solve_part_two(&input);
     println!("Part #2: {}", result);
+    Ok(())
 }
 
+pub const DAY: Day = Day {
+    day: 0,
+    sample: include_str!("../sample.TXT"),
+    part_one: implementations!(parse_input, solve_part_one),
+    part_two: implementations!(parse_input, solve_part_two),
+    generate: gen::generate,
+    inspect: |input| format!("{:#?}", parse_input(&normalize(input))),
+    commands: &[],
+    simulations: &[],
+};
+
+export_plugin!(DAY);
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let result = solve_part_one(&parse_input(INPUT));
+        let result =
+            solve_part_one(&parse_input(&normalize(INPUT.get().unwrap())));
 
         #[cfg(debug_assertions)]
         assert_eq!(result, 4512);
@@ -48,13 +75,15 @@ mod tests {
 
     #[bench]
     fn bench_part_one(b: &mut test::Bencher) {
-        let input = parse_input(INPUT);
+        let input = normalize(INPUT.get().unwrap());
+        let input = parse_input(&input);
         b.iter(|| solve_part_one(&input));
     }
 
     #[test]
     fn test_part_two() {
-        let result = solve_part_two(&parse_input(INPUT));
+        let result =
+            solve_part_two(&parse_input(&normalize(INPUT.get().unwrap())));
 
         #[cfg(debug_assertions)]
         assert_eq!(result, 1924);
@@ -65,7 +94,20 @@ mod tests {
 
     #[bench]
     fn bench_part_two(b: &mut test::Bencher) {
-        let input = parse_input(INPUT);
+        let input = normalize(INPUT.get().unwrap());
+        let input = parse_input(&input);
         b.iter(|| solve_part_two(&input));
     }
+
+    #[test]
+    fn test_generated_input_parses() {
+        for seed in 0..10 {
+            parse_input(&gen::generate(seed, 50));
+        }
+    }
+
+    #[test]
+    fn test_sample_variants() {
+        common::input::assert_variants(&DAY, INPUT.get().unwrap());
+    }
 }
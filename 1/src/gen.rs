@@ -0,0 +1,19 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use common::rng::Rng;
+
+/// Generates the food lists of `size` elves.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let elves = (0..size.max(1))
+        .map(|_| {
+            (0..rng.range(1..=8))
+                .map(|_| rng.range(1000..=9999u32).to_string())
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect::<Vec<_>>();
+    elves.join("\n\n") + "\n"
+}
@@ -0,0 +1,66 @@
+//! Solvers reading the input from a [`BufRead`], one elf at a time.
+
+use common::input::for_each_line;
+use std::io::{self, BufRead};
+use std::num::ParseIntError;
+
+/// The three largest totals, largest first.
+fn top_three(reader: impl BufRead) -> io::Result<[usize; 3]> {
+    let mut top = [0; 3];
+    let mut total = 0;
+    let mut push = |total: usize| {
+        if let Some(i) = top.iter().position(|&t| total > t) {
+            top.copy_within(i..2, i + 1);
+            top[i] = total;
+        }
+    };
+    for_each_line(reader, |line| {
+        match line {
+            "" => push(std::mem::take(&mut total)),
+            line => total += line.parse::<usize>().map_err(invalid(line))?,
+        }
+        Ok(())
+    })?;
+    push(total);
+    Ok(top)
+}
+
+/// An [`io::ErrorKind::InvalidData`] error for a line that is not a number.
+fn invalid(line: &str) -> impl FnOnce(ParseIntError) -> io::Error + '_ {
+    move |err| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{:?}: {}", line, err),
+        )
+    }
+}
+
+pub fn solve_part_one(reader: impl BufRead) -> io::Result<usize> {
+    Ok(top_three(reader)?[0])
+}
+
+pub fn solve_part_two(reader: impl BufRead) -> io::Result<usize> {
+    Ok(top_three(reader)?.iter().sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_input, INPUT};
+    use common::input::normalize;
+
+    #[test]
+    fn test_matches_in_memory() {
+        let input = parse_input(&normalize(INPUT.get().unwrap()));
+        for variant in common::input::variants(INPUT.get().unwrap()) {
+            assert_eq!(
+                solve_part_one(variant.as_bytes()).unwrap(),
+                crate::solve_part_one(&input)
+            );
+            assert_eq!(
+                solve_part_two(variant.as_bytes()).unwrap(),
+                crate::solve_part_two(&input)
+            );
+        }
+    }
+}
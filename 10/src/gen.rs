@@ -0,0 +1,28 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use common::rng::Rng;
+
+/// Generates a program of `size` instructions.
+///
+/// `X` stays within the screen so the CRT output looks like a real image.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut x = 1;
+    (0..size.max(1))
+        .map(|_| {
+            if rng.chance(0.3) {
+                return "noop\n".to_string();
+            }
+            let v = loop {
+                let v = rng.range(-20..=20);
+                if v != 0 && (0..40).contains(&(x + v)) {
+                    break v;
+                }
+            };
+            x += v;
+            format!("addx {}\n", v)
+        })
+        .collect()
+}
@@ -1,11 +1,25 @@
-#![feature(test)]
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(test, feature(test))]
+extern crate alloc;
+#[cfg(test)]
 extern crate test;
 
-#[cfg(debug_assertions)]
-const INPUT: &str = include_str!("../sample.TXT");
+mod gen;
+#[cfg(feature = "std")]
+pub mod stream;
+
+use alloc::{format, string::String, vec, vec::Vec};
+use common::input::normalize;
+use common::simulation::{playback, Simulation, Simulator};
+use common::{export_plugin, implementations, Day};
+use core::fmt;
 
-#[cfg(not(debug_assertions))]
-const INPUT: &str = include_str!("../input.TXT");
+#[cfg(all(feature = "std", debug_assertions))]
+static INPUT: common::input::PuzzleInput =
+    common::input::PuzzleInput::sample(include_str!("../sample.TXT"));
+#[cfg(all(feature = "std", not(debug_assertions)))]
+static INPUT: common::input::PuzzleInput =
+    common::input::PuzzleInput::personal(env!("CARGO_MANIFEST_DIR"));
 
 type ParsedInput = Vec<Opcode>;
 
@@ -32,74 +46,176 @@ fn get_pixel_value(x: i32, cycle: usize) -> char {
     }
 }
 
+/// The X register and the number of cycles run so far.
+#[derive(Debug, Copy, Clone)]
+struct Cpu {
+    x: i32,
+    cycle: usize,
+}
+
+impl Cpu {
+    fn new() -> Self {
+        Self { x: 1, cycle: 0 }
+    }
+
+    /// Runs one cycle, returning the cycle number and X after it.
+    fn execute(&mut self, op: Opcode) -> (usize, i32) {
+        self.x = op.execute(self.x);
+        self.cycle += 1;
+        (self.cycle, self.x)
+    }
+}
+
+/// The program running one cycle per step, the CRT drawing a pixel each
+/// cycle.
+struct Device {
+    program: Vec<Opcode>,
+    state: (Cpu, String),
+}
+
+impl Device {
+    fn new(program: Vec<Opcode>) -> Self {
+        let cpu = Cpu::new();
+        let crt = String::from(get_pixel_value(cpu.x, 0));
+        Self {
+            program,
+            state: (cpu, crt),
+        }
+    }
+
+    /// The complete rows of the CRT.
+    fn screen(&self) -> String {
+        self.state
+            .1
+            .as_bytes()
+            .chunks_exact(40)
+            .map(|row| core::str::from_utf8(row).unwrap())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl Simulation for Device {
+    type Snapshot = (Cpu, String);
+
+    fn step(&mut self) -> bool {
+        let (cpu, crt) = &mut self.state;
+        let Some(&op) = self.program.get(cpu.cycle) else {
+            return false;
+        };
+        let (cycle, x) = cpu.execute(op);
+        crt.push(get_pixel_value(x, cycle));
+        true
+    }
+
+    fn steps(&self) -> usize {
+        self.state.0.cycle
+    }
+
+    fn snapshot(&self) -> Self::Snapshot {
+        self.state.clone()
+    }
+
+    fn restore(&mut self, snapshot: &Self::Snapshot) {
+        self.state = snapshot.clone();
+    }
+}
+
+/// The registers, then the CRT drawn so far.
+impl fmt::Display for Device {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (cpu, crt) = &self.state;
+        write!(f, "cycle {}: X = {}", cpu.cycle + 1, cpu.x)?;
+        for row in crt.as_bytes().chunks(40) {
+            write!(f, "\n{}", core::str::from_utf8(row).unwrap())?;
+        }
+        Ok(())
+    }
+}
+
 fn solve_part_one(input: &ParsedInput) -> i32 {
-    input
-        .iter()
-        .fold(vec![1], |mut xs, op| {
-            let x = *xs.last().unwrap();
-            xs.push(op.execute(x));
-            xs
-        })
-        .iter()
-        .enumerate()
-        .skip(19)
-        .step_by(40)
-        .map(|(i, x)| ((i + 1) as i32) * x)
-        .sum()
+    let mut device = Device::new(input.clone());
+    let mut strength = 0;
+    loop {
+        let Cpu { x, cycle } = device.state.0;
+        if cycle % 40 == 19 {
+            strength += (cycle + 1) as i32 * x;
+        }
+        if !device.step() {
+            break strength;
+        }
+    }
 }
 
 fn solve_part_two(input: &ParsedInput) -> String {
-    input
-        .iter()
-        .enumerate()
-        .fold((1, vec!['#']), |(x, mut crt), (cycle, op)| {
-            let x = op.execute(x);
-            crt.push(get_pixel_value(x, cycle + 1));
-            (x, crt)
-        })
-        .1
-        .chunks_exact(40)
-        .map(|x| x.iter().collect::<String>())
-        .collect::<Vec<_>>()
-        .join("\n")
+    let mut device = Device::new(input.clone());
+    device.run();
+    device.screen()
+}
+
+/// The opcodes of an instruction, one per cycle it takes.
+fn parse_line(line: &str) -> Result<Vec<Opcode>, String> {
+    let invalid = || format!("invalid instruction: {:?}", line);
+    match line.split_whitespace().collect::<Vec<_>>()[..] {
+        ["noop"] => Ok(vec![Opcode::Noop]),
+        ["addx", n] => {
+            let n = n.parse::<i32>().map_err(|_| invalid())?;
+            Ok(vec![Opcode::Noop, Opcode::AddX(n)])
+        }
+        _ => Err(invalid()),
+    }
 }
 
 fn parse_input(input: &str) -> ParsedInput {
     input
         .lines()
-        .flat_map(
-            |curr| match curr.split_whitespace().collect::<Vec<_>>()[..] {
-                ["noop"] => vec![Opcode::Noop],
-                ["addx", n] => {
-                    let n = n.parse::<i32>().unwrap();
-                    vec![Opcode::Noop, Opcode::AddX(n)]
-                }
-                _ => unreachable!(),
-            },
-        )
+        .flat_map(|line| parse_line(line).unwrap())
         .collect()
 }
 
-pub fn solve() {
-    let input = parse_input(INPUT);
+#[cfg(feature = "std")]
+pub fn solve() -> Result<(), String> {
+    let input = normalize(INPUT.get()?);
+    let input = parse_input(&input);
 
     let result = solve_part_one(&input);
     println!("Part #1: {}", result);
 
     let result = solve_part_two(&input);
     println!("Part #2:\n{}", result);
+    Ok(())
 }
 
+pub const DAY: Day = Day {
+    day: 10,
+    sample: include_str!("../sample.TXT"),
+    part_one: implementations!(parse_input, solve_part_one),
+    part_two: implementations!(parse_input, solve_part_two),
+    generate: gen::generate,
+    inspect: |input| format!("{:#?}", parse_input(&normalize(input))),
+    commands: &[],
+    simulations: &[Simulator {
+        name: "crt",
+        help: "the program drawing on the CRT, one cycle per step",
+        start: |input| {
+            Ok(playback(Device::new(parse_input(&normalize(input)))))
+        },
+    }],
+};
+
+export_plugin!(DAY);
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let result = solve_part_one(&parse_input(INPUT));
+        let result =
+            solve_part_one(&parse_input(&normalize(INPUT.get().unwrap())));
 
         #[cfg(debug_assertions)]
-        assert_eq!(result, 4512);
+        assert_eq!(result, 13140);
 
         #[cfg(not(debug_assertions))]
         assert_eq!(result, 27027);
@@ -107,13 +223,15 @@ mod tests {
 
     #[bench]
     fn bench_part_one(b: &mut test::Bencher) {
-        let input = parse_input(INPUT);
+        let input = normalize(INPUT.get().unwrap());
+        let input = parse_input(&input);
         b.iter(|| solve_part_one(&input));
     }
 
     #[test]
     fn test_part_two() {
-        let result = solve_part_two(&parse_input(INPUT));
+        let result =
+            solve_part_two(&parse_input(&normalize(INPUT.get().unwrap())));
 
         #[cfg(debug_assertions)]
         assert_eq!(
@@ -140,7 +258,35 @@ mod tests {
 
     #[bench]
     fn bench_part_two(b: &mut test::Bencher) {
-        let input = parse_input(INPUT);
+        let input = normalize(INPUT.get().unwrap());
+        let input = parse_input(&input);
         b.iter(|| solve_part_two(&input));
     }
+
+    #[test]
+    fn test_device_replay() {
+        let mut device =
+            (DAY.simulations[0].start)(INPUT.get().unwrap()).unwrap();
+        assert!(device.seek(3));
+        assert_eq!(device.render(), "cycle 4: X = 16\n##..");
+        assert!(device.seek(40));
+        assert!(device
+            .render()
+            .ends_with("##..##..##..##..##..##..##..##..##..##..\n#"));
+        assert!(!device.seek(500));
+        assert_eq!(device.steps(), 240);
+    }
+
+    #[test]
+    fn test_generated_input_parses() {
+        for seed in 0..10 {
+            let input = parse_input(&gen::generate(seed, 150));
+            assert!(input.len() >= 150);
+        }
+    }
+
+    #[test]
+    fn test_sample_variants() {
+        common::input::assert_variants(&DAY, INPUT.get().unwrap());
+    }
 }
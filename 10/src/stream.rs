@@ -0,0 +1,68 @@
+//! Solvers reading the program from a [`BufRead`], one instruction at a
+//! time.
+
+use crate::{get_pixel_value, parse_line, Cpu};
+use common::input::for_each_line;
+use std::io::{self, BufRead};
+
+pub fn solve_part_one(reader: impl BufRead) -> io::Result<i32> {
+    let mut cpu = Cpu::new();
+    let mut strength = 0;
+    for_each_line(reader, |line| {
+        let ops = parse_line(line)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        for op in ops {
+            let (cycle, x) = cpu.execute(op);
+            if cycle % 40 == 19 {
+                strength += (cycle + 1) as i32 * x;
+            }
+        }
+        Ok(())
+    })?;
+    Ok(strength)
+}
+
+pub fn solve_part_two(reader: impl BufRead) -> io::Result<String> {
+    let mut cpu = Cpu::new();
+    let mut screen = String::new();
+    let mut row = String::from('#');
+    for_each_line(reader, |line| {
+        let ops = parse_line(line)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        for op in ops {
+            let (cycle, x) = cpu.execute(op);
+            row.push(get_pixel_value(x, cycle));
+            if row.len() == 40 {
+                if !screen.is_empty() {
+                    screen.push('\n');
+                }
+                screen.push_str(&row);
+                row.clear();
+            }
+        }
+        Ok(())
+    })?;
+    Ok(screen)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_input, INPUT};
+    use common::input::normalize;
+
+    #[test]
+    fn test_matches_in_memory() {
+        let input = parse_input(&normalize(INPUT.get().unwrap()));
+        for variant in common::input::variants(INPUT.get().unwrap()) {
+            assert_eq!(
+                solve_part_one(variant.as_bytes()).unwrap(),
+                crate::solve_part_one(&input)
+            );
+            assert_eq!(
+                solve_part_two(variant.as_bytes()).unwrap(),
+                crate::solve_part_two(&input)
+            );
+        }
+    }
+}
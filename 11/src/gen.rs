@@ -0,0 +1,119 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use common::rng::Rng;
+
+const PRIMES: [u64; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+
+#[derive(Clone, Copy)]
+enum Op {
+    Square,
+    Add(u64),
+    Mul(u64),
+}
+
+struct Rules {
+    items: Vec<Vec<u64>>,
+    ops: Vec<Op>,
+    tests: Vec<u64>,
+    targets: Vec<[usize; 2]>,
+}
+
+/// Generates between three and eight monkeys holding `size` items in total.
+///
+/// Divisors are distinct primes so part two's modulus squared fits in 64 bits,
+/// and rule sets whose part one would overflow are rejected and redrawn.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let rules = (0..)
+        .map(|attempt| random_rules(&mut rng, size, attempt < 32))
+        .find(fits_in_u64)
+        .unwrap();
+
+    let monkeys = (0..rules.ops.len())
+        .map(|i| {
+            let items = rules.items[i]
+                .iter()
+                .map(|w| w.to_string())
+                .collect::<Vec<_>>();
+            let op = match rules.ops[i] {
+                Op::Square => "old * old".to_string(),
+                Op::Add(x) => format!("old + {}", x),
+                Op::Mul(x) => format!("old * {}", x),
+            };
+            format!(
+                "Monkey {}:\n  Starting items: {}\n  Operation: new = {}\n  \
+                 Test: divisible by {}\n    If true: throw to monkey {}\n    \
+                 If false: throw to monkey {}\n",
+                i,
+                items.join(", "),
+                op,
+                rules.tests[i],
+                rules.targets[i][0],
+                rules.targets[i][1],
+            )
+        })
+        .collect::<Vec<_>>();
+    monkeys.join("\n")
+}
+
+fn random_rules(rng: &mut Rng, size: usize, allow_mul: bool) -> Rules {
+    let count = rng.range(3..=8);
+    let mut tests = PRIMES.to_vec();
+    rng.shuffle(&mut tests);
+    tests.truncate(count);
+
+    let square = rng.below(count as u64) as usize;
+    let ops = (0..count)
+        .map(|i| match (allow_mul, rng.chance(0.5)) {
+            (true, _) if i == square => Op::Square,
+            (true, true) => Op::Mul(rng.range(2..=19)),
+            _ => Op::Add(rng.range(1..=8)),
+        })
+        .collect();
+    let targets = (0..count)
+        .map(|i| {
+            let mut others = (0..count).filter(|&j| j != i).collect::<Vec<_>>();
+            rng.shuffle(&mut others);
+            [others[0], others[1]]
+        })
+        .collect();
+    let mut items = vec![vec![]; count];
+    for _ in 0..size {
+        items[rng.below(count as u64) as usize].push(rng.range(50..=99));
+    }
+    Rules {
+        items,
+        ops,
+        tests,
+        targets,
+    }
+}
+
+/// Plays part one's twenty rounds with checked arithmetic.
+fn fits_in_u64(rules: &Rules) -> bool {
+    let mut items = rules.items.clone();
+    for _ in 0..20 {
+        for i in 0..items.len() {
+            for item in core::mem::take(&mut items[i]) {
+                let worry = match rules.ops[i] {
+                    Op::Square => item.checked_mul(item),
+                    Op::Add(x) => item.checked_add(x),
+                    Op::Mul(x) => item.checked_mul(x),
+                };
+                let Some(worry) = worry.map(|w| w / 3) else {
+                    return false;
+                };
+                let dest = match worry.is_multiple_of(rules.tests[i]) {
+                    true => rules.targets[i][0],
+                    false => rules.targets[i][1],
+                };
+                items[dest].push(worry);
+            }
+        }
+    }
+    true
+}
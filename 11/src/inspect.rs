@@ -0,0 +1,70 @@
+use super::{describe, parse_input, KeepAway};
+use alloc::{format, string::String};
+use common::input::normalize;
+use common::simulation::Simulation;
+use common::Command;
+use itertools::Itertools;
+
+pub const COMMANDS: &[Command] = &[Command {
+    name: "rounds",
+    usage: "rounds <n> [div3|lcm]",
+    help: "play n rounds with part one (div3) or part two (lcm) relief",
+    run: rounds,
+}];
+
+fn rounds(input: &str, args: &[&str]) -> Result<String, String> {
+    let n = args
+        .first()
+        .ok_or("missing number of rounds")?
+        .parse::<usize>()
+        .map_err(|_| "invalid number of rounds")?;
+    let input = normalize(input);
+    let monkeys = parse_input(&input);
+    let mut game = match args.get(1).copied().unwrap_or("div3") {
+        "div3" => KeepAway::with_division(monkeys, n),
+        "lcm" => KeepAway::with_modulo(monkeys, n),
+        relief => return Err(format!("unknown relief: {}", relief)),
+    };
+    game.run();
+    let monkeys = game.monkeys.iter().enumerate();
+    Ok(monkeys.map(|(i, monkey)| describe(i, monkey)).join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rounds() {
+        let input = include_str!("../sample.TXT");
+        assert_eq!(
+            rounds(input, &["1"]).unwrap(),
+            "Monkey 0: inspected 2 items, holding [20, 23, 27, 26]\n\
+             Monkey 1: inspected 4 items, holding [2080, 25, 167, 207, 401, \
+             1046]\n\
+             Monkey 2: inspected 3 items, holding []\n\
+             Monkey 3: inspected 5 items, holding []"
+        );
+        assert!(rounds(input, &["20", "lcm"]).is_ok());
+        assert!(rounds(input, &[]).is_err());
+        assert!(rounds(input, &["1", "none"]).is_err());
+    }
+
+    #[test]
+    fn test_keep_away_replay() {
+        let input = include_str!("../sample.TXT");
+        let mut game = (crate::DAY.simulations[0].start)(input).unwrap();
+        assert!(game.seek(20) && !game.step());
+        let after_20 = game.render();
+        assert!(
+            after_20.starts_with("After round 20:\nMonkey 0: inspected 101")
+        );
+        assert!(game.seek(1));
+        assert_eq!(
+            game.render().lines().nth(1),
+            Some("Monkey 0: inspected 2 items, holding [20, 23, 27, 26]")
+        );
+        assert!(game.seek(20));
+        assert_eq!(game.render(), after_20);
+    }
+}
@@ -1,14 +1,29 @@
-#![feature(test)]
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(test, feature(test))]
+extern crate alloc;
+#[cfg(test)]
 extern crate test;
 
-use itertools::Itertools;
-use std::collections::VecDeque;
+mod gen;
+mod inspect;
 
-#[cfg(debug_assertions)]
-const INPUT: &str = include_str!("../sample.TXT");
+use alloc::borrow::ToOwned;
+use alloc::collections::VecDeque;
+use alloc::{format, string::String, vec::Vec};
+use common::context::{Cancelled, Context};
+use common::input::normalize;
+use common::parse;
+use common::simulation::{playback, Simulation, Simulator};
+use common::{export_plugin, implementations, Day};
+use core::fmt;
+use itertools::Itertools;
 
-#[cfg(not(debug_assertions))]
-const INPUT: &str = include_str!("../input.TXT");
+#[cfg(all(feature = "std", debug_assertions))]
+static INPUT: common::input::PuzzleInput =
+    common::input::PuzzleInput::sample(include_str!("../sample.TXT"));
+#[cfg(all(feature = "std", not(debug_assertions)))]
+static INPUT: common::input::PuzzleInput =
+    common::input::PuzzleInput::personal(env!("CARGO_MANIFEST_DIR"));
 
 type ParsedInput = Vec<Monkey>;
 
@@ -28,48 +43,139 @@ struct Monkey {
     items: VecDeque<usize>,
 }
 
-fn do_round<F: Fn(usize) -> usize>(
-    mut monkeys: Vec<Monkey>,
-    relief_strategy: F,
-) -> Vec<Monkey> {
-    for i in 0..monkeys.len() {
-        while let Some(item) = monkeys[i].items.pop_front() {
-            monkeys[i].count += 1;
-            let worry_level = match monkeys[i].op {
-                Operation::Add(x) => item + x,
-                Operation::Mul(x) => item * x,
-                Operation::Square => item * item,
-            };
-            let worry_level = relief_strategy(worry_level);
-            let dest = monkeys[i].targets
-                [usize::from(worry_level % monkeys[i].test == 0)];
-            monkeys[dest].items.push_back(worry_level);
+/// How worry levels are kept in check after each inspection.
+#[derive(Debug, Copy, Clone)]
+enum Relief {
+    /// Divided by three, from the monkeys leaving the item undamaged.
+    Divide(usize),
+    /// Reduced modulo the product of the tests, which keeps every test's
+    /// outcome.
+    Modulo(usize),
+}
+
+impl Relief {
+    fn apply(self, worry_level: usize) -> usize {
+        match self {
+            Relief::Divide(d) => worry_level / d,
+            Relief::Modulo(m) => worry_level % m,
+        }
+    }
+}
+
+/// The monkeys playing keep away, one round per step.
+#[derive(Debug, Clone)]
+struct KeepAway {
+    monkeys: Vec<Monkey>,
+    relief: Relief,
+    round: usize,
+    rounds: usize,
+}
+
+impl KeepAway {
+    fn with_division(monkeys: Vec<Monkey>, rounds: usize) -> Self {
+        Self::new(monkeys, Relief::Divide(3), rounds)
+    }
+
+    fn with_modulo(monkeys: Vec<Monkey>, rounds: usize) -> Self {
+        let lcm = monkeys.iter().map(|m| m.test).product::<usize>();
+        Self::new(monkeys, Relief::Modulo(lcm), rounds)
+    }
+
+    fn new(monkeys: Vec<Monkey>, relief: Relief, rounds: usize) -> Self {
+        Self {
+            monkeys,
+            relief,
+            round: 0,
+            rounds,
         }
     }
-    monkeys
+
+    /// The product of the two highest inspection counts.
+    fn monkey_business(&self) -> usize {
+        self.monkeys
+            .iter()
+            .map(|x| x.count)
+            .sorted()
+            .rev()
+            .take(2)
+            .product()
+    }
+}
+
+impl Simulation for KeepAway {
+    type Snapshot = (Vec<Monkey>, usize);
+
+    fn step(&mut self) -> bool {
+        if self.round == self.rounds {
+            return false;
+        }
+        let monkeys = &mut self.monkeys;
+        for i in 0..monkeys.len() {
+            while let Some(item) = monkeys[i].items.pop_front() {
+                monkeys[i].count += 1;
+                let worry_level = match monkeys[i].op {
+                    Operation::Add(x) => item + x,
+                    Operation::Mul(x) => item * x,
+                    Operation::Square => item * item,
+                };
+                let worry_level = self.relief.apply(worry_level);
+                let dest = monkeys[i].targets
+                    [usize::from(worry_level.is_multiple_of(monkeys[i].test))];
+                monkeys[dest].items.push_back(worry_level);
+            }
+        }
+        self.round += 1;
+        true
+    }
+
+    fn steps(&self) -> usize {
+        self.round
+    }
+
+    fn snapshot(&self) -> Self::Snapshot {
+        (self.monkeys.clone(), self.round)
+    }
+
+    fn restore(&mut self, (monkeys, round): &Self::Snapshot) {
+        self.monkeys.clone_from(monkeys);
+        self.round = *round;
+    }
+}
+
+/// What each monkey holds after the rounds played.
+impl fmt::Display for KeepAway {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "After round {}:", self.round)?;
+        for (i, monkey) in self.monkeys.iter().enumerate() {
+            write!(f, "\n{}", describe(i, monkey))?;
+        }
+        Ok(())
+    }
+}
+
+fn describe(i: usize, monkey: &Monkey) -> String {
+    format!(
+        "Monkey {}: inspected {} items, holding [{}]",
+        i,
+        monkey.count,
+        monkey.items.iter().join(", ")
+    )
 }
 
 fn solve_part_one(input: &ParsedInput) -> usize {
-    (0..20)
-        .fold(input.to_owned(), |state, _| do_round(state, |w| w / 3))
-        .into_iter()
-        .map(|x| x.count)
-        .sorted()
-        .rev()
-        .take(2)
-        .product()
+    let mut game = KeepAway::with_division(input.to_owned(), 20);
+    game.run();
+    game.monkey_business()
 }
 
-fn solve_part_two(input: &ParsedInput) -> usize {
-    let lcm = input.iter().map(|m| m.test).product::<usize>();
-    (0..10_000)
-        .fold(input.to_owned(), |state, _| do_round(state, |w| w % lcm))
-        .into_iter()
-        .map(|x| x.count)
-        .sorted()
-        .rev()
-        .take(2)
-        .product()
+fn solve_part_two(
+    input: &ParsedInput,
+    context: &Context,
+) -> Result<usize, Cancelled> {
+    let mut game = KeepAway::with_modulo(input.to_owned(), 10_000);
+    context.set_total(10_000);
+    game.run_in(context)?;
+    Ok(game.monkey_business())
 }
 
 fn parse_input(input: &str) -> ParsedInput {
@@ -91,10 +197,7 @@ fn parse_input(input: &str) -> ParsedInput {
 }
 
 fn parse_items(items: &str) -> VecDeque<usize> {
-    items
-        .split_whitespace()
-        .filter_map(|x| x.trim_end_matches(',').parse().ok())
-        .collect()
+    parse::numbers(items).collect()
 }
 
 fn parse_op(op: &str) -> Option<Operation> {
@@ -111,32 +214,66 @@ fn parse_op(op: &str) -> Option<Operation> {
 }
 
 fn parse_test(test: &str) -> Option<usize> {
-    test.split_whitespace().last()?.parse().ok()
+    parse::extract(test).ok().map(|[divisor]| divisor)
 }
 
 fn parse_decisions(if_true: &str, if_false: &str) -> Option<[usize; 2]> {
-    let if_true = if_true.split_whitespace().last()?;
-    let if_false = if_false.split_whitespace().last()?;
-    Some([if_false.parse().ok()?, if_true.parse().ok()?])
+    let [if_true] = parse::extract(if_true).ok()?;
+    let [if_false] = parse::extract(if_false).ok()?;
+    Some([if_false, if_true])
 }
 
-pub fn solve() {
-    let input = parse_input(INPUT);
+#[cfg(feature = "std")]
+pub fn solve() -> Result<(), String> {
+    let input = normalize(INPUT.get()?);
+    let input = parse_input(&input);
 
     let result = solve_part_one(&input);
     println!("Part #1: {}", result);
 
-    let result = solve_part_two(&input);
+    let result = solve_part_two(&input, &Context::new()).unwrap();
     println!("Part #2: {}", result);
+    Ok(())
 }
 
+pub const DAY: Day = Day {
+    day: 11,
+    sample: include_str!("../sample.TXT"),
+    part_one: implementations!(parse_input, solve_part_one),
+    part_two: implementations!(parse_input, with_context solve_part_two),
+    generate: gen::generate,
+    inspect: |input| format!("{:#?}", parse_input(&normalize(input))),
+    commands: inspect::COMMANDS,
+    simulations: &[
+        Simulator {
+            name: "div3",
+            help: "the 20 rounds of part one, worry divided by three",
+            start: |input| {
+                let input = parse_input(&normalize(input));
+                Ok(playback(KeepAway::with_division(input, 20)))
+            },
+        },
+        Simulator {
+            name: "lcm",
+            help: "the 10000 rounds of part two, worry kept modulo the tests",
+            start: |input| {
+                let input = parse_input(&normalize(input));
+                Ok(playback(KeepAway::with_modulo(input, 10_000)))
+            },
+        },
+    ],
+};
+
+export_plugin!(DAY);
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let result = solve_part_one(&parse_input(INPUT));
+        let result =
+            solve_part_one(&parse_input(&normalize(INPUT.get().unwrap())));
 
         #[cfg(debug_assertions)]
         assert_eq!(result, 10605);
@@ -147,13 +284,18 @@ mod tests {
 
     #[bench]
     fn bench_part_one(b: &mut test::Bencher) {
-        let input = parse_input(INPUT);
+        let input = normalize(INPUT.get().unwrap());
+        let input = parse_input(&input);
         b.iter(|| solve_part_one(&input));
     }
 
     #[test]
     fn test_part_two() {
-        let result = solve_part_two(&parse_input(INPUT));
+        let result = solve_part_two(
+            &parse_input(&normalize(INPUT.get().unwrap())),
+            &Context::new(),
+        )
+        .unwrap();
 
         #[cfg(debug_assertions)]
         assert_eq!(result, 2713310158);
@@ -164,7 +306,35 @@ mod tests {
 
     #[bench]
     fn bench_part_two(b: &mut test::Bencher) {
-        let input = parse_input(INPUT);
-        b.iter(|| solve_part_two(&input));
+        let input = normalize(INPUT.get().unwrap());
+        let input = parse_input(&input);
+        b.iter(|| solve_part_two(&input, &Context::new()));
+    }
+
+    #[test]
+    fn test_part_two_context() {
+        let (input, context) = (
+            parse_input(&normalize(INPUT.get().unwrap())),
+            Context::new(),
+        );
+        solve_part_two(&input, &context).unwrap();
+        assert_eq!(context.progress(), Some((10_000, 10_000)));
+        context.cancel();
+        assert_eq!(solve_part_two(&input, &context), Err(Cancelled));
+    }
+
+    #[test]
+    fn test_generated_input_parses() {
+        for seed in 0..10 {
+            let input = parse_input(&gen::generate(seed, 50));
+            assert!((3..=8).contains(&input.len()));
+            assert_eq!(input.iter().map(|m| m.items.len()).sum::<usize>(), 50);
+            solve_part_one(&input);
+        }
+    }
+
+    #[test]
+    fn test_sample_variants() {
+        common::input::assert_variants(&DAY, INPUT.get().unwrap());
     }
 }
@@ -0,0 +1,41 @@
+use alloc::{string::String, vec, vec::Vec};
+use common::rng::Rng;
+
+/// Generates a heightmap `size` columns wide (at least 26).
+///
+/// A path from `S` on the left edge to `E` on the right edge climbs at most
+/// one step at a time, so the summit is always reachable.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let width = size.max(26);
+    let height = (width / 4).max(5);
+    let mut map = (0..height)
+        .map(|_| {
+            (0..width)
+                .map(|_| rng.range(b'a'..=b'z'))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut y = rng.below(height as u64) as usize;
+    let mut path = vec![];
+    for x in 0..width {
+        let target = rng.below(height as u64) as usize;
+        while y != target {
+            path.push((x, y));
+            y = if y < target { y + 1 } else { y - 1 };
+        }
+        path.push((x, y));
+    }
+    for (i, &(x, y)) in path.iter().enumerate() {
+        map[y][x] = b'a' + (i * 25 / (path.len() - 1)) as u8;
+    }
+    let (sx, sy) = path[0];
+    let (ex, ey) = path[path.len() - 1];
+    map[sy][sx] = b'S';
+    map[ey][ex] = b'E';
+
+    map.into_iter()
+        .map(|row| String::from_utf8(row).unwrap() + "\n")
+        .collect()
+}
@@ -0,0 +1,52 @@
+use super::{lowest_squares, parse_input, route};
+use alloc::{format, string::String};
+use common::input::normalize;
+use common::Command;
+use core::cmp::Ordering;
+
+pub const COMMANDS: &[Command] = &[Command {
+    name: "path",
+    usage: "path [1|2]",
+    help: "draw a shortest route to the best signal, from S or any `a`",
+    run: path,
+}];
+
+fn path(input: &str, args: &[&str]) -> Result<String, String> {
+    let input = normalize(input);
+    let input = parse_input(&input);
+    let search = match args.first().copied().unwrap_or("1") {
+        "1" => route(&input, [input.start]),
+        "2" => route(&input, lowest_squares(&input)),
+        part => return Err(format!("no part {}", part)),
+    };
+    let path = search.goal_path().ok_or("the end cannot be reached")?;
+
+    let mut map = input.heights.map(|_| '.');
+    for step in path.windows(2) {
+        let ((x, y), (nx, ny)) = (step[0], step[1]);
+        map[step[0]] = match (nx.cmp(&x), ny.cmp(&y)) {
+            (Ordering::Greater, _) => '>',
+            (Ordering::Less, _) => '<',
+            (_, Ordering::Greater) => 'v',
+            _ => '^',
+        };
+    }
+    map[input.end] = 'E';
+    Ok(format!("{}\n{} steps", map, path.len() - 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path() {
+        let input = include_str!("../sample.TXT");
+        let output = path(input, &[]).unwrap();
+        assert!(output.ends_with("\n31 steps"));
+        let arrows = output.chars().filter(|c| "<>^v".contains(*c)).count();
+        assert_eq!(arrows, 31);
+        assert!(path(input, &["2"]).unwrap().ends_with("\n29 steps"));
+        assert!(path(input, &["3"]).is_err());
+    }
+}
@@ -1,118 +1,119 @@
-#![feature(test)]
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(test, feature(test))]
+extern crate alloc;
+#[cfg(test)]
 extern crate test;
 
-use std::collections::VecDeque;
-
-#[cfg(debug_assertions)]
-const INPUT: &str = include_str!("../sample.TXT");
+mod gen;
+mod inspect;
+#[cfg(feature = "visualize")]
+pub mod visualize;
 
-#[cfg(not(debug_assertions))]
-const INPUT: &str = include_str!("../input.TXT");
+use alloc::format;
+use common::grid::{Grid, Pos};
+use common::input::normalize;
+use common::search::{self, Search};
+use common::{export_plugin, implementations, Day};
 
-type ParsedInput = ((usize, usize), Vec<Node>);
+#[cfg(all(feature = "std", debug_assertions))]
+static INPUT: common::input::PuzzleInput =
+    common::input::PuzzleInput::sample(include_str!("../sample.TXT"));
+#[cfg(all(feature = "std", not(debug_assertions)))]
+static INPUT: common::input::PuzzleInput =
+    common::input::PuzzleInput::personal(env!("CARGO_MANIFEST_DIR"));
 
 #[derive(Debug, Clone)]
-struct Node {
-    value: u8,
-    children: Vec<usize>,
+struct Heightmap {
+    start: Pos,
+    end: Pos,
+    heights: Grid<u8>,
 }
 
-fn get_shortest_distance(nodes: &[Node], start: usize, target: usize) -> usize {
-    let mut queue = VecDeque::new();
-    let mut visited = vec![false; nodes.len()];
-    queue.push_back((start, 0));
-    while let Some((node, distance)) = queue.pop_front() {
-        if node == target {
-            return distance;
-        }
-        if visited[node] {
-            continue;
-        }
-        visited[node] = true;
-        for &child in &nodes[node].children {
-            if nodes[child].value <= nodes[node].value + 1 {
-                queue.push_back((child, distance + 1));
-            }
-        }
-    }
-    usize::MAX
+type ParsedInput = Heightmap;
+
+/// Shortest route from the nearest of `starts` to the end.
+fn route(
+    input: &ParsedInput,
+    starts: impl IntoIterator<Item = Pos>,
+) -> Search<Pos> {
+    let heights = &input.heights;
+    // One step climbs at most one higher, or goes down any amount.
+    let climbable = |&pos: &Pos| {
+        heights
+            .neighbors(pos)
+            .filter(move |&next| heights[next] <= heights[pos] + 1)
+    };
+    search::bfs(starts, climbable, |&pos| pos == input.end)
 }
 
-fn solve_part_one(((start, end), nodes): &ParsedInput) -> usize {
-    get_shortest_distance(nodes, *start, *end)
+fn lowest_squares(input: &ParsedInput) -> impl Iterator<Item = Pos> + '_ {
+    input
+        .heights
+        .iter()
+        .filter_map(|(pos, &height)| (height == b'a').then_some(pos))
 }
 
-fn solve_part_two(((_, target), nodes): &ParsedInput) -> usize {
-    nodes
-        .iter()
-        .enumerate()
-        .filter_map(|(i, node)| (node.value == b'a').then_some(i))
-        .map(|i| get_shortest_distance(nodes, i, *target))
-        .min()
-        .unwrap()
+fn solve_part_one(input: &ParsedInput) -> usize {
+    route(input, [input.start]).goal_distance().unwrap()
+}
+
+fn solve_part_two(input: &ParsedInput) -> usize {
+    route(input, lowest_squares(input)).goal_distance().unwrap()
 }
 
 fn parse_input(input: &str) -> ParsedInput {
-    let height = input.lines().count();
-    let width = input.lines().next().unwrap().len();
-    input.lines().enumerate().fold(
-        ((0, 0), vec![]),
-        |((mut start, mut end), mut nodes), (y, line)| {
-            nodes.extend(line.char_indices().map(|(x, ch)| {
-                let value = match ch {
-                    'S' => {
-                        start = y * width + x;
-                        b'a'
-                    }
-                    'E' => {
-                        end = y * width + x;
-                        b'z'
-                    }
-                    ch => ch as u8,
-                };
-
-                let children = [(0, 1), (0, -1), (1, 0), (-1, 0)]
-                    .iter()
-                    .filter_map(|(dx, dy)| {
-                        let x = x as i32 + dx;
-                        let y = y as i32 + dy;
-                        if x < 0
-                            || y < 0
-                            || x as usize >= width
-                            || y as usize >= height
-                        {
-                            return None;
-                        }
-                        let x = x as usize;
-                        let y = y as usize;
-                        Some(y * width + x)
-                    })
-                    .collect();
-
-                Node { value, children }
-            }));
-            ((start, end), nodes)
-        },
-    )
+    let map = Grid::parse(input, |ch| ch as u8).unwrap();
+    let start = map.position(|&ch| ch == b'S').unwrap();
+    let end = map.position(|&ch| ch == b'E').unwrap();
+    let heights = map.map(|&ch| match ch {
+        b'S' => b'a',
+        b'E' => b'z',
+        ch => ch,
+    });
+    Heightmap {
+        start,
+        end,
+        heights,
+    }
 }
 
-pub fn solve() {
-    let input = parse_input(INPUT);
+#[cfg(feature = "std")]
+pub fn solve() -> Result<(), String> {
+    let input = normalize(INPUT.get()?);
+    let input = parse_input(&input);
 
     let result = solve_part_one(&input);
     println!("Part #1: {}", result);
 
     let result = solve_part_two(&input);
     println!("Part #2: {}", result);
+
+    #[cfg(feature = "visualize")]
+    common::svg::export_from_args(|| visualize::svg(INPUT.get()?));
+    Ok(())
 }
 
+pub const DAY: Day = Day {
+    day: 12,
+    sample: include_str!("../sample.TXT"),
+    part_one: implementations!(parse_input, solve_part_one),
+    part_two: implementations!(parse_input, solve_part_two),
+    generate: gen::generate,
+    inspect: |input| format!("{:#?}", parse_input(&normalize(input))),
+    commands: inspect::COMMANDS,
+    simulations: &[],
+};
+
+export_plugin!(DAY);
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let result = solve_part_one(&parse_input(INPUT));
+        let result =
+            solve_part_one(&parse_input(&normalize(INPUT.get().unwrap())));
 
         #[cfg(debug_assertions)]
         assert_eq!(result, 31);
@@ -123,13 +124,15 @@ mod tests {
 
     #[bench]
     fn bench_part_one(b: &mut test::Bencher) {
-        let input = parse_input(INPUT);
+        let input = normalize(INPUT.get().unwrap());
+        let input = parse_input(&input);
         b.iter(|| solve_part_one(&input));
     }
 
     #[test]
     fn test_part_two() {
-        let result = solve_part_two(&parse_input(INPUT));
+        let result =
+            solve_part_two(&parse_input(&normalize(INPUT.get().unwrap())));
 
         #[cfg(debug_assertions)]
         assert_eq!(result, 29);
@@ -140,7 +143,22 @@ mod tests {
 
     #[bench]
     fn bench_part_two(b: &mut test::Bencher) {
-        let input = parse_input(INPUT);
+        let input = normalize(INPUT.get().unwrap());
+        let input = parse_input(&input);
         b.iter(|| solve_part_two(&input));
     }
+
+    #[test]
+    fn test_generated_input_parses() {
+        for seed in 0..10 {
+            let input = parse_input(&gen::generate(seed, 40));
+            assert_eq!(input.heights.width() * input.heights.height(), 40 * 10);
+            assert!(route(&input, [input.start]).goal().is_some());
+        }
+    }
+
+    #[test]
+    fn test_sample_variants() {
+        common::input::assert_variants(&DAY, INPUT.get().unwrap());
+    }
 }
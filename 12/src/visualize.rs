@@ -0,0 +1,116 @@
+//! The heightmap as a picture, from deep blue at `a` to white at `z`, with
+//! the shortest route in red and the best signal in yellow.
+
+use crate::{lowest_squares, parse_input, route};
+use alloc::format;
+use alloc::string::{String, ToString};
+use common::geometry::{BoundingBox, Point};
+use common::grid::Pos;
+use common::image::{Image, Rgb};
+use common::input::normalize;
+use common::svg::Svg;
+
+const LOW: Rgb = Rgb(10, 30, 90);
+const HIGH: Rgb = Rgb(240, 240, 240);
+const ROUTE: Rgb = Rgb(230, 40, 40);
+const END: Rgb = Rgb(255, 220, 0);
+const HIKE: Rgb = Rgb(255, 140, 0);
+
+pub fn image(input: &str, part: u8) -> Result<Image, String> {
+    let input = parse_input(&normalize(input));
+    let search = match part {
+        1 => route(&input, [input.start]),
+        2 => route(&input, lowest_squares(&input)),
+        part => return Err(format!("no part {}", part)),
+    };
+    let mut image = Image::from_grid(&input.heights, |&h| {
+        LOW.blend(HIGH, (h - b'a') as u32, 25)
+    });
+    for (x, y) in search.goal_path().into_iter().flatten() {
+        image.set(x, y, ROUTE);
+    }
+    image.set(input.end.0, input.end.1, END);
+    Ok(image)
+}
+
+/// The heightmap with a layer per height, the shortest route from `S` in
+/// red and the shortest from any `a` in orange.
+pub fn svg(input: &str) -> Result<Svg, String> {
+    let input = parse_input(&normalize(input));
+    let point = |(x, y): Pos| Point::new(x as i64, y as i64);
+    let heights = &input.heights;
+    let corner = point((heights.width() - 1, heights.height() - 1));
+    let mut svg = Svg::new(BoundingBox::new(Point::ZERO).including(corner), 3);
+
+    for height in b'a'..=b'z' {
+        let color = LOW.blend(HIGH, (height - b'a') as u32, 25);
+        let layer = svg
+            .layer(&format!("height-{}", height as char))
+            .fill(&color.to_string())
+            .stroke("none", 0.0);
+        for (pos, _) in heights.iter().filter(|&(_, &h)| h == height) {
+            layer.cell(point(pos));
+        }
+    }
+    let routes = [
+        ("route", ROUTE, route(&input, [input.start]), "from S"),
+        (
+            "hike",
+            HIKE,
+            route(&input, lowest_squares(&input)),
+            "from any a",
+        ),
+    ];
+    for (id, color, search, _) in &routes {
+        if let Some(path) = search.goal_path() {
+            svg.layer(id)
+                .stroke(&color.to_string(), 3.0)
+                .polyline(path.into_iter().map(point));
+        }
+    }
+    for (y, (id, color, search, from)) in (-2..).zip(&routes) {
+        let label = match search.goal_distance() {
+            Some(steps) => format!("{} steps {}", steps, from),
+            None => format!("no route {}", from),
+        };
+        svg.layer(&format!("{}-label", id))
+            .fill(&color.to_string())
+            .text(Point::new(0, y), &label);
+    }
+    svg.layer("ends")
+        .fill(&END.to_string())
+        .stroke("none", 0.0)
+        .text(point(input.start), "S")
+        .text(point(input.end), "E");
+    Ok(svg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_image() {
+        let input = include_str!("../sample.TXT");
+        let map = image(input, 1).unwrap();
+        assert_eq!((map.width(), map.height()), (8, 5));
+        let route = (0..8)
+            .flat_map(|x| (0..5).map(move |y| (x, y)))
+            .filter(|&(x, y)| map.get(x, y) == Some(ROUTE))
+            .count();
+        assert_eq!(route, 31);
+        assert_eq!(map.get(5, 2), Some(END));
+        assert!(image(input, 0).is_err());
+    }
+
+    #[test]
+    fn test_svg() {
+        let svg = svg(include_str!("../sample.TXT")).unwrap().to_string();
+        assert_eq!(svg.matches("<rect").count(), 8 * 5);
+        assert_eq!(svg.matches("<g id=\"height-").count(), 26);
+        assert_eq!(svg.matches("<polyline").count(), 2);
+        assert!(svg.contains(">31 steps from S</text>"));
+        assert!(svg.contains(">29 steps from any a</text>"));
+        assert!(svg.contains("<text x=\"5\" y=\"2\" stroke=\"none\">E<"));
+    }
+}
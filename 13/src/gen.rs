@@ -0,0 +1,39 @@
+use super::Packet;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use common::rng::Rng;
+
+/// Generates `size` pairs of packets.
+///
+/// Packets that compare equal to a divider packet are redrawn, as they would
+/// make the decoder key ambiguous.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let dividers = [
+        Packet::parse("[[2]]").unwrap(),
+        Packet::parse("[[6]]").unwrap(),
+    ];
+    let mut packet = || loop {
+        let packet = random_list(&mut rng, 0);
+        if !dividers.contains(&Packet::parse(&packet).unwrap()) {
+            break packet;
+        }
+    };
+    (0..size.max(1))
+        .map(|_| format!("{}\n{}\n", packet(), packet()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn random_list(rng: &mut Rng, depth: usize) -> String {
+    let items = (0..rng.range(0..=5))
+        .map(|_| match depth < 4 && rng.chance(0.3) {
+            true => random_list(rng, depth + 1),
+            false => rng.range(0..=10).to_string(),
+        })
+        .collect::<Vec<_>>();
+    format!("[{}]", items.join(","))
+}
@@ -0,0 +1,83 @@
+use super::{parse_input, Packet};
+use alloc::{format, string::String};
+use common::input::normalize;
+use common::Command;
+use itertools::Itertools;
+
+pub const COMMANDS: &[Command] = &[
+    Command {
+        name: "pair",
+        usage: "pair <index>",
+        help: "show a pair of packets (1-based) and whether it is ordered",
+        run: pair,
+    },
+    Command {
+        name: "sorted",
+        usage: "sorted",
+        help: "list all packets and the dividers in order",
+        run: sorted,
+    },
+];
+
+fn pair(input: &str, args: &[&str]) -> Result<String, String> {
+    let idx = args
+        .first()
+        .ok_or("missing pair index")?
+        .parse::<usize>()
+        .map_err(|_| "invalid pair index")?;
+    let input = normalize(input);
+    let packets = parse_input(&input);
+    let pair = idx
+        .checked_sub(1)
+        .and_then(|i| packets.get(2 * i..2 * i + 2))
+        .ok_or_else(|| format!("no pair {}", idx))?;
+    let verdict = match pair[0] < pair[1] {
+        true => "right order",
+        false => "wrong order",
+    };
+    Ok(format!("{}\n{}\n{}", pair[0], pair[1], verdict))
+}
+
+fn sorted(input: &str, _args: &[&str]) -> Result<String, String> {
+    let dividers = [
+        Packet::parse("[[2]]").unwrap(),
+        Packet::parse("[[6]]").unwrap(),
+    ];
+    let input = normalize(input);
+    let packets = parse_input(&input);
+    Ok(packets
+        .iter()
+        .chain(dividers.iter())
+        .sorted()
+        .enumerate()
+        .map(|(i, p)| match dividers.contains(p) {
+            true => format!("{:>4} {}  <- divider", i + 1, p),
+            false => format!("{:>4} {}", i + 1, p),
+        })
+        .join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pair() {
+        let input = include_str!("../sample.TXT");
+        assert_eq!(
+            pair(input, &["2"]).unwrap(),
+            "[[1],[2,3,4]]\n[[1],4]\nright order"
+        );
+        assert!(pair(input, &["3"]).unwrap().ends_with("wrong order"));
+        assert!(pair(input, &["0"]).is_err());
+        assert!(pair(input, &["9"]).is_err());
+    }
+
+    #[test]
+    fn test_sorted() {
+        let output = sorted(include_str!("../sample.TXT"), &[]).unwrap();
+        assert_eq!(output.lines().count(), 18);
+        assert!(output.contains("  10 [[2]]  <- divider"));
+        assert!(output.contains("  14 [[6]]  <- divider"));
+    }
+}
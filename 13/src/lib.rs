@@ -1,15 +1,24 @@
-#![feature(array_chunks)]
-#![feature(test)]
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(test, feature(test))]
+extern crate alloc;
+#[cfg(test)]
 extern crate test;
 
-use itertools::Itertools;
-use std::{cmp::Ordering, iter::Peekable};
+mod gen;
+mod inspect;
 
-#[cfg(debug_assertions)]
-const INPUT: &str = include_str!("../sample.TXT");
+use alloc::{format, string::String, vec, vec::Vec};
+use common::input::normalize;
+use common::{export_plugin, implementations, Day};
+use core::{cmp::Ordering, fmt, iter::Peekable};
+use itertools::Itertools;
 
-#[cfg(not(debug_assertions))]
-const INPUT: &str = include_str!("../input.TXT");
+#[cfg(all(feature = "std", debug_assertions))]
+static INPUT: common::input::PuzzleInput =
+    common::input::PuzzleInput::sample(include_str!("../sample.TXT"));
+#[cfg(all(feature = "std", not(debug_assertions)))]
+static INPUT: common::input::PuzzleInput =
+    common::input::PuzzleInput::personal(env!("CARGO_MANIFEST_DIR"));
 
 type ParsedInput = Vec<Packet>;
 
@@ -21,9 +30,10 @@ enum Packet {
 
 fn solve_part_one(input: &ParsedInput) -> usize {
     input
-        .array_chunks::<2>()
+        .iter()
+        .tuples()
         .enumerate()
-        .filter_map(|(i, [lhs, rhs])| (lhs < rhs).then_some(i + 1))
+        .filter_map(|(i, (lhs, rhs))| (lhs < rhs).then_some(i + 1))
         .sum()
 }
 
@@ -45,16 +55,32 @@ fn parse_input(input: &str) -> ParsedInput {
     input.lines().filter_map(Packet::parse).collect()
 }
 
-pub fn solve() {
-    let input = parse_input(INPUT);
+#[cfg(feature = "std")]
+pub fn solve() -> Result<(), String> {
+    let input = normalize(INPUT.get()?);
+    let input = parse_input(&input);
 
     let result = solve_part_one(&input);
     println!("Part #1: {}", result);
 
     let result = solve_part_two(&input);
     println!("Part #2: {}", result);
+    Ok(())
 }
 
+pub const DAY: Day = Day {
+    day: 13,
+    sample: include_str!("../sample.TXT"),
+    part_one: implementations!(parse_input, solve_part_one),
+    part_two: implementations!(parse_input, solve_part_two),
+    generate: gen::generate,
+    inspect: |input| format!("{:#?}", parse_input(&normalize(input))),
+    commands: inspect::COMMANDS,
+    simulations: &[],
+};
+
+export_plugin!(DAY);
+
 impl Packet {
     pub fn parse(input: &str) -> Option<Self> {
         let mut chars = input.chars().peekable();
@@ -90,6 +116,15 @@ impl Packet {
     }
 }
 
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Packet::Integer(n) => write!(f, "{}", n),
+            Packet::List(list) => write!(f, "[{}]", list.iter().join(",")),
+        }
+    }
+}
+
 impl Eq for Packet {}
 impl PartialEq for Packet {
     fn eq(&self, other: &Self) -> bool {
@@ -98,7 +133,7 @@ impl PartialEq for Packet {
 }
 
 impl PartialOrd for Packet {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
@@ -140,7 +175,8 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = solve_part_one(&parse_input(INPUT));
+        let result =
+            solve_part_one(&parse_input(&normalize(INPUT.get().unwrap())));
 
         #[cfg(debug_assertions)]
         assert_eq!(result, 13);
@@ -151,13 +187,15 @@ mod tests {
 
     #[bench]
     fn bench_part_one(b: &mut test::Bencher) {
-        let input = parse_input(INPUT);
+        let input = normalize(INPUT.get().unwrap());
+        let input = parse_input(&input);
         b.iter(|| solve_part_one(&input));
     }
 
     #[test]
     fn test_part_two() {
-        let result = solve_part_two(&parse_input(INPUT));
+        let result =
+            solve_part_two(&parse_input(&normalize(INPUT.get().unwrap())));
 
         #[cfg(debug_assertions)]
         assert_eq!(result, 140);
@@ -168,7 +206,21 @@ mod tests {
 
     #[bench]
     fn bench_part_two(b: &mut test::Bencher) {
-        let input = parse_input(INPUT);
+        let input = normalize(INPUT.get().unwrap());
+        let input = parse_input(&input);
         b.iter(|| solve_part_two(&input));
     }
+
+    #[test]
+    fn test_generated_input_parses() {
+        for seed in 0..10 {
+            let input = parse_input(&gen::generate(seed, 50));
+            assert_eq!(input.len(), 100);
+        }
+    }
+
+    #[test]
+    fn test_sample_variants() {
+        common::input::assert_variants(&DAY, INPUT.get().unwrap());
+    }
 }
@@ -0,0 +1,67 @@
+use super::{Cave, SOURCE};
+use alloc::{format, string::String, vec, vec::Vec};
+use common::geometry::Point;
+use common::rng::Rng;
+
+/// Generates `size` rock paths below the sand source.
+///
+/// Layouts where part one's sand would pile up to the source without ever
+/// reaching the abyss are redrawn.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let paths = loop {
+        let paths = (0..size.max(1))
+            .map(|_| random_path(&mut rng, 10 + size as i64))
+            .collect::<Vec<_>>();
+        if reaches_abyss(&paths) {
+            break paths;
+        }
+    };
+    paths
+        .iter()
+        .map(|path| {
+            let points = path
+                .iter()
+                .map(|point| format!("{},{}", point.x, point.y))
+                .collect::<Vec<_>>();
+            points.join(" -> ") + "\n"
+        })
+        .collect()
+}
+
+fn random_path(rng: &mut Rng, depth: i64) -> Vec<Point> {
+    let mut point =
+        Point::new(rng.range(500 - depth..=500 + depth), rng.range(2..=depth));
+    let mut path = vec![point];
+    let horizontal = rng.chance(0.5);
+    for i in 0..rng.range(1..=4) {
+        let step = rng.range(1..=8) * if rng.chance(0.5) { 1 } else { -1 };
+        point = match (i % 2 == 0) == horizontal {
+            true => Point::new(point.x + step, point.y),
+            false => Point::new(point.x, (point.y + step).clamp(2, depth)),
+        };
+        if path.last() != Some(&point) {
+            path.push(point);
+        }
+    }
+    if path.len() == 1 {
+        path.push(point + Point::new(1, 0));
+    }
+    path
+}
+
+/// Plays part one, bailing out if the source gets blocked first.
+fn reaches_abyss(paths: &[Vec<Point>]) -> bool {
+    let (w, h) = paths
+        .iter()
+        .flatten()
+        .fold((0, 0), |(mx, my), p| (p.x.max(mx), p.y.max(my)));
+    let mut cave = Cave::new(paths, h);
+    loop {
+        match cave.drop_sand(SOURCE) {
+            Point { x, y } if x >= w || y >= h => return true,
+            SOURCE => return false,
+            point => cave.fill(point),
+        };
+    }
+}
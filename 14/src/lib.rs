@@ -1,113 +1,315 @@
-#![feature(test)]
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(test, feature(test))]
+extern crate alloc;
+#[cfg(test)]
 extern crate test;
 
-use itertools::Itertools;
-use std::collections::BTreeSet;
+mod gen;
+#[cfg(feature = "visualize")]
+pub mod visualize;
 
-#[cfg(debug_assertions)]
-const INPUT: &str = include_str!("../sample.TXT");
+use alloc::string::ToString;
+use alloc::{format, vec::Vec};
+use common::context::{Cancelled, Context};
+use common::geometry::{BoundingBox, Point, Vec2};
+use common::grid::{Grid, Pos};
+use common::input::normalize;
+use common::parse;
+use common::simulation::{playback, Simulation, Simulator};
+use common::{export_plugin, implementations, Day};
+use core::fmt;
+use itertools::Itertools;
 
-#[cfg(not(debug_assertions))]
-const INPUT: &str = include_str!("../input.TXT");
+#[cfg(all(feature = "std", debug_assertions))]
+static INPUT: common::input::PuzzleInput =
+    common::input::PuzzleInput::sample(include_str!("../sample.TXT"));
+#[cfg(all(feature = "std", not(debug_assertions)))]
+static INPUT: common::input::PuzzleInput =
+    common::input::PuzzleInput::personal(env!("CARGO_MANIFEST_DIR"));
 
 type ParsedInput = Vec<Vec<Point>>;
-type Point = (i32, i32);
 
-fn drop_sand(cave: &BTreeSet<Point>, x: i32, y: i32, h: i32) -> Point {
-    for (nx, ny) in [(0, 1), (-1, 1), (1, 1)].map(|(dx, dy)| (x + dx, y + dy)) {
-        if ny > h {
-            break;
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Tile {
+    Air,
+    Rock,
+    Sand,
+}
+
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let c = match self {
+            Tile::Air => '.',
+            Tile::Rock => '#',
+            Tile::Sand => 'o',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+const SOURCE: Point = Point::new(500, 0);
+
+/// The slice of the cave sand can reach when it rests no deeper than
+/// `depth`, with `bounds` the cave coordinates it covers.
+#[derive(Clone)]
+struct Cave {
+    tiles: Grid<Tile>,
+    bounds: BoundingBox,
+}
+
+impl Cave {
+    fn new(paths: &[Vec<Point>], depth: i64) -> Self {
+        let rocks = paths.iter().flatten().copied();
+        let bounds = BoundingBox::from_points(rocks.chain([SOURCE]))
+            .unwrap()
+            // Sand spreads at most one column per row on each side.
+            .including(SOURCE + Vec2::new(-depth, depth))
+            .including(SOURCE + Vec2::new(depth, depth))
+            .expand(1);
+        let bounds = BoundingBox {
+            min: Point::new(bounds.min.x, 0),
+            max: Point::new(bounds.max.x, depth),
+        };
+        let mut cave = Self {
+            tiles: Grid::new(
+                bounds.width() as usize,
+                bounds.height() as usize,
+                Tile::Air,
+            ),
+            bounds,
+        };
+        for path in paths {
+            cave.add_rock(path);
         }
-        if cave.contains(&(nx, ny)) {
-            continue;
+        cave
+    }
+
+    fn pos(&self, point: Point) -> Pos {
+        let offset = point - self.bounds.min;
+        (offset.x as usize, offset.y as usize)
+    }
+
+    fn point(&self, (x, y): Pos) -> Point {
+        self.bounds.min + Vec2::new(x as i64, y as i64)
+    }
+
+    fn add_rock(&mut self, points: &[Point]) {
+        for line in points.windows(2) {
+            let (mut point, end) = (line[0], line[1]);
+            loop {
+                if self.bounds.contains(point) {
+                    let pos = self.pos(point);
+                    self.tiles[pos] = Tile::Rock;
+                }
+                if point == end {
+                    break;
+                }
+                point = point.step_toward(end);
+            }
         }
-        return drop_sand(cave, nx, ny, h);
     }
-    (x, y)
-}
 
-fn add_rock(mut cave: BTreeSet<Point>, points: &[Point]) -> BTreeSet<Point> {
-    for point in points.windows(2) {
-        let ((sx, sy), (ex, ey)) = (point[0], point[1]);
-        let (sx, ex) = (sx.min(ex), sx.max(ex));
-        let (sy, ey) = (sy.min(ey), sy.max(ey));
-        for y in sy..=ey {
-            for x in sx..=ex {
-                cave.insert((x, y));
+    /// Where a unit of sand poured at `from` comes to rest.
+    fn drop_sand(&self, from: Point) -> Point {
+        let mut pos = self.pos(from);
+        while pos.1 + 1 < self.tiles.height() {
+            let next = [0, -1, 1]
+                .into_iter()
+                .filter_map(|dx| self.tiles.step(pos, (dx, 1)))
+                .find(|&next| self.tiles[next] == Tile::Air);
+            match next {
+                Some(next) => pos = next,
+                None => break,
             }
         }
+        self.point(pos)
+    }
+
+    fn fill(&mut self, point: Point) {
+        let pos = self.pos(point);
+        self.tiles[pos] = Tile::Sand;
     }
-    cave
 }
 
-fn solve_part_one(input: &ParsedInput) -> usize {
-    let (w, h) = input
-        .iter()
-        .flatten()
-        .fold((0, 0), |(mx, my), &(x, y)| (mx.max(x), my.max(y)));
-    let mut cave = input.iter().fold(BTreeSet::new(), |c, r| add_rock(c, r));
-    let mut count = 0;
-    loop {
-        match drop_sand(&cave, 500, 0, h) {
-            (x, y) if x >= w || y >= h => break count,
-            (x, y) => {
-                count += 1;
-                cave.insert((x, y));
-            }
+/// What stops the sand.
+#[derive(Debug, Copy, Clone)]
+enum Bottom {
+    /// Sand coming to rest at or past `limit`, on either axis, has fallen
+    /// past every rock.
+    Abyss { limit: Point },
+    /// A floor under the cave, sand piles up until it blocks the source.
+    Floor,
+}
+
+/// Sand poured from the source, one unit per step until no more can rest.
+#[derive(Clone)]
+struct Sandfall {
+    cave: Cave,
+    bottom: Bottom,
+    settled: usize,
+    over: bool,
+}
+
+impl Sandfall {
+    fn abyss(paths: &[Vec<Point>]) -> Self {
+        let (w, h) = paths
+            .iter()
+            .flatten()
+            .fold((0, 0), |(mx, my), p| (p.x.max(mx), p.y.max(my)));
+        Self::new(
+            Cave::new(paths, h),
+            Bottom::Abyss {
+                limit: Point::new(w, h),
+            },
+        )
+    }
+
+    fn floor(paths: &[Vec<Point>]) -> Self {
+        let h = paths.iter().flatten().fold(0, |my, p| p.y.max(my));
+        Self::new(Cave::new(paths, h + 1), Bottom::Floor)
+    }
+
+    fn new(cave: Cave, bottom: Bottom) -> Self {
+        Self {
+            cave,
+            bottom,
+            settled: 0,
+            over: false,
         }
     }
 }
 
-fn solve_part_two(input: &ParsedInput) -> usize {
-    let h = input.iter().flatten().fold(0, |my, &(_, y)| my.max(y));
-    let mut cave = input.iter().fold(BTreeSet::new(), |t, p| add_rock(t, p));
-    let mut count = 0;
-    loop {
-        match drop_sand(&cave, 500, 0, h + 1) {
-            (500, 0) => break count + 1,
-            (x, y) => {
-                count += 1;
-                cave.insert((x, y));
+impl Simulation for Sandfall {
+    type Snapshot = Self;
+
+    fn step(&mut self) -> bool {
+        if self.over {
+            return false;
+        }
+        let point = self.cave.drop_sand(SOURCE);
+        match self.bottom {
+            Bottom::Abyss { limit }
+                if point.x >= limit.x || point.y >= limit.y =>
+            {
+                self.over = true;
+                return false;
             }
+            Bottom::Floor if point == SOURCE => self.over = true,
+            _ => (),
+        }
+        self.cave.fill(point);
+        self.settled += 1;
+        true
+    }
+
+    fn steps(&self) -> usize {
+        self.settled
+    }
+
+    fn snapshot(&self) -> Self {
+        self.clone()
+    }
+
+    fn restore(&mut self, snapshot: &Self) {
+        self.clone_from(snapshot);
+    }
+}
+
+/// The cave like in the puzzle, `+` for the source.
+impl fmt::Display for Sandfall {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut map = self.cave.tiles.map(Tile::to_string);
+        let source = self.cave.pos(SOURCE);
+        if self.cave.tiles[source] == Tile::Air {
+            map[source] = "+".to_string();
         }
+        write!(f, "{}\n{} units of sand", map, self.settled)
     }
 }
 
+fn solve_part_one(
+    input: &ParsedInput,
+    context: &Context,
+) -> Result<usize, Cancelled> {
+    Sandfall::abyss(input).run_in(context)
+}
+
+fn solve_part_two(
+    input: &ParsedInput,
+    context: &Context,
+) -> Result<usize, Cancelled> {
+    Sandfall::floor(input).run_in(context)
+}
+
 fn parse_input(input: &str) -> ParsedInput {
     input
         .lines()
         .map(|line| {
-            line.split("->")
-                .map(|points| {
-                    points
-                        .trim()
-                        .split(',')
-                        .flat_map(|s| s.parse().ok())
-                        .collect_tuple()
-                        .unwrap()
-                })
+            parse::numbers(line)
+                .tuples()
+                .map(|(x, y)| Point::new(x, y))
                 .collect()
         })
         .collect()
 }
 
-pub fn solve() {
-    let input = parse_input(INPUT);
+#[cfg(feature = "std")]
+pub fn solve() -> Result<(), String> {
+    let input = normalize(INPUT.get()?);
+    let input = parse_input(&input);
 
-    let result = solve_part_one(&input);
+    let result = solve_part_one(&input, &Context::new()).unwrap();
     println!("Part #1: {}", result);
 
-    let result = solve_part_two(&input);
+    let result = solve_part_two(&input, &Context::new()).unwrap();
     println!("Part #2: {}", result);
+
+    #[cfg(feature = "visualize")]
+    common::svg::export_from_args(|| visualize::svg(INPUT.get()?));
+    Ok(())
 }
 
+pub const DAY: Day = Day {
+    day: 14,
+    sample: include_str!("../sample.TXT"),
+    part_one: implementations!(parse_input, with_context solve_part_one),
+    part_two: implementations!(parse_input, with_context solve_part_two),
+    generate: gen::generate,
+    inspect: |input| format!("{:#?}", parse_input(&normalize(input))),
+    commands: &[],
+    simulations: &[
+        Simulator {
+            name: "abyss",
+            help: "sand falling until it flows into the abyss",
+            start: |input| {
+                let input = parse_input(&normalize(input));
+                Ok(playback(Sandfall::abyss(&input)))
+            },
+        },
+        Simulator {
+            name: "floor",
+            help: "sand piling up on the floor until it blocks the source",
+            start: |input| {
+                let input = parse_input(&normalize(input));
+                Ok(playback(Sandfall::floor(&input)))
+            },
+        },
+    ],
+};
+
+export_plugin!(DAY);
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let result = solve_part_one(&parse_input(INPUT));
+        let result = solve_part_one(
+            &parse_input(&normalize(INPUT.get().unwrap())),
+            &Context::new(),
+        )
+        .unwrap();
 
         #[cfg(debug_assertions)]
         assert_eq!(result, 24);
@@ -118,13 +320,18 @@ mod tests {
 
     #[bench]
     fn bench_part_one(b: &mut test::Bencher) {
-        let input = parse_input(INPUT);
-        b.iter(|| solve_part_one(&input));
+        let input = normalize(INPUT.get().unwrap());
+        let input = parse_input(&input);
+        b.iter(|| solve_part_one(&input, &Context::new()));
     }
 
     #[test]
     fn test_part_two() {
-        let result = solve_part_two(&parse_input(INPUT));
+        let result = solve_part_two(
+            &parse_input(&normalize(INPUT.get().unwrap())),
+            &Context::new(),
+        )
+        .unwrap();
 
         #[cfg(debug_assertions)]
         assert_eq!(result, 93);
@@ -135,7 +342,48 @@ mod tests {
 
     #[bench]
     fn bench_part_two(b: &mut test::Bencher) {
-        let input = parse_input(INPUT);
-        b.iter(|| solve_part_two(&input));
+        let input = normalize(INPUT.get().unwrap());
+        let input = parse_input(&input);
+        b.iter(|| solve_part_two(&input, &Context::new()));
+    }
+
+    #[test]
+    fn test_cancelled() {
+        let context = Context::new();
+        context.cancel();
+        let input = parse_input(&normalize(INPUT.get().unwrap()));
+        assert_eq!(solve_part_one(&input, &context), Err(Cancelled));
+        assert_eq!(solve_part_two(&input, &context), Err(Cancelled));
+    }
+
+    #[test]
+    fn test_sandfall_replay() {
+        let mut sand =
+            (DAY.simulations[0].start)(INPUT.get().unwrap()).unwrap();
+        assert!(sand.seek(5));
+        assert!(sand.render().ends_with(
+            "..........o.#........\n\
+             ........oooo#........\n\
+             ....#########........\n\
+             5 units of sand"
+        ));
+        assert!(!sand.seek(25));
+        assert_eq!(sand.steps(), 24);
+        assert!(sand.seek(0));
+        assert!(sand.render().starts_with("..........+..........\n"));
+    }
+
+    #[test]
+    fn test_generated_input_parses() {
+        for seed in 0..10 {
+            let input = parse_input(&gen::generate(seed, 20));
+            assert_eq!(input.len(), 20);
+            solve_part_one(&input, &Context::new()).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_sample_variants() {
+        common::input::assert_variants(&DAY, INPUT.get().unwrap());
     }
 }
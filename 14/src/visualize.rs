@@ -0,0 +1,132 @@
+//! The cave as pictures: rock in gray, resting sand in yellow and the
+//! source in white.
+
+use crate::{parse_input, Sandfall, Tile, SOURCE};
+use alloc::string::{String, ToString};
+use alloc::{format, vec::Vec};
+use common::geometry::{BoundingBox, Point};
+use common::image::{Animation, Image, Rgb};
+use common::input::normalize;
+use common::simulation::Simulation;
+use common::svg::Svg;
+
+const AIR: Rgb = Rgb(20, 16, 14);
+const ROCK: Rgb = Rgb(120, 110, 100);
+const SAND: Rgb = Rgb(230, 190, 90);
+const SOURCE_COLOR: Rgb = Rgb::WHITE;
+
+fn sandfall(input: &str, part: u8) -> Result<Sandfall, String> {
+    let input = parse_input(&normalize(input));
+    match part {
+        1 => Ok(Sandfall::abyss(&input)),
+        2 => Ok(Sandfall::floor(&input)),
+        part => Err(format!("no part {}", part)),
+    }
+}
+
+/// The cave once no more sand can rest.
+pub fn image(input: &str, part: u8) -> Result<Image, String> {
+    let mut sandfall = sandfall(input, part)?;
+    sandfall.run();
+    Ok(frame(&sandfall))
+}
+
+/// The sand piling up, a frame every `every` units.
+pub fn animation(
+    input: &str,
+    part: u8,
+    every: usize,
+) -> Result<Animation, String> {
+    Animation::record(&mut sandfall(input, part)?, every, 4, frame)
+}
+
+fn frame(sandfall: &Sandfall) -> Image {
+    let cave = &sandfall.cave;
+    let mut image = Image::from_grid(&cave.tiles, |tile| match tile {
+        Tile::Air => AIR,
+        Tile::Rock => ROCK,
+        Tile::Sand => SAND,
+    });
+    let (x, y) = cave.pos(SOURCE);
+    if cave.tiles[(x, y)] == Tile::Air {
+        image.set(x, y, SOURCE_COLOR);
+    }
+    image
+}
+
+/// The rock paths as lines and the sand resting once it starts falling
+/// into the abyss.
+pub fn svg(input: &str) -> Result<Svg, String> {
+    let paths = parse_input(&normalize(input));
+    let mut sandfall = Sandfall::abyss(&paths);
+    sandfall.run();
+    let cave = &sandfall.cave;
+    let sand = cave
+        .tiles
+        .iter()
+        .filter(|&(_, &tile)| tile == Tile::Sand)
+        .map(|(pos, _)| cave.point(pos))
+        .collect::<Vec<_>>();
+    let view = BoundingBox::from_points(paths.iter().flatten().copied())
+        .ok_or("no rock")?
+        .including(SOURCE);
+    let view = sand.iter().fold(view, |view, &p| view.including(p));
+    let mut svg = Svg::new(view, 2);
+
+    let rock = svg.layer("rock").stroke(&ROCK.to_string(), 4.0);
+    for path in &paths {
+        rock.polyline(path.iter().copied());
+    }
+    let resting = svg
+        .layer("sand")
+        .fill(&SAND.to_string())
+        .stroke("none", 0.0);
+    for &point in &sand {
+        resting.circle(point, 0.45);
+    }
+    svg.layer("source")
+        .fill("black")
+        .stroke("none", 0.0)
+        .font_size(1.5)
+        .circle(SOURCE, 0.3)
+        .text(
+            Point::new(view.min.x, view.min.y - 1),
+            &format!("{} units of sand come to rest", sandfall.settled),
+        );
+    Ok(svg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_images() {
+        let input = include_str!("../sample.TXT");
+        let cave = image(input, 1).unwrap();
+        let count = |image: &Image, color| {
+            (0..image.width())
+                .flat_map(|x| (0..image.height()).map(move |y| (x, y)))
+                .filter(|&(x, y)| image.get(x, y) == Some(color))
+                .count()
+        };
+        assert_eq!(count(&cave, SAND), 24);
+        assert_eq!(count(&cave, SOURCE_COLOR), 1);
+        assert_eq!(count(&image(input, 2).unwrap(), SAND), 93);
+
+        let animation = animation(input, 1, 5).unwrap();
+        assert_eq!(animation.frames().len(), 1 + 5);
+        assert_eq!(animation.frames().last(), Some(&cave));
+        assert!(animation.to_gif().is_ok());
+        assert!(image(input, 0).is_err());
+    }
+
+    #[test]
+    fn test_svg() {
+        let svg = svg(include_str!("../sample.TXT")).unwrap().to_string();
+        assert_eq!(svg.matches("<polyline").count(), 2);
+        assert_eq!(svg.matches("<circle").count(), 24 + 1);
+        assert!(svg.contains("points=\"498,4 498,6 496,6\""));
+        assert!(svg.contains(">24 units of sand come to rest</text>"));
+    }
+}
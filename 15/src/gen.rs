@@ -0,0 +1,72 @@
+use super::UPPER_BOUND;
+use alloc::{format, string::String, vec, vec::Vec};
+use common::geometry::Point;
+use common::rng::Rng;
+
+/// Generates `size` sensors plus one in each corner of the search area,
+/// leaving exactly one uncovered position inside it.
+///
+/// The beacons on either side of the gap are candidates for every sensor,
+/// so none reaches the gap. The corner sensors report one of those two,
+/// which is all it takes for them to cover their quadrant around the gap:
+/// the other beacons are placed out of their reach, outside the search
+/// area. Every sensor reports its closest beacon, and sensors with two
+/// closest beacons are sampled again.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let n = UPPER_BOUND as i64;
+    let gap = Point::new(rng.range(2..=n - 2), rng.range(2..=n - 2));
+
+    let corners =
+        [(0, 0), (n, 0), (0, n), (n, n)].map(|(x, y)| Point::new(x, y));
+    let reach = |sensor: Point| sensor.manhattan(gap) - 1;
+    let mut beacons = vec![gap - Point::new(1, 0), gap + Point::new(1, 0)];
+    while beacons.len() < size / 4 + 2 {
+        let beacon = Point::new(rng.range(-n..=2 * n), rng.range(-n..=2 * n));
+        if corners
+            .iter()
+            .all(|&corner| corner.manhattan(beacon) > reach(corner))
+        {
+            beacons.push(beacon);
+        }
+    }
+
+    let mut sensors = corners
+        .map(|corner| (corner, closest(corner, &beacons).unwrap()))
+        .to_vec();
+    while sensors.len() < size + 4 {
+        let sensor = Point::new(rng.range(0..=n), rng.range(0..=n));
+        let Some(beacon) = closest(sensor, &beacons) else {
+            continue;
+        };
+        let range = sensor.manhattan(beacon);
+        if range > 0 && range < sensor.manhattan(gap) {
+            sensors.push((sensor, beacon));
+        }
+    }
+    rng.shuffle(&mut sensors);
+
+    sensors
+        .iter()
+        .map(|(sensor, beacon)| {
+            format!(
+                "Sensor at x={}, y={}: closest beacon is at x={}, y={}\n",
+                sensor.x, sensor.y, beacon.x, beacon.y
+            )
+        })
+        .collect()
+}
+
+/// The beacon closest to `sensor`, `None` if two are.
+fn closest(sensor: Point, beacons: &[Point]) -> Option<Point> {
+    let mut distances: Vec<_> = beacons
+        .iter()
+        .map(|&beacon| (sensor.manhattan(beacon), beacon))
+        .collect();
+    distances.sort_unstable_by_key(|&(distance, _)| distance);
+    match distances[..] {
+        [(d0, _), (d1, _), ..] if d0 == d1 => None,
+        [(_, beacon), ..] => Some(beacon),
+        [] => None,
+    }
+}
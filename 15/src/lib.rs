@@ -1,124 +1,170 @@
-#![feature(test)]
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(test, feature(test))]
+extern crate alloc;
+#[cfg(test)]
 extern crate test;
 
+mod gen;
+#[cfg(feature = "visualize")]
+pub mod visualize;
+
+use alloc::collections::BTreeSet;
+use alloc::{format, vec::Vec};
+use common::context::{Cancelled, Context};
+use common::geometry::Point;
+use common::input::normalize;
+use common::interval::{Interval, RangeSet};
+use common::{export_plugin, implementations, scan, Day};
 use itertools::Itertools;
-use std::collections::BTreeSet;
 
-#[cfg(debug_assertions)]
-const INPUT: &str = include_str!("../sample.TXT");
-#[cfg(not(debug_assertions))]
-const INPUT: &str = include_str!("../input.TXT");
+#[cfg(all(feature = "std", debug_assertions))]
+static INPUT: common::input::PuzzleInput =
+    common::input::PuzzleInput::sample(include_str!("../sample.TXT"));
+#[cfg(all(feature = "std", not(debug_assertions)))]
+static INPUT: common::input::PuzzleInput =
+    common::input::PuzzleInput::personal(env!("CARGO_MANIFEST_DIR"));
 
 #[cfg(debug_assertions)]
-const ROW: isize = 10;
+const ROW: i64 = 10;
 #[cfg(not(debug_assertions))]
-const ROW: isize = 2_000_000;
+const ROW: i64 = 2_000_000;
 
 #[cfg(debug_assertions)]
 const UPPER_BOUND: usize = 20;
 #[cfg(not(debug_assertions))]
 const UPPER_BOUND: usize = 4_000_000;
 
-type Point = (isize, isize);
 type ParsedInput = Vec<Point>;
 
+/// Positions of the row where a beacon cannot be: covered by a sensor and
+/// not a known beacon.
 fn solve_part_one(input: &ParsedInput) -> usize {
-    let mut scanned = scan(input, ROW).iter().fold(
-        BTreeSet::new(),
-        |mut s, &(start, end)| {
-            (start..=end).for_each(|x| {
-                s.insert(x);
-            });
-            s
-        },
-    );
-    input
+    let beacons = input
         .iter()
         .tuples()
-        .filter_map(|(_, (x, y))| (*y == ROW).then_some(x))
-        .for_each(|x| {
-            scanned.remove(x);
-        });
-    scanned.len()
+        .filter_map(|(_, beacon)| {
+            (beacon.y == ROW).then_some(Interval::point(beacon.x))
+        })
+        .collect::<RangeSet<_>>();
+    scan(input, ROW).difference(&beacons).len() as usize
+}
+
+/// Brute force, sharing nothing with [`scan`]: every position of the row
+/// within reach of a sensor, one at a time, less the known beacons.
+fn solve_part_one_set(input: &ParsedInput) -> usize {
+    let mut covered = BTreeSet::new();
+    for (sensor, beacon) in input.iter().tuples() {
+        let reach = (sensor.x - beacon.x).abs() + (sensor.y - beacon.y).abs();
+        let left = reach - (ROW - sensor.y).abs();
+        for x in sensor.x - left..=sensor.x + left {
+            covered.insert(x);
+        }
+    }
+    for (_, beacon) in input.iter().tuples() {
+        if beacon.y == ROW {
+            covered.remove(&beacon.x);
+        }
+    }
+    covered.len()
 }
 
-fn solve_part_two(input: &ParsedInput) -> usize {
-    for y in 0..=UPPER_BOUND {
-        let ranges = merge(&scan(input, y as isize), UPPER_BOUND);
-        if let &[(_, x), _] = &ranges[..] {
-            return (x + 1) * 4_000_000usize + y;
+fn solve_part_two(
+    input: &ParsedInput,
+    context: &Context,
+) -> Result<usize, Cancelled> {
+    let beacon = distress_beacon(input, context)?.unwrap();
+    Ok(beacon.x as usize * 4_000_000 + beacon.y as usize)
+}
+
+/// The one position of the search area out of reach of every sensor,
+/// reporting the rows scanned to `context`.
+fn distress_beacon(
+    input: &ParsedInput,
+    context: &Context,
+) -> Result<Option<Point>, Cancelled> {
+    let area = RangeSet::from(Interval::new(0, UPPER_BOUND as i64));
+    context.set_total(UPPER_BOUND as u64 + 1);
+    for y in 0..=UPPER_BOUND as i64 {
+        context.check()?;
+        context.set_done(y as u64);
+        let uncovered = area.difference(&scan(input, y));
+        if let Some(gap) = uncovered.intervals().first() {
+            return Ok(Some(Point::new(gap.start, y)));
         }
     }
-    unreachable!()
+    Ok(None)
 }
 
 fn parse_input(input: &str) -> ParsedInput {
     input
         .lines()
         .flat_map(|line| {
-            line.split(|c: char| c.is_ascii_punctuation() && c != '-')
-                .filter_map(|s| s.parse().ok())
-                .tuples()
-                .collect_vec()
+            let (sx, sy, bx, by) = scan!(
+                line,
+                "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+                i64,
+                i64,
+                i64,
+                i64
+            )
+            .unwrap();
+            [Point::new(sx, sy), Point::new(bx, by)]
         })
         .collect()
 }
 
-pub fn solve() {
-    let input = parse_input(INPUT);
+#[cfg(feature = "std")]
+pub fn solve() -> Result<(), String> {
+    let input = normalize(INPUT.get()?);
+    let input = parse_input(&input);
 
     let result = solve_part_one(&input);
     println!("Part #1: {}", result);
 
-    let result = solve_part_two(&input);
+    let result = solve_part_two(&input, &Context::new()).unwrap();
     println!("Part #2: {}", result);
-}
 
-fn clamp(range: (isize, isize), max: usize) -> (usize, usize) {
-    let low = range.0.max(0) as usize;
-    let high = (range.1 as usize).min(max);
-    (low, high)
+    #[cfg(feature = "visualize")]
+    common::svg::export_from_args(|| visualize::svg(INPUT.get()?));
+    Ok(())
 }
 
-fn intersects(a: (usize, usize), b: (usize, usize)) -> bool {
-    a.0 <= b.1 && b.0 <= a.1
-}
+pub const DAY: Day = Day {
+    day: 15,
+    sample: include_str!("../sample.TXT"),
+    part_one: implementations!(parse_input;
+        "ranges" => solve_part_one,
+        "set" => solve_part_one_set,
+    ),
+    part_two: implementations!(parse_input, with_context solve_part_two),
+    generate: gen::generate,
+    inspect: |input| format!("{:#?}", parse_input(&normalize(input))),
+    commands: &[],
+    simulations: &[],
+};
+
+export_plugin!(DAY);
 
-fn scan(points: &[Point], row: isize) -> Vec<(isize, isize)> {
+/// The positions of `row` within reach of a sensor.
+fn scan(points: &[Point], row: i64) -> RangeSet<i64> {
     points
         .iter()
         .tuples()
-        .filter_map(|(&(sx, sy), &(bx, by))| {
-            let d = isize::abs(sx - bx) + isize::abs(sy - by);
-            let t = d - isize::abs(row - sy);
-            (t > 0).then_some((sx - t, sx + t))
+        .filter_map(|(&sensor, &beacon)| {
+            let t = sensor.manhattan(beacon) - i64::abs(row - sensor.y);
+            (t >= 0).then(|| Interval::new(sensor.x - t, sensor.x + t))
         })
         .collect()
 }
 
-fn merge(ranges: &[(isize, isize)], upper_bound: usize) -> Vec<(usize, usize)> {
-    ranges.iter().map(|&r| clamp(r, upper_bound)).sorted().fold(
-        vec![],
-        |mut merged, (low, high)| {
-            if let Some((ml, mh)) = merged.last_mut() {
-                if intersects((low, high), (*ml, *mh)) {
-                    *mh = usize::max(*mh, high);
-                    return merged;
-                }
-            }
-            merged.push((low, high));
-            merged
-        },
-    )
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let result = solve_part_one(&parse_input(INPUT));
+        let result =
+            solve_part_one(&parse_input(&normalize(INPUT.get().unwrap())));
 
         #[cfg(debug_assertions)]
         assert_eq!(result, 26);
@@ -129,16 +175,51 @@ mod tests {
 
     #[bench]
     fn bench_part_one(b: &mut test::Bencher) {
-        let input = parse_input(INPUT);
+        let input = normalize(INPUT.get().unwrap());
+        let input = parse_input(&input);
         b.iter(|| solve_part_one(&input));
     }
 
+    #[test]
+    fn test_part_one_set() {
+        let input = normalize(INPUT.get().unwrap());
+        let input = parse_input(&input);
+        assert_eq!(solve_part_one_set(&input), solve_part_one(&input));
+        for seed in 0..10 {
+            let input = parse_input(&gen::generate(seed, 20));
+            assert_eq!(solve_part_one_set(&input), solve_part_one(&input));
+        }
+    }
+
+    #[test]
+    fn test_range_tip_on_row() {
+        let tip = format!(
+            "Sensor at x=0, y={}: closest beacon is at x=5, y={}\n",
+            ROW - 5,
+            ROW - 5
+        );
+        let input = parse_input(&tip);
+        assert_eq!(solve_part_one(&input), 1);
+        assert_eq!(solve_part_one_set(&input), 1);
+    }
+
+    #[bench]
+    fn bench_part_one_set(b: &mut test::Bencher) {
+        let input = normalize(INPUT.get().unwrap());
+        let input = parse_input(&input);
+        b.iter(|| solve_part_one_set(&input));
+    }
+
     #[test]
     fn test_part_two() {
-        let result = solve_part_two(&parse_input(INPUT));
+        let result = solve_part_two(
+            &parse_input(&normalize(INPUT.get().unwrap())),
+            &Context::new(),
+        )
+        .unwrap();
 
         #[cfg(debug_assertions)]
-        assert_eq!(result, 1924);
+        assert_eq!(result, 56000011);
 
         #[cfg(not(debug_assertions))]
         assert_eq!(result, 36975);
@@ -146,7 +227,51 @@ mod tests {
 
     #[bench]
     fn bench_part_two(b: &mut test::Bencher) {
-        let input = parse_input(INPUT);
-        b.iter(|| solve_part_two(&input));
+        let input = normalize(INPUT.get().unwrap());
+        let input = parse_input(&input);
+        b.iter(|| solve_part_two(&input, &Context::new()));
+    }
+
+    #[test]
+    fn test_part_two_context() {
+        let (input, context) = (
+            parse_input(&normalize(INPUT.get().unwrap())),
+            Context::new(),
+        );
+        solve_part_two(&input, &context).unwrap();
+        assert_eq!(context.progress(), Some((11, UPPER_BOUND as u64 + 1)));
+        context.cancel();
+        assert_eq!(solve_part_two(&input, &context), Err(Cancelled));
+    }
+
+    #[test]
+    fn test_generated_input_parses() {
+        for seed in 0..10 {
+            let input = parse_input(&gen::generate(seed, 20));
+            assert_eq!(input.len(), 2 * 24);
+            let frequency = solve_part_two(&input, &Context::new()).unwrap();
+            assert!(frequency / 4_000_000 <= UPPER_BOUND);
+            assert!(frequency % 4_000_000 <= UPPER_BOUND);
+        }
+    }
+
+    #[test]
+    fn test_generated_closest_beacons() {
+        for seed in 0..10 {
+            let input = parse_input(&gen::generate(seed, 20));
+            for (sensor, beacon) in input.iter().tuples() {
+                let range = sensor.manhattan(*beacon);
+                for other in input.iter().skip(1).step_by(2) {
+                    assert!(
+                        other == beacon || sensor.manhattan(*other) > range
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sample_variants() {
+        common::input::assert_variants(&DAY, INPUT.get().unwrap());
     }
 }
@@ -0,0 +1,104 @@
+//! The sensors as a drawing: the area each one covers, the row of part one
+//! and the search area of part two with the distress beacon found in it.
+
+use crate::{distress_beacon, parse_input, solve_part_one, ROW, UPPER_BOUND};
+use alloc::collections::BTreeSet;
+use alloc::{format, string::String, vec::Vec};
+use common::context::Context;
+use common::geometry::{BoundingBox, Point, Vec2};
+use common::input::normalize;
+use common::svg::Svg;
+use itertools::Itertools;
+
+pub fn svg(input: &str) -> Result<Svg, String> {
+    let input = parse_input(&normalize(input));
+    let pairs = input.iter().copied().tuples().collect::<Vec<_>>();
+    let corners = |(sensor, beacon): (Point, Point)| {
+        let r = sensor.manhattan(beacon);
+        [(0, -r), (r, 0), (0, r), (-r, 0)]
+            .map(|(dx, dy)| sensor + Vec2::new(dx, dy))
+    };
+    let bound = UPPER_BOUND as i64;
+    let area = (Point::ZERO, Point::new(bound, bound));
+    let view =
+        BoundingBox::from_points(pairs.iter().flat_map(|&pair| corners(pair)))
+            .ok_or("no sensors")?
+            .including(area.0)
+            .including(area.1);
+    let size = view.width().max(view.height());
+    let unit = size as f64 / 100.0;
+    // Labels sit on whole coordinates, a few percent away from what they
+    // annotate.
+    let offset = (size / 30).max(1);
+    let mut svg = Svg::new(view, size / 20);
+
+    let coverage = svg.layer("coverage");
+    coverage
+        .fill("#4682b4")
+        .opacity(0.25)
+        .stroke("#4682b4", 1.0);
+    for &pair in &pairs {
+        coverage.polygon(corners(pair));
+    }
+    svg.layer("search-area")
+        .stroke("#555", 2.0)
+        .rect(area.0, area.1);
+    svg.layer("links")
+        .stroke("#888", 1.0)
+        .polyline(pairs.iter().flat_map(|&(s, b)| [s, b, s]));
+    let sensors = svg.layer("sensors").fill("#1f4e79").stroke("none", 0.0);
+    for &(sensor, _) in &pairs {
+        sensors.circle(sensor, unit);
+    }
+    let beacons = svg.layer("beacons").fill("#2e8b57").stroke("none", 0.0);
+    for &beacon in pairs.iter().map(|(_, b)| b).collect::<BTreeSet<_>>() {
+        beacons.circle(beacon, unit);
+    }
+
+    let (left, right) = (view.min.x - size / 20, view.max.x + size / 20);
+    let free = solve_part_one(&input);
+    svg.layer("row")
+        .stroke("#d62728", 2.0)
+        .fill("#d62728")
+        .font_size(3.0 * unit)
+        .line(Point::new(left, ROW), Point::new(right, ROW))
+        .text(
+            Point::new(left, ROW - offset),
+            &format!("y={}: {} positions cannot hold a beacon", ROW, free),
+        );
+    let gap = svg
+        .layer("distress-beacon")
+        .fill("#ff8c00")
+        .stroke("none", 0.0)
+        .font_size(3.0 * unit);
+    match distress_beacon(&input, &Context::new()).unwrap() {
+        Some(beacon) => gap.circle(beacon, 2.0 * unit).text(
+            beacon + Vec2::new(offset, 0),
+            &format!(
+                "x={}, y={}: tuning frequency {}",
+                beacon.x,
+                beacon.y,
+                beacon.x * 4_000_000 + beacon.y
+            ),
+        ),
+        None => gap.text(area.0, "no distress beacon"),
+    };
+    Ok(svg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_svg() {
+        let svg = svg(include_str!("../sample.TXT")).unwrap().to_string();
+        assert_eq!(svg.matches("<polygon").count(), 14);
+        assert_eq!(svg.matches("<circle").count(), 14 + 6 + 1);
+        assert!(svg.contains("y=10: 26 positions cannot hold a beacon"));
+        assert!(svg.contains("x=14, y=11: tuning frequency 56000011"));
+        assert!(svg.contains("<rect x=\"-0.5\" y=\"-0.5\" width=\"21\""));
+        assert!(svg.starts_with("<svg") && svg.ends_with("</svg>"));
+    }
+}
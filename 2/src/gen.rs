@@ -0,0 +1,14 @@
+use alloc::{format, string::String};
+use common::rng::Rng;
+
+/// Generates a strategy guide with `size` rounds.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    (0..size.max(1))
+        .map(|_| {
+            let opponent = rng.choose(&['A', 'B', 'C']);
+            let me = rng.choose(&['X', 'Y', 'Z']);
+            format!("{} {}\n", opponent, me)
+        })
+        .collect()
+}
@@ -1,11 +1,23 @@
-#![feature(test)]
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(test, feature(test))]
+extern crate alloc;
+#[cfg(test)]
 extern crate test;
 
-#[cfg(debug_assertions)]
-const INPUT: &str = include_str!("../sample.TXT");
+mod gen;
+#[cfg(feature = "std")]
+pub mod stream;
+
+use alloc::{format, string::String, vec::Vec};
+use common::input::normalize;
+use common::{export_plugin, implementations, Day};
 
-#[cfg(not(debug_assertions))]
-const INPUT: &str = include_str!("../input.TXT");
+#[cfg(all(feature = "std", debug_assertions))]
+static INPUT: common::input::PuzzleInput =
+    common::input::PuzzleInput::sample(include_str!("../sample.TXT"));
+#[cfg(all(feature = "std", not(debug_assertions)))]
+static INPUT: common::input::PuzzleInput =
+    common::input::PuzzleInput::personal(env!("CARGO_MANIFEST_DIR"));
 
 type ParsedInput = Vec<(char, char)>;
 
@@ -56,36 +68,57 @@ fn solve_part_two(input: &ParsedInput) -> usize {
     input.iter().map(score_p2).sum()
 }
 
+/// A round, `A`, `B` or `C` then `X`, `Y` or `Z`.
+fn parse_line(line: &str) -> Result<(char, char), String> {
+    match line.as_bytes() {
+        &[opponent @ b'A'..=b'C', b' ', me @ b'X'..=b'Z'] => {
+            Ok((opponent as char, me as char))
+        }
+        _ => Err(format!("invalid round: {:?}", line)),
+    }
+}
+
 fn parse_input(input: &str) -> ParsedInput {
     input
         .lines()
-        .map(|line| {
-            let mut chars = line.chars();
-            let opponent = chars.next().unwrap();
-            let _ = chars.next().unwrap();
-            let me = chars.next().unwrap();
-            (opponent, me)
-        })
+        .map(|line| parse_line(line).unwrap())
         .collect()
 }
 
-pub fn solve() {
-    let input = parse_input(INPUT);
+#[cfg(feature = "std")]
+pub fn solve() -> Result<(), String> {
+    let input = normalize(INPUT.get()?);
+    let input = parse_input(&input);
 
     let result = solve_part_one(&input);
     println!("Part #1: {}", result);
 
     let result = solve_part_two(&input);
     println!("Part #2: {}", result);
+    Ok(())
 }
 
+pub const DAY: Day = Day {
+    day: 2,
+    sample: include_str!("../sample.TXT"),
+    part_one: implementations!(parse_input, solve_part_one),
+    part_two: implementations!(parse_input, solve_part_two),
+    generate: gen::generate,
+    inspect: |input| format!("{:#?}", parse_input(&normalize(input))),
+    commands: &[],
+    simulations: &[],
+};
+
+export_plugin!(DAY);
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let result = solve_part_one(&parse_input(INPUT));
+        let result =
+            solve_part_one(&parse_input(&normalize(INPUT.get().unwrap())));
 
         #[cfg(debug_assertions)]
         assert_eq!(result, 15);
@@ -96,13 +129,15 @@ mod tests {
 
     #[bench]
     fn bench_part_one(b: &mut test::Bencher) {
-        let input = parse_input(INPUT);
+        let input = normalize(INPUT.get().unwrap());
+        let input = parse_input(&input);
         b.iter(|| solve_part_one(&input));
     }
 
     #[test]
     fn test_part_two() {
-        let result = solve_part_two(&parse_input(INPUT));
+        let result =
+            solve_part_two(&parse_input(&normalize(INPUT.get().unwrap())));
 
         #[cfg(debug_assertions)]
         assert_eq!(result, 12);
@@ -113,7 +148,21 @@ mod tests {
 
     #[bench]
     fn bench_part_two(b: &mut test::Bencher) {
-        let input = parse_input(INPUT);
+        let input = normalize(INPUT.get().unwrap());
+        let input = parse_input(&input);
         b.iter(|| solve_part_two(&input));
     }
+
+    #[test]
+    fn test_generated_input_parses() {
+        for seed in 0..10 {
+            let input = parse_input(&gen::generate(seed, 50));
+            assert_eq!(input.len(), 50);
+        }
+    }
+
+    #[test]
+    fn test_sample_variants() {
+        common::input::assert_variants(&DAY, INPUT.get().unwrap());
+    }
 }
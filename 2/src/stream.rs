@@ -0,0 +1,49 @@
+//! Solvers reading the input from a [`BufRead`], one round at a time.
+
+use crate::{parse_line, score_p1, score_p2};
+use common::input::for_each_line;
+use std::io::{self, BufRead};
+
+fn total_score(
+    reader: impl BufRead,
+    score: fn(&(char, char)) -> usize,
+) -> io::Result<usize> {
+    let mut total = 0;
+    for_each_line(reader, |line| {
+        let round = parse_line(line)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        total += score(&round);
+        Ok(())
+    })?;
+    Ok(total)
+}
+
+pub fn solve_part_one(reader: impl BufRead) -> io::Result<usize> {
+    total_score(reader, score_p1)
+}
+
+pub fn solve_part_two(reader: impl BufRead) -> io::Result<usize> {
+    total_score(reader, score_p2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_input, INPUT};
+    use common::input::normalize;
+
+    #[test]
+    fn test_matches_in_memory() {
+        let input = parse_input(&normalize(INPUT.get().unwrap()));
+        for variant in common::input::variants(INPUT.get().unwrap()) {
+            assert_eq!(
+                solve_part_one(variant.as_bytes()).unwrap(),
+                crate::solve_part_one(&input)
+            );
+            assert_eq!(
+                solve_part_two(variant.as_bytes()).unwrap(),
+                crate::solve_part_two(&input)
+            );
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use alloc::{string::String, vec, vec::Vec};
+use common::rng::Rng;
+
+/// Generates `size` rucksacks, rounded up to whole groups of three.
+///
+/// Each rucksack has exactly one item type in both compartments and each
+/// group shares exactly one badge, as the puzzle guarantees.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut output = String::new();
+    for _ in 0..size.max(1).div_ceil(3) {
+        let mut items = ('a'..='z').chain('A'..='Z').collect::<Vec<_>>();
+        rng.shuffle(&mut items);
+        let badge = items.pop().unwrap();
+        for pool in items.chunks(items.len() / 3).take(3) {
+            output.push_str(&rucksack(&mut rng, badge, pool));
+            output.push('\n');
+        }
+    }
+    output
+}
+
+fn rucksack(rng: &mut Rng, badge: char, pool: &[char]) -> String {
+    let (shared, pool) = match rng.chance(1.0 / 3.0) {
+        true => (badge, pool),
+        false => (pool[0], &pool[1..]),
+    };
+    let (left_pool, right_pool) = pool.split_at(pool.len() / 2);
+    let len = rng.range(4..=16);
+
+    let mut left = vec![shared];
+    if shared != badge {
+        left.push(badge);
+    }
+    while left.len() < len {
+        left.push(*rng.choose(left_pool));
+    }
+    let mut right = vec![shared];
+    while right.len() < len {
+        right.push(*rng.choose(right_pool));
+    }
+    rng.shuffle(&mut left);
+    rng.shuffle(&mut right);
+    left.into_iter().chain(right).collect()
+}
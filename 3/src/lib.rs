@@ -1,11 +1,23 @@
-#![feature(test)]
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(test, feature(test))]
+extern crate alloc;
+#[cfg(test)]
 extern crate test;
 
-#[cfg(debug_assertions)]
-const INPUT: &str = include_str!("../sample.TXT");
+mod gen;
+#[cfg(feature = "std")]
+pub mod stream;
+
+use alloc::{format, vec::Vec};
+use common::input::normalize;
+use common::{export_plugin, implementations, Day};
 
-#[cfg(not(debug_assertions))]
-const INPUT: &str = include_str!("../input.TXT");
+#[cfg(all(feature = "std", debug_assertions))]
+static INPUT: common::input::PuzzleInput =
+    common::input::PuzzleInput::sample(include_str!("../sample.TXT"));
+#[cfg(all(feature = "std", not(debug_assertions)))]
+static INPUT: common::input::PuzzleInput =
+    common::input::PuzzleInput::personal(env!("CARGO_MANIFEST_DIR"));
 
 type ParsedInput<'i> = Vec<&'i str>;
 
@@ -17,17 +29,23 @@ fn priority(c: &char) -> u8 {
     }
 }
 
+fn rucksack_priority(line: &str) -> usize {
+    let (a, b) = line.split_at(line.len() / 2);
+    let mut common = a.chars().filter(|c| b.contains(*c)).collect::<Vec<_>>();
+    common.dedup();
+    common.iter().map(|c| priority(c) as usize).sum()
+}
+
+fn badge_priority(a: &str, b: &str, c: &str) -> usize {
+    let common: char = a
+        .chars()
+        .find(|ch| b.contains(*ch) && c.contains(*ch))
+        .unwrap();
+    priority(&common) as usize
+}
+
 fn solve_part_one(input: &ParsedInput) -> usize {
-    input
-        .iter()
-        .map(|line| line.split_at(line.len() / 2))
-        .map(|(a, b)| {
-            let mut common =
-                a.chars().filter(|c| b.contains(*c)).collect::<Vec<_>>();
-            common.dedup();
-            common.iter().map(|c| priority(c) as usize).sum::<usize>()
-        })
-        .sum()
+    input.iter().map(|line| rucksack_priority(line)).sum()
 }
 
 fn solve_part_two(input: &ParsedInput) -> usize {
@@ -35,38 +53,51 @@ fn solve_part_two(input: &ParsedInput) -> usize {
         .chunks_exact(3)
         .map(|chunk| {
             if let &[a, b, c] = chunk {
-                let common: char = a
-                    .chars()
-                    .find(|ch| b.contains(*ch) && c.contains(*ch))
-                    .unwrap();
-                return priority(&common) as usize;
+                return badge_priority(a, b, c);
             }
             unreachable!()
         })
         .sum()
 }
 
-fn parse_input(input: &str) -> ParsedInput {
+fn parse_input(input: &str) -> ParsedInput<'_> {
     input.lines().collect()
 }
 
-pub fn solve() {
-    let input = parse_input(INPUT);
+#[cfg(feature = "std")]
+pub fn solve() -> Result<(), String> {
+    let input = normalize(INPUT.get()?);
+    let input = parse_input(&input);
 
     let result = solve_part_one(&input);
     println!("Part #1: {}", result);
 
     let result = solve_part_two(&input);
     println!("Part #2: {}", result);
+    Ok(())
 }
 
+pub const DAY: Day = Day {
+    day: 3,
+    sample: include_str!("../sample.TXT"),
+    part_one: implementations!(parse_input, solve_part_one),
+    part_two: implementations!(parse_input, solve_part_two),
+    generate: gen::generate,
+    inspect: |input| format!("{:#?}", parse_input(&normalize(input))),
+    commands: &[],
+    simulations: &[],
+};
+
+export_plugin!(DAY);
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let result = solve_part_one(&parse_input(INPUT));
+        let result =
+            solve_part_one(&parse_input(&normalize(INPUT.get().unwrap())));
 
         #[cfg(debug_assertions)]
         assert_eq!(result, 157);
@@ -77,13 +108,15 @@ mod tests {
 
     #[bench]
     fn bench_part_one(b: &mut test::Bencher) {
-        let input = parse_input(INPUT);
+        let input = normalize(INPUT.get().unwrap());
+        let input = parse_input(&input);
         b.iter(|| solve_part_one(&input));
     }
 
     #[test]
     fn test_part_two() {
-        let result = solve_part_two(&parse_input(INPUT));
+        let result =
+            solve_part_two(&parse_input(&normalize(INPUT.get().unwrap())));
 
         #[cfg(debug_assertions)]
         assert_eq!(result, 70);
@@ -94,7 +127,22 @@ mod tests {
 
     #[bench]
     fn bench_part_two(b: &mut test::Bencher) {
-        let input = parse_input(INPUT);
+        let input = normalize(INPUT.get().unwrap());
+        let input = parse_input(&input);
         b.iter(|| solve_part_two(&input));
     }
+
+    #[test]
+    fn test_generated_input_parses() {
+        for seed in 0..10 {
+            let input = gen::generate(seed, 30);
+            assert_eq!(parse_input(&input).len(), 30);
+            solve_part_two(&parse_input(&input));
+        }
+    }
+
+    #[test]
+    fn test_sample_variants() {
+        common::input::assert_variants(&DAY, INPUT.get().unwrap());
+    }
 }
@@ -0,0 +1,52 @@
+//! Solvers reading the input from a [`BufRead`], holding at most one group
+//! of three rucksacks.
+
+use crate::{badge_priority, rucksack_priority};
+use common::input::for_each_line;
+use std::io::{self, BufRead};
+
+pub fn solve_part_one(reader: impl BufRead) -> io::Result<usize> {
+    let mut total = 0;
+    for_each_line(reader, |line| {
+        total += rucksack_priority(line);
+        Ok(())
+    })?;
+    Ok(total)
+}
+
+pub fn solve_part_two(reader: impl BufRead) -> io::Result<usize> {
+    let mut total = 0;
+    let mut group: Vec<String> = Vec::with_capacity(3);
+    for_each_line(reader, |line| {
+        group.push(line.to_string());
+        if let [a, b, c] = &group[..] {
+            total += badge_priority(a, b, c);
+            group.clear();
+        }
+        Ok(())
+    })?;
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_input, INPUT};
+    use common::input::normalize;
+
+    #[test]
+    fn test_matches_in_memory() {
+        let input = normalize(INPUT.get().unwrap());
+        let input = parse_input(&input);
+        for variant in common::input::variants(INPUT.get().unwrap()) {
+            assert_eq!(
+                solve_part_one(variant.as_bytes()).unwrap(),
+                crate::solve_part_one(&input)
+            );
+            assert_eq!(
+                solve_part_two(variant.as_bytes()).unwrap(),
+                crate::solve_part_two(&input)
+            );
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use alloc::{format, string::String};
+use common::rng::Rng;
+
+/// Generates `size` pairs of section assignments.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut assignment = || {
+        let start = rng.range(1..=99u32);
+        let end = rng.range(start..=99);
+        format!("{}-{}", start, end)
+    };
+    (0..size.max(1))
+        .map(|_| format!("{},{}\n", assignment(), assignment()))
+        .collect()
+}
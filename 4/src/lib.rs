@@ -1,67 +1,94 @@
-#![feature(test)]
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(test, feature(test))]
+extern crate alloc;
+#[cfg(test)]
 extern crate test;
 
-#[cfg(debug_assertions)]
-const INPUT: &str = include_str!("../sample.TXT");
+mod gen;
+#[cfg(feature = "std")]
+pub mod stream;
+
+use alloc::{format, string::String, vec::Vec};
+use common::input::normalize;
+use common::interval::Interval;
+use common::parse;
+use common::{export_plugin, implementations, Day};
 
-#[cfg(not(debug_assertions))]
-const INPUT: &str = include_str!("../input.TXT");
+#[cfg(all(feature = "std", debug_assertions))]
+static INPUT: common::input::PuzzleInput =
+    common::input::PuzzleInput::sample(include_str!("../sample.TXT"));
+#[cfg(all(feature = "std", not(debug_assertions)))]
+static INPUT: common::input::PuzzleInput =
+    common::input::PuzzleInput::personal(env!("CARGO_MANIFEST_DIR"));
 
-type Assignment = (usize, usize);
+type Assignment = Interval<usize>;
 type ParsedInput = Vec<(Assignment, Assignment)>;
 
+fn fully_contains((a, b): &(Assignment, Assignment)) -> bool {
+    a.contains_interval(b) || b.contains_interval(a)
+}
+
+fn overlaps((a, b): &(Assignment, Assignment)) -> bool {
+    a.overlaps(b)
+}
+
 fn solve_part_one(input: &ParsedInput) -> usize {
-    input
-        .iter()
-        .filter(|((min_a, max_a), (min_b, max_b))| {
-            min_a <= min_b && max_a >= max_b || min_b <= min_a && max_b >= max_a
-        })
-        .count()
+    input.iter().filter(|pair| fully_contains(pair)).count()
 }
 
 fn solve_part_two(input: &ParsedInput) -> usize {
-    input
-        .iter()
-        .filter(|((min_a, max_a), (min_b, max_b))| {
-            max_a >= min_b && max_b >= min_a
-        })
-        .count()
+    input.iter().filter(|pair| overlaps(pair)).count()
+}
+
+fn parse_line(line: &str) -> Result<(Assignment, Assignment), String> {
+    let [a, b, c, d] = parse::extract(line)?;
+    if a > b || c > d {
+        return Err(format!("empty assignment in {:?}", line));
+    }
+    Ok((Interval::new(a, b), Interval::new(c, d)))
 }
 
 fn parse_input(input: &str) -> ParsedInput {
     input
         .lines()
-        .map(|line| {
-            let parts = line
-                .split(',')
-                .map(|p| {
-                    p.split('-')
-                        .map(|i| i.parse::<usize>().unwrap())
-                        .collect::<Vec<_>>()
-                })
-                .collect::<Vec<_>>();
-            ((parts[0][0], parts[0][1]), (parts[1][0], parts[1][1]))
-        })
+        .map(|line| parse_line(line).unwrap())
         .collect()
 }
 
-pub fn solve() {
-    let input = parse_input(INPUT);
+#[cfg(feature = "std")]
+pub fn solve() -> Result<(), String> {
+    let input = normalize(INPUT.get()?);
+    let input = parse_input(&input);
 
     let result = solve_part_one(&input);
     println!("Part #1: {}", result);
 
     let result = solve_part_two(&input);
     println!("Part #2: {}", result);
+    Ok(())
 }
 
+pub const DAY: Day = Day {
+    day: 4,
+    sample: include_str!("../sample.TXT"),
+    part_one: implementations!(parse_input, solve_part_one),
+    part_two: implementations!(parse_input, solve_part_two),
+    generate: gen::generate,
+    inspect: |input| format!("{:#?}", parse_input(&normalize(input))),
+    commands: &[],
+    simulations: &[],
+};
+
+export_plugin!(DAY);
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let result = solve_part_one(&parse_input(INPUT));
+        let result =
+            solve_part_one(&parse_input(&normalize(INPUT.get().unwrap())));
 
         #[cfg(debug_assertions)]
         assert_eq!(result, 2);
@@ -72,13 +99,15 @@ mod tests {
 
     #[bench]
     fn bench_part_one(b: &mut test::Bencher) {
-        let input = parse_input(INPUT);
+        let input = normalize(INPUT.get().unwrap());
+        let input = parse_input(&input);
         b.iter(|| solve_part_one(&input));
     }
 
     #[test]
     fn test_part_two() {
-        let result = solve_part_two(&parse_input(INPUT));
+        let result =
+            solve_part_two(&parse_input(&normalize(INPUT.get().unwrap())));
 
         #[cfg(debug_assertions)]
         assert_eq!(result, 4);
@@ -89,7 +118,21 @@ mod tests {
 
     #[bench]
     fn bench_part_two(b: &mut test::Bencher) {
-        let input = parse_input(INPUT);
+        let input = normalize(INPUT.get().unwrap());
+        let input = parse_input(&input);
         b.iter(|| solve_part_two(&input));
     }
+
+    #[test]
+    fn test_generated_input_parses() {
+        for seed in 0..10 {
+            let input = parse_input(&gen::generate(seed, 50));
+            assert_eq!(input.len(), 50);
+        }
+    }
+
+    #[test]
+    fn test_sample_variants() {
+        common::input::assert_variants(&DAY, INPUT.get().unwrap());
+    }
 }
@@ -0,0 +1,49 @@
+//! Solvers reading the input from a [`BufRead`], one pair at a time.
+
+use crate::{fully_contains, overlaps, parse_line, Assignment};
+use common::input::for_each_line;
+use std::io::{self, BufRead};
+
+fn count_pairs(
+    reader: impl BufRead,
+    matches: fn(&(Assignment, Assignment)) -> bool,
+) -> io::Result<usize> {
+    let mut count = 0;
+    for_each_line(reader, |line| {
+        let pair = parse_line(line)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        count += usize::from(matches(&pair));
+        Ok(())
+    })?;
+    Ok(count)
+}
+
+pub fn solve_part_one(reader: impl BufRead) -> io::Result<usize> {
+    count_pairs(reader, fully_contains)
+}
+
+pub fn solve_part_two(reader: impl BufRead) -> io::Result<usize> {
+    count_pairs(reader, overlaps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_input, INPUT};
+    use common::input::normalize;
+
+    #[test]
+    fn test_matches_in_memory() {
+        let input = parse_input(&normalize(INPUT.get().unwrap()));
+        for variant in common::input::variants(INPUT.get().unwrap()) {
+            assert_eq!(
+                solve_part_one(variant.as_bytes()).unwrap(),
+                crate::solve_part_one(&input)
+            );
+            assert_eq!(
+                solve_part_two(variant.as_bytes()).unwrap(),
+                crate::solve_part_two(&input)
+            );
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use common::rng::Rng;
+
+/// Generates a drawing of up to nine stacks followed by `size` moves.
+///
+/// Moves never empty a stack, so every stack has a crate on top at the end.
+/// Stacks start with at least two crates, so there is always a legal move.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut heights = (0..rng.range(3..=9))
+        .map(|_| rng.range(2..=8))
+        .collect::<Vec<usize>>();
+    let max_height = *heights.iter().max().unwrap();
+
+    let mut output = String::new();
+    for level in (0..max_height).rev() {
+        let row = heights
+            .iter()
+            .map(|&h| match h > level {
+                true => format!("[{}]", rng.range(b'A'..=b'Z') as char),
+                false => "   ".to_string(),
+            })
+            .collect::<Vec<_>>();
+        output.push_str(&row.join(" "));
+        output.push('\n');
+    }
+    let labels = (1..=heights.len())
+        .map(|i| format!(" {} ", i))
+        .collect::<Vec<_>>();
+    output.push_str(&labels.join(" "));
+    output.push_str("\n\n");
+
+    for _ in 0..size {
+        let sources = (0..heights.len())
+            .filter(|&i| heights[i] > 1)
+            .collect::<Vec<_>>();
+        let src = *rng.choose(&sources);
+        let dst = (src + rng.range(1..=heights.len() - 1)) % heights.len();
+        let amount = rng.range(1..=heights[src] - 1);
+        heights[src] -= amount;
+        heights[dst] += amount;
+        output.push_str(&format!(
+            "move {} from {} to {}\n",
+            amount,
+            src + 1,
+            dst + 1
+        ));
+    }
+    output
+}
@@ -1,13 +1,25 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 #![feature(iter_next_chunk)]
-#![feature(test)]
+#![cfg_attr(test, feature(test))]
+extern crate alloc;
+#[cfg(test)]
 extern crate test;
-use std::ops::{Deref, DerefMut};
 
-#[cfg(debug_assertions)]
-const INPUT: &str = include_str!("../sample.TXT");
+mod gen;
+use alloc::{format, string::String, vec, vec::Vec};
+use common::input::normalize;
+use common::parse;
+use common::simulation::{playback, Simulation, Simulator};
+use common::{export_plugin, implementations, Day};
+use core::fmt;
+use core::ops::{Deref, DerefMut};
 
-#[cfg(not(debug_assertions))]
-const INPUT: &str = include_str!("../input.TXT");
+#[cfg(all(feature = "std", debug_assertions))]
+static INPUT: common::input::PuzzleInput =
+    common::input::PuzzleInput::sample(include_str!("../sample.TXT"));
+#[cfg(all(feature = "std", not(debug_assertions)))]
+static INPUT: common::input::PuzzleInput =
+    common::input::PuzzleInput::personal(env!("CARGO_MANIFEST_DIR"));
 
 #[derive(Debug, Clone, Copy)]
 struct Move(usize, usize, usize);
@@ -17,24 +29,103 @@ struct Stack(Vec<char>);
 
 type ParsedInput = (Vec<Move>, Vec<Stack>);
 
-fn solve_part_one(input: &ParsedInput) -> String {
-    let (mut moves, mut stacks) = input.clone();
-    while let Some(Move(amount, src, dst)) = moves.pop() {
-        let stack_len = stacks[src].len();
-        let slice = stacks[src].split_off(stack_len - amount);
-        stacks[dst].extend(slice.iter().rev());
+/// The crane working through the moves, one per step.
+struct Crane {
+    /// In reverse, the next move last.
+    moves: Vec<Move>,
+    /// The CrateMover 9001 lifts several crates at once, keeping their
+    /// order.
+    keeps_order: bool,
+    state: (Vec<Stack>, usize),
+}
+
+impl Crane {
+    fn new((moves, stacks): ParsedInput, keeps_order: bool) -> Self {
+        Self {
+            moves,
+            keeps_order,
+            state: (stacks, 0),
+        }
+    }
+
+    fn top(&self) -> String {
+        self.state.0.iter().map_while(|c| c.last()).collect()
     }
-    stacks.iter().map_while(|c| c.last()).collect()
 }
 
-fn solve_part_two(input: &ParsedInput) -> String {
-    let (mut moves, mut stacks) = input.clone();
-    while let Some(Move(amount, src, dst)) = moves.pop() {
+impl Simulation for Crane {
+    /// The stacks and the number of moves done.
+    type Snapshot = (Vec<Stack>, usize);
+
+    fn step(&mut self) -> bool {
+        let (stacks, done) = &mut self.state;
+        let Some(&Move(amount, src, dst)) = self.moves.iter().rev().nth(*done)
+        else {
+            return false;
+        };
         let stack_len = stacks[src].len();
         let slice = stacks[src].split_off(stack_len - amount);
-        stacks[dst].extend(slice);
+        match self.keeps_order {
+            true => stacks[dst].extend(slice),
+            false => stacks[dst].extend(slice.iter().rev()),
+        }
+        *done += 1;
+        true
+    }
+
+    fn steps(&self) -> usize {
+        self.state.1
+    }
+
+    fn snapshot(&self) -> Self::Snapshot {
+        self.state.clone()
+    }
+
+    fn restore(&mut self, snapshot: &Self::Snapshot) {
+        self.state = snapshot.clone();
     }
-    stacks.iter().map_while(|c| c.last()).collect()
+}
+
+/// The stacks drawn like in the input, with the next move below.
+impl fmt::Display for Crane {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (stacks, done) = &self.state;
+        let height = stacks.iter().map(|s| s.len()).max().unwrap_or(0);
+        for level in (0..height).rev() {
+            let mut row = String::new();
+            for stack in stacks {
+                match stack.get(level) {
+                    Some(c) => row.push_str(&format!("[{}] ", c)),
+                    None => row.push_str("    "),
+                }
+            }
+            writeln!(f, "{}", row.trim_end())?;
+        }
+        let labels = (1..=stacks.len()).map(|i| format!(" {}  ", i));
+        write!(f, "{}", labels.collect::<String>().trim_end())?;
+        match self.moves.iter().rev().nth(*done) {
+            Some(Move(amount, src, dst)) => write!(
+                f,
+                "\nnext: move {} from {} to {}",
+                amount,
+                src + 1,
+                dst + 1
+            ),
+            None => write!(f, "\ndone: {}", self.top()),
+        }
+    }
+}
+
+fn solve_part_one(input: &ParsedInput) -> String {
+    let mut crane = Crane::new(input.clone(), false);
+    crane.run();
+    crane.top()
+}
+
+fn solve_part_two(input: &ParsedInput) -> String {
+    let mut crane = Crane::new(input.clone(), true);
+    crane.run();
+    crane.top()
 }
 
 fn parse_input(input: &str) -> ParsedInput {
@@ -53,21 +144,13 @@ fn parse_input(input: &str) -> ParsedInput {
                     }
                 }
                 Some(c) if c.is_ascii_digit() => {
-                    stacks = line
-                        .split_whitespace()
-                        .filter_map(|s| {
-                            s.parse::<u8>().map(|_| Stack(vec![])).ok()
-                        })
+                    stacks = parse::numbers::<u8>(line)
+                        .map(|_| Stack(vec![]))
                         .collect()
                 }
                 Some('m') => {
-                    if let &[amount, src, dest] = &line
-                        .split_whitespace()
-                        .filter_map(|s| s.parse().ok())
-                        .collect::<Vec<_>>()[..]
-                    {
-                        ops.push(Move(amount, src - 1, dest - 1));
-                    }
+                    let [amount, src, dest] = parse::extract(line).unwrap();
+                    ops.push(Move(amount, src - 1, dest - 1));
                 }
                 _ => (),
             }
@@ -75,16 +158,49 @@ fn parse_input(input: &str) -> ParsedInput {
         })
 }
 
-pub fn solve() {
-    let input = parse_input(INPUT);
+#[cfg(feature = "std")]
+pub fn solve() -> Result<(), String> {
+    let input = normalize(INPUT.get()?);
+    let input = parse_input(&input);
 
     let result = solve_part_one(&input);
     println!("Part #1: {}", result);
 
     let result = solve_part_two(&input);
     println!("Part #2: {}", result);
+    Ok(())
 }
 
+pub const DAY: Day = Day {
+    day: 5,
+    sample: include_str!("../sample.TXT"),
+    part_one: implementations!(parse_input, solve_part_one),
+    part_two: implementations!(parse_input, solve_part_two),
+    generate: gen::generate,
+    inspect: |input| format!("{:#?}", parse_input(&normalize(input))),
+    commands: &[],
+    simulations: &[
+        Simulator {
+            name: "9000",
+            help: "the CrateMover 9000 moving one crate at a time",
+            start: |input| {
+                let input = parse_input(&normalize(input));
+                Ok(playback(Crane::new(input, false)))
+            },
+        },
+        Simulator {
+            name: "9001",
+            help: "the CrateMover 9001 moving several crates at once",
+            start: |input| {
+                let input = parse_input(&normalize(input));
+                Ok(playback(Crane::new(input, true)))
+            },
+        },
+    ],
+};
+
+export_plugin!(DAY);
+
 impl Deref for Stack {
     type Target = Vec<char>;
 
@@ -105,7 +221,8 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = solve_part_one(&parse_input(INPUT));
+        let result =
+            solve_part_one(&parse_input(&normalize(INPUT.get().unwrap())));
 
         #[cfg(debug_assertions)]
         assert_eq!(&result, "CMZ");
@@ -116,13 +233,15 @@ mod tests {
 
     #[bench]
     fn bench_part_one(b: &mut test::Bencher) {
-        let input = parse_input(INPUT);
+        let input = normalize(INPUT.get().unwrap());
+        let input = parse_input(&input);
         b.iter(|| solve_part_one(&input));
     }
 
     #[test]
     fn test_part_two() {
-        let result = solve_part_two(&parse_input(INPUT));
+        let result =
+            solve_part_two(&parse_input(&normalize(INPUT.get().unwrap())));
 
         #[cfg(debug_assertions)]
         assert_eq!(result, "MCD");
@@ -133,7 +252,38 @@ mod tests {
 
     #[bench]
     fn bench_part_two(b: &mut test::Bencher) {
-        let input = parse_input(INPUT);
+        let input = normalize(INPUT.get().unwrap());
+        let input = parse_input(&input);
         b.iter(|| solve_part_two(&input));
     }
+
+    #[test]
+    fn test_crane_replay() {
+        let mut crane =
+            (DAY.simulations[1].start)(INPUT.get().unwrap()).unwrap();
+        assert!(crane.seek(2));
+        assert_eq!(
+            crane.render(),
+            "        [D]\n        [N]\n    [C] [Z]\n    [M] [P]\n \
+             1   2   3\nnext: move 2 from 2 to 1"
+        );
+        assert!(crane.step() && crane.step() && !crane.step());
+        assert!(crane.render().ends_with("done: MCD"));
+        assert!(crane.seek(0));
+        assert!(crane.render().starts_with("    [D]\n[N] [C]\n"));
+    }
+
+    #[test]
+    fn test_generated_input_parses() {
+        for seed in 0..10 {
+            let input = parse_input(&gen::generate(seed, 50));
+            assert_eq!(input.0.len(), 50);
+            assert_eq!(solve_part_one(&input).len(), input.1.len());
+        }
+    }
+
+    #[test]
+    fn test_sample_variants() {
+        common::input::assert_variants(&DAY, INPUT.get().unwrap());
+    }
 }
@@ -0,0 +1,21 @@
+use alloc::{string::String, vec::Vec};
+use common::rng::Rng;
+
+/// Generates a datastream of `size` characters (at least 14).
+///
+/// The noise before the marker only uses three letters, so both markers are
+/// found no later than the planted run of fourteen distinct characters.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let size = size.max(14);
+    let prefix_len = rng.range(0..=size - 14);
+
+    let mut stream = (0..prefix_len)
+        .map(|_| *rng.choose(&['a', 'b', 'c']))
+        .collect::<Vec<_>>();
+    let mut letters = ('a'..='z').collect::<Vec<_>>();
+    rng.shuffle(&mut letters);
+    stream.extend(&letters[..14]);
+    stream.extend((stream.len()..size).map(|_| rng.range(b'a'..=b'z') as char));
+    stream.into_iter().chain(Some('\n')).collect()
+}
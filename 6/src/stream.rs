@@ -0,0 +1,104 @@
+//! Solvers reading the datastream from a [`BufRead`]. Only the current
+//! window is kept, so the stream can be arbitrarily long.
+
+use std::io::{self, BufRead};
+
+const BOM: &[u8] = "\u{feff}".as_bytes();
+
+/// Offset of the end of the first window of `sz` distinct bytes. The stream
+/// ends at the first whitespace.
+fn first_unique_window(
+    mut reader: impl BufRead,
+    sz: usize,
+) -> io::Result<usize> {
+    let mut window = vec![0; sz];
+    let mut counts = [0u32; 256];
+    let mut repeated = 0;
+    let mut offset = 0;
+    let mut bom = 0;
+    loop {
+        let buf = reader.fill_buf()?;
+        if buf.is_empty() {
+            break;
+        }
+        let len = buf.len();
+        for &byte in buf {
+            if offset == 0 && bom < BOM.len() && byte == BOM[bom] {
+                bom += 1;
+                continue;
+            }
+            if byte.is_ascii_whitespace() {
+                break;
+            }
+            let slot = &mut window[offset % sz];
+            if offset >= sz {
+                counts[*slot as usize] -= 1;
+                if counts[*slot as usize] == 1 {
+                    repeated -= 1;
+                }
+            }
+            *slot = byte;
+            counts[byte as usize] += 1;
+            if counts[byte as usize] == 2 {
+                repeated += 1;
+            }
+            offset += 1;
+            if offset >= sz && repeated == 0 {
+                return Ok(offset);
+            }
+        }
+        if buf.iter().any(u8::is_ascii_whitespace) {
+            break;
+        }
+        reader.consume(len);
+    }
+    Err(io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("no window of {} distinct characters", sz),
+    ))
+}
+
+pub fn solve_part_one(reader: impl BufRead) -> io::Result<usize> {
+    first_unique_window(reader, 4)
+}
+
+pub fn solve_part_two(reader: impl BufRead) -> io::Result<usize> {
+    first_unique_window(reader, 14)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_input, INPUT};
+    use common::input::normalize;
+
+    #[test]
+    fn test_matches_in_memory() {
+        let input = normalize(INPUT.get().unwrap());
+        let input = parse_input(&input);
+        for variant in common::input::variants(INPUT.get().unwrap()) {
+            assert_eq!(
+                solve_part_one(variant.as_bytes()).unwrap(),
+                crate::solve_part_one(&input)
+            );
+            assert_eq!(
+                solve_part_two(variant.as_bytes()).unwrap(),
+                crate::solve_part_two(&input)
+            );
+        }
+    }
+
+    #[test]
+    fn test_small_buffers() {
+        let reader =
+            io::BufReader::with_capacity(3, INPUT.get().unwrap().as_bytes());
+        assert_eq!(
+            solve_part_two(reader).unwrap(),
+            crate::solve_part_two(&parse_input(&normalize(
+                INPUT.get().unwrap()
+            )))
+        );
+        let err = solve_part_one("abcabc\nabcd".as_bytes()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
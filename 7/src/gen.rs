@@ -0,0 +1,82 @@
+use alloc::{format, string::String, vec, vec::Vec};
+use common::rng::Rng;
+
+const MAX_USED: usize = 60_000_000;
+
+/// Generates a terminal session exploring a tree of `size` directories.
+///
+/// The total size lands between 40M and 70M so part two always has a
+/// non-negative amount of space to free.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut tree = vec![Node::default()];
+    for idx in 1..size.max(1) {
+        let parent = rng.below(idx as u64) as usize;
+        let name = loop {
+            let name = random_name(&mut rng);
+            if !tree[parent].dirs.iter().any(|&d| tree[d].name == name) {
+                break name;
+            }
+        };
+        tree[parent].dirs.push(idx);
+        tree.push(Node {
+            name,
+            ..Node::default()
+        });
+    }
+
+    for node in tree.iter_mut() {
+        node.files = (0..rng.range(0..=4))
+            .map(|_| rng.range(1..=300_000))
+            .collect();
+    }
+    let total = tree.iter().flat_map(|n| &n.files).sum::<usize>();
+    if total > MAX_USED {
+        for file in tree.iter_mut().flat_map(|n| n.files.iter_mut()) {
+            *file = (*file * MAX_USED / total).max(1);
+        }
+    }
+    let total = tree.iter().flat_map(|n| &n.files).sum::<usize>();
+    let target = rng.range(total.max(40_000_000) + 1..=69_999_999);
+    tree[0].files.push(target - total);
+
+    let mut output = String::from("$ cd /\n");
+    write_dir(&mut rng, &tree, 0, &mut output);
+    output
+}
+
+#[derive(Default)]
+struct Node {
+    name: String,
+    dirs: Vec<usize>,
+    files: Vec<usize>,
+}
+
+fn write_dir(rng: &mut Rng, tree: &[Node], idx: usize, output: &mut String) {
+    let mut entries = tree[idx]
+        .dirs
+        .iter()
+        .map(|&d| format!("dir {}", tree[d].name))
+        .chain(tree[idx].files.iter().map(|size| {
+            format!("{} {}.{}", size, random_name(rng), random_name(rng))
+        }))
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut entries);
+
+    output.push_str("$ ls\n");
+    for entry in entries {
+        output.push_str(&entry);
+        output.push('\n');
+    }
+    for &dir in &tree[idx].dirs {
+        output.push_str(&format!("$ cd {}\n", tree[dir].name));
+        write_dir(rng, tree, dir, output);
+        output.push_str("$ cd ..\n");
+    }
+}
+
+fn random_name(rng: &mut Rng) -> String {
+    (0..rng.range(1..=8))
+        .map(|_| rng.range(b'a'..=b'z') as char)
+        .collect()
+}
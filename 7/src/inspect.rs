@@ -0,0 +1,66 @@
+use super::{get_dir_size, parse_input, Dir};
+use alloc::string::String;
+use common::input::normalize;
+use common::Command;
+use core::fmt::Write;
+
+pub const COMMANDS: &[Command] = &[Command {
+    name: "tree",
+    usage: "tree [max depth]",
+    help: "print the directory tree with file and total sizes",
+    run: tree,
+}];
+
+fn tree(input: &str, args: &[&str]) -> Result<String, String> {
+    let max_depth = match args.first() {
+        Some(depth) => depth.parse().map_err(|_| "invalid depth")?,
+        None => usize::MAX,
+    };
+    let input = normalize(input);
+    let fs = parse_input(&input);
+    let mut output = String::new();
+    write_dir(&fs, 0, 0, max_depth, &mut output);
+    Ok(output)
+}
+
+fn write_dir(
+    fs: &[Dir],
+    idx: usize,
+    depth: usize,
+    max_depth: usize,
+    output: &mut String,
+) {
+    let _ = writeln!(
+        output,
+        "{:indent$}- {} (files {}, total {})",
+        "",
+        fs[idx].name,
+        fs[idx].size,
+        get_dir_size(fs, idx),
+        indent = depth * 2
+    );
+    if depth < max_depth {
+        for &dir in &fs[idx].dirs {
+            write_dir(fs, dir, depth + 1, max_depth, output);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tree() {
+        let input = include_str!("../sample.TXT");
+        assert_eq!(
+            tree(input, &[]).unwrap(),
+            "- / (files 23352670, total 48381165)\n  \
+             - a (files 94269, total 94853)\n    \
+             - e (files 584, total 584)\n  \
+             - d (files 24933642, total 24933642)\n"
+        );
+        assert_eq!(tree(input, &["0"]).unwrap().lines().count(), 1);
+        assert!(tree(input, &["x"]).is_err());
+    }
+}
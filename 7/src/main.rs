@@ -1,5 +1,8 @@
-use solution::solve;
+use day07::solve;
 
 fn main() {
-    solve();
+    if let Err(err) = solve() {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
 }
@@ -0,0 +1,16 @@
+use alloc::string::String;
+use common::rng::Rng;
+
+/// Generates a `size` x `size` map of tree heights.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let size = size.max(1);
+    (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| rng.range(b'0'..=b'9') as char)
+                .chain(Some('\n'))
+                .collect::<String>()
+        })
+        .collect()
+}
@@ -1,116 +1,127 @@
-#![feature(test)]
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(test, feature(test))]
+extern crate alloc;
+#[cfg(test)]
 extern crate test;
 
-#[cfg(debug_assertions)]
-const INPUT: &str = include_str!("../sample.TXT");
-
-#[cfg(not(debug_assertions))]
-const INPUT: &str = include_str!("../input.TXT");
+mod gen;
+#[cfg(feature = "visualize")]
+pub mod visualize;
 
-type ParsedInput = Vec<Vec<u32>>;
+use alloc::format;
+use common::grid::{Grid, Pos, ORTHOGONAL};
+use common::input::normalize;
+use common::{export_plugin, implementations, Day};
 
-const DIRECTIONS: [(i32, i32); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+#[cfg(all(feature = "std", debug_assertions))]
+static INPUT: common::input::PuzzleInput =
+    common::input::PuzzleInput::sample(include_str!("../sample.TXT"));
+#[cfg(all(feature = "std", not(debug_assertions)))]
+static INPUT: common::input::PuzzleInput =
+    common::input::PuzzleInput::personal(env!("CARGO_MANIFEST_DIR"));
 
-fn get_scenic_score(grid: &[Vec<u32>], x: usize, y: usize) -> usize {
-    if x == 0 || y == 0 || x == grid.len() - 1 || y == grid[x].len() - 1 {
-        return 0;
-    }
+type ParsedInput = Grid<u32>;
 
-    DIRECTIONS.iter().fold(1, |result, (dx, dy)| {
-        let mut px = ((x as i32) + *dx) as usize;
-        let mut py = ((y as i32) + *dy) as usize;
+fn get_scenic_score(grid: &ParsedInput, pos: Pos) -> usize {
+    ORTHOGONAL.iter().fold(1, |result, &dir| {
         let mut view_dist = 0;
-        loop {
+        for tree in grid.ray(pos, dir) {
             view_dist += 1;
-            if px == 0
-                || py == 0
-                || px == grid.len() - 1
-                || py == grid[px].len() - 1
-                || grid[px][py] >= grid[x][y]
-            {
+            if grid[tree] >= grid[pos] {
                 break;
             }
-            px = ((px as i32) + *dx) as usize;
-            py = ((py as i32) + *dy) as usize;
         }
         result * view_dist
     })
 }
 
-fn is_tree_visible(grid: &[Vec<u32>], x: usize, y: usize) -> bool {
-    if x == 0 || y == 0 || x == grid.len() - 1 || y == grid[x].len() - 1 {
-        return true;
-    }
-    for (dx, dy) in &DIRECTIONS {
-        let mut px = ((x as i32) + *dx) as usize;
-        let mut py = ((y as i32) + *dy) as usize;
-        while grid[x][y] > grid[px][py] {
-            if px == 0
-                || py == 0
-                || px == grid.len() - 1
-                || py == grid[px].len() - 1
-            {
-                return true;
-            }
-            px = ((px as i32) + *dx) as usize;
-            py = ((py as i32) + *dy) as usize;
-        }
-    }
-    false
+fn is_tree_visible(grid: &ParsedInput, pos: Pos) -> bool {
+    ORTHOGONAL
+        .iter()
+        .any(|&dir| grid.ray(pos, dir).all(|tree| grid[tree] < grid[pos]))
 }
 
 fn solve_part_one(input: &ParsedInput) -> usize {
     input
-        .iter()
-        .enumerate()
-        .map(|(x, row)| {
-            row.iter()
-                .enumerate()
-                .filter(|(y, _)| is_tree_visible(input, x, *y))
-                .count()
-        })
-        .sum::<usize>()
+        .positions()
+        .filter(|&pos| is_tree_visible(input, pos))
+        .count()
+}
+
+/// Marks trees visible from each edge with a running maximum per line,
+/// visiting every tree a constant number of times.
+fn solve_part_one_sweep(input: &ParsedInput) -> usize {
+    let mut visible = Grid::new(input.width(), input.height(), false);
+    let mut sweep = |cells: &mut dyn Iterator<Item = Pos>| {
+        let mut tallest = None;
+        for pos in cells {
+            if tallest < Some(input[pos]) {
+                visible[pos] = true;
+                tallest = Some(input[pos]);
+            }
+        }
+    };
+    let (width, height) = (input.width(), input.height());
+    for y in 0..height {
+        sweep(&mut (0..width).map(|x| (x, y)));
+        sweep(&mut (0..width).rev().map(|x| (x, y)));
+    }
+    for x in 0..width {
+        sweep(&mut (0..height).map(|y| (x, y)));
+        sweep(&mut (0..height).rev().map(|y| (x, y)));
+    }
+    visible.iter().filter(|(_, &v)| v).count()
 }
 
 fn solve_part_two(input: &ParsedInput) -> usize {
     input
-        .iter()
-        .enumerate()
-        .map(|(x, row)| {
-            row.iter()
-                .enumerate()
-                .map(|(y, _)| get_scenic_score(input, x, y))
-                .max()
-        })
+        .positions()
+        .map(|pos| get_scenic_score(input, pos))
         .max()
-        .flatten()
         .unwrap()
 }
 
 fn parse_input(input: &str) -> ParsedInput {
-    input
-        .lines()
-        .map(|line| line.chars().map_while(|c| c.to_digit(10)).collect())
-        .collect()
+    Grid::parse(input, |c| c.to_digit(10).unwrap()).unwrap()
 }
 
-pub fn solve() {
-    let input = parse_input(INPUT);
+#[cfg(feature = "std")]
+pub fn solve() -> Result<(), String> {
+    let input = normalize(INPUT.get()?);
+    let input = parse_input(&input);
 
     let result = solve_part_one(&input);
     println!("Part #1: {}", result);
 
     let result = solve_part_two(&input);
     println!("Part #2: {}", result);
+    Ok(())
 }
 
+pub const DAY: Day = Day {
+    day: 8,
+    sample: include_str!("../sample.TXT"),
+    part_one: implementations!(parse_input;
+        "scan" => solve_part_one,
+        "sweep" => solve_part_one_sweep,
+    ),
+    part_two: implementations!(parse_input, solve_part_two),
+    generate: gen::generate,
+    inspect: |input| format!("{:#?}", parse_input(&normalize(input))),
+    commands: &[],
+    simulations: &[],
+};
+
+export_plugin!(DAY);
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let result = solve_part_one(&parse_input(INPUT));
+        let result =
+            solve_part_one(&parse_input(&normalize(INPUT.get().unwrap())));
 
         #[cfg(debug_assertions)]
         assert_eq!(result, 21);
@@ -121,13 +132,33 @@ mod tests {
 
     #[bench]
     fn bench_part_one(b: &mut test::Bencher) {
-        let input = parse_input(INPUT);
+        let input = normalize(INPUT.get().unwrap());
+        let input = parse_input(&input);
         b.iter(|| solve_part_one(&input));
     }
 
+    #[test]
+    fn test_part_one_sweep() {
+        let input = normalize(INPUT.get().unwrap());
+        let input = parse_input(&input);
+        assert_eq!(solve_part_one_sweep(&input), solve_part_one(&input));
+        for seed in 0..10 {
+            let input = parse_input(&gen::generate(seed, 30));
+            assert_eq!(solve_part_one_sweep(&input), solve_part_one(&input));
+        }
+    }
+
+    #[bench]
+    fn bench_part_one_sweep(b: &mut test::Bencher) {
+        let input = normalize(INPUT.get().unwrap());
+        let input = parse_input(&input);
+        b.iter(|| solve_part_one_sweep(&input));
+    }
+
     #[test]
     fn test_part_two() {
-        let result = solve_part_two(&parse_input(INPUT));
+        let result =
+            solve_part_two(&parse_input(&normalize(INPUT.get().unwrap())));
 
         #[cfg(debug_assertions)]
         assert_eq!(result, 8);
@@ -138,7 +169,21 @@ mod tests {
 
     #[bench]
     fn bench_part_two(b: &mut test::Bencher) {
-        let input = parse_input(INPUT);
+        let input = normalize(INPUT.get().unwrap());
+        let input = parse_input(&input);
         b.iter(|| solve_part_two(&input));
     }
+
+    #[test]
+    fn test_generated_input_parses() {
+        for seed in 0..10 {
+            let input = parse_input(&gen::generate(seed, 50));
+            assert_eq!((input.width(), input.height()), (50, 50));
+        }
+    }
+
+    #[test]
+    fn test_sample_variants() {
+        common::input::assert_variants(&DAY, INPUT.get().unwrap());
+    }
 }
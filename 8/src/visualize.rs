@@ -0,0 +1,54 @@
+//! The forest as a picture, taller trees in lighter greens. Part one dims
+//! the trees hidden from every edge, part two marks the tree with the best
+//! view in red.
+
+use crate::{get_scenic_score, is_tree_visible, parse_input};
+use alloc::{format, string::String};
+use common::image::{Image, Rgb};
+use common::input::normalize;
+
+const SHORT: Rgb = Rgb(10, 40, 10);
+const TALL: Rgb = Rgb(140, 230, 80);
+const BEST: Rgb = Rgb(255, 30, 30);
+
+pub fn image(input: &str, part: u8) -> Result<Image, String> {
+    let forest = parse_input(&normalize(input));
+    let mut image = Image::from_grid(&forest, |&h| SHORT.blend(TALL, h, 9));
+    match part {
+        1 => {
+            for (x, y) in forest.positions() {
+                if !is_tree_visible(&forest, (x, y)) {
+                    let color = image.get(x, y).unwrap();
+                    image.set(x, y, color.blend(Rgb::BLACK, 2, 3));
+                }
+            }
+        }
+        2 => {
+            let (x, y) = forest
+                .positions()
+                .max_by_key(|&pos| get_scenic_score(&forest, pos))
+                .ok_or("no trees")?;
+            image.set(x, y, BEST);
+        }
+        part => return Err(format!("no part {}", part)),
+    }
+    Ok(image)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_image() {
+        let input = include_str!("../sample.TXT");
+        let forest = image(input, 2).unwrap();
+        assert_eq!((forest.width(), forest.height()), (5, 5));
+        assert_eq!(forest.get(2, 3), Some(BEST));
+        assert_eq!(forest.get(0, 0), Some(SHORT.blend(TALL, 3, 9)));
+        let hidden = image(input, 1).unwrap();
+        assert_ne!(hidden.get(3, 3), forest.get(3, 3));
+        assert_eq!(hidden.get(1, 1), forest.get(1, 1));
+        assert!(image(input, 3).is_err());
+    }
+}
@@ -0,0 +1,13 @@
+use alloc::{format, string::String};
+use common::rng::Rng;
+
+/// Generates `size` head motions of one to twenty steps each.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    (0..size.max(1))
+        .map(|_| {
+            let dir = rng.choose(&['U', 'D', 'L', 'R']);
+            format!("{} {}\n", dir, rng.range(1..=20))
+        })
+        .collect()
+}
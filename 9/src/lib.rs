@@ -1,143 +1,241 @@
-#![feature(test)]
-
-use std::collections::BTreeSet;
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(test, feature(test))]
+extern crate alloc;
+#[cfg(test)]
 extern crate test;
 
-#[cfg(debug_assertions)]
-const INPUT: &str = include_str!("../sample.TXT");
+use alloc::collections::BTreeSet;
+use alloc::{format, string::String, vec::Vec};
+use common::geometry::{BoundingBox, Direction, Point};
+use common::grid::Grid;
+use common::input::normalize;
+use common::simulation::{playback, Simulation, Simulator};
+use common::{export_plugin, implementations, Day};
+use core::{fmt, iter};
 
-#[cfg(not(debug_assertions))]
-const INPUT: &str = include_str!("../input.TXT");
+mod gen;
+#[cfg(feature = "std")]
+pub mod stream;
+#[cfg(feature = "visualize")]
+pub mod visualize;
 
-#[derive(Debug, Copy, Clone)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
+#[cfg(all(feature = "std", debug_assertions))]
+static INPUT: common::input::PuzzleInput =
+    common::input::PuzzleInput::sample(include_str!("../sample.TXT"));
+#[cfg(all(feature = "std", not(debug_assertions)))]
+static INPUT: common::input::PuzzleInput =
+    common::input::PuzzleInput::personal(env!("CARGO_MANIFEST_DIR"));
 
 #[derive(Debug, Copy, Clone)]
 struct Step(Direction, i32);
 
 type ParsedInput = Vec<Step>;
-type Coord = (i32, i32);
-
-const ADJACENCY_MAP: [(i32, i32); 9] = [
-    (0, -1),
-    (-1, 0),
-    (1, 0),
-    (0, 1),
-    (0, 0),
-    (-1, -1),
-    (1, -1),
-    (-1, 1),
-    (1, 1),
-];
-
-fn is_adjacent((x, y): Coord, (tx, ty): Coord) -> bool {
-    let (dx, dy) = (x - tx, y - ty);
-    dx.abs() <= 1 && dy.abs() <= 1
+
+/// Where the two knots of part one's rope start.
+const START_SHORT: Point = Point::new(0, 4);
+/// Where the ten knots of part two's rope start.
+const START_LONG: Point = Point::new(11, 15);
+
+/// Where a knot moves when the one ahead of it is at `head`.
+fn follow_head(head: Point, tail: Point) -> Point {
+    if tail.touches(head) {
+        tail
+    } else {
+        tail.step_toward(head)
+    }
+}
+
+/// A rope of `N` knots and the positions its tail has visited.
+#[derive(Clone)]
+struct Rope<const N: usize> {
+    knots: [Point; N],
+    visited: BTreeSet<Point>,
 }
 
-fn follow_head(head: Coord, (tx, ty): Coord) -> Coord {
-    if is_adjacent(head, (tx, ty)) {
-        return (tx, ty);
-    }
-    let (dx, dy) = (head.0 - tx, head.1 - ty);
-    if dx <= 1 || dy <= 1 {
-        for (dx, dy) in ADJACENCY_MAP[..4].iter() {
-            let px = tx + dx;
-            let py = ty + dy;
-            let (dx, dy) = (head.0 - px, head.1 - py);
-            if is_adjacent(head, (px, py)) && (dx == 0 || dy == 0) {
-                return (px, py);
-            }
+impl<const N: usize> Rope<N> {
+    fn new(start: Point) -> Self {
+        Self {
+            knots: [start; N],
+            visited: BTreeSet::new(),
         }
     }
-    for (dx, dy) in ADJACENCY_MAP[5..].iter() {
-        let px = tx + dx;
-        let py = ty + dy;
-        if is_adjacent(head, (px, py)) {
-            return (px, py);
+
+    /// Moves the head one square, the other knots following.
+    fn pull(&mut self, d: Direction) {
+        self.knots[0] += d.delta();
+        for i in 1..N {
+            self.knots[i] = follow_head(self.knots[i - 1], self.knots[i]);
         }
+        self.visited.insert(self.knots[N - 1]);
     }
-    unreachable!();
 }
 
-fn apply_step((x, y): Coord, step: Step) -> Coord {
-    match step {
-        Step(Direction::Up, n) => (x, y - n),
-        Step(Direction::Down, n) => (x, y + n),
-        Step(Direction::Left, n) => (x - n, y),
-        Step(Direction::Right, n) => (x + n, y),
+/// The rope pulled through the motions, one square per step.
+struct Motions<const N: usize> {
+    start: Point,
+    /// The direction of every square the head moves.
+    pulls: Vec<Direction>,
+    /// The rope and the number of squares moved.
+    state: (Rope<N>, usize),
+}
+
+impl<const N: usize> Motions<N> {
+    fn new(motions: &[Step], start: Point) -> Self {
+        Self {
+            start,
+            pulls: motions
+                .iter()
+                .flat_map(|&Step(d, c)| iter::repeat_n(d, c as usize))
+                .collect(),
+            state: (Rope::new(start), 0),
+        }
+    }
+
+    fn visited(&self) -> usize {
+        self.state.0.visited.len()
     }
 }
 
-fn solve_part_one(input: &ParsedInput) -> usize {
-    let (mut head, mut tail) = ((0, 4), (0, 4));
-    let mut visited = BTreeSet::new();
-    for Step(d, c) in input {
-        for _ in 0..*c {
-            head = apply_step(head, Step(*d, 1));
-            tail = follow_head(head, tail);
-            visited.insert(tail);
+impl<const N: usize> Simulation for Motions<N> {
+    type Snapshot = (Rope<N>, usize);
+
+    fn step(&mut self) -> bool {
+        let (rope, moved) = &mut self.state;
+        let Some(&d) = self.pulls.get(*moved) else {
+            return false;
+        };
+        rope.pull(d);
+        *moved += 1;
+        true
+    }
+
+    fn steps(&self) -> usize {
+        self.state.1
+    }
+
+    fn snapshot(&self) -> Self::Snapshot {
+        self.state.clone()
+    }
+
+    fn restore(&mut self, snapshot: &Self::Snapshot) {
+        self.state = snapshot.clone();
+    }
+}
+
+/// The knots over the visited positions, like the puzzle's diagrams: `H`
+/// for the head, then the knot numbers (`T` for a two-knot tail), `#` for
+/// visited and `s` for the start.
+impl<const N: usize> fmt::Display for Motions<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rope = &self.state.0;
+        let points = rope.knots.iter().chain(&rope.visited);
+        let bounds = BoundingBox::from_points(points.copied())
+            .unwrap()
+            .including(self.start);
+        let mut map =
+            Grid::new(bounds.width() as usize, bounds.height() as usize, '.');
+        let pos = |p: Point| {
+            let offset = p - bounds.min;
+            (offset.x as usize, offset.y as usize)
+        };
+        for &p in &rope.visited {
+            map[pos(p)] = '#';
         }
+        map[pos(self.start)] = 's';
+        for (i, &knot) in rope.knots.iter().enumerate().rev() {
+            map[pos(knot)] = match i {
+                0 => 'H',
+                1 if N == 2 => 'T',
+                i => char::from_digit(i as u32, 36).unwrap(),
+            };
+        }
+        write!(f, "{}\n{} visited", map, self.visited())
     }
-    visited.len()
+}
+
+fn solve_part_one(input: &ParsedInput) -> usize {
+    let mut motions = Motions::<2>::new(input, START_SHORT);
+    motions.run();
+    motions.visited()
 }
 
 fn solve_part_two(input: &ParsedInput) -> usize {
-    let mut knots = [(11, 15); 10];
-    let mut visited = BTreeSet::new();
-    for Step(d, c) in input {
-        for _ in 0..*c {
-            knots[0] = apply_step(knots[0], Step(*d, 1));
-            for i in 1..knots.len() {
-                knots[i] = follow_head(knots[i - 1], knots[i]);
-                if i == knots.len() - 1 {
-                    visited.insert(knots[i]);
-                }
-            }
-        }
+    let mut motions = Motions::<10>::new(input, START_LONG);
+    motions.run();
+    motions.visited()
+}
+
+fn parse_line(line: &str) -> Result<Step, String> {
+    let invalid = || format!("invalid motion: {:?}", line);
+    let (dir, dist) = line.split_once(' ').ok_or_else(invalid)?;
+    let dist = dist.parse().map_err(|_| invalid())?;
+    match dir {
+        "U" => Ok(Step(Direction::Up, dist)),
+        "D" => Ok(Step(Direction::Down, dist)),
+        "L" => Ok(Step(Direction::Left, dist)),
+        "R" => Ok(Step(Direction::Right, dist)),
+        _ => Err(invalid()),
     }
-    visited.len()
 }
 
 fn parse_input(input: &str) -> ParsedInput {
     input
         .lines()
-        .map(|line| {
-            let (dir, dist) = line.split_once(' ').unwrap();
-            let dist = dist.parse().unwrap();
-            match dir {
-                "U" => Step(Direction::Up, dist),
-                "D" => Step(Direction::Down, dist),
-                "L" => Step(Direction::Left, dist),
-                "R" => Step(Direction::Right, dist),
-                _ => panic!("Unknown direction"),
-            }
-        })
+        .map(|line| parse_line(line).unwrap())
         .collect()
 }
 
-pub fn solve() {
-    let input = parse_input(INPUT);
+#[cfg(feature = "std")]
+pub fn solve() -> Result<(), String> {
+    let input = normalize(INPUT.get()?);
+    let input = parse_input(&input);
 
     let result = solve_part_one(&input);
     println!("Part #1: {}", result);
 
     let result = solve_part_two(&input);
     println!("Part #2: {}", result);
+    Ok(())
 }
 
+pub const DAY: Day = Day {
+    day: 9,
+    sample: include_str!("../sample.TXT"),
+    part_one: implementations!(parse_input, solve_part_one),
+    part_two: implementations!(parse_input, solve_part_two),
+    generate: gen::generate,
+    inspect: |input| format!("{:#?}", parse_input(&normalize(input))),
+    commands: &[],
+    simulations: &[
+        Simulator {
+            name: "2",
+            help: "a rope with a head and a tail",
+            start: |input| {
+                let input = parse_input(&normalize(input));
+                Ok(playback(Motions::<2>::new(&input, START_SHORT)))
+            },
+        },
+        Simulator {
+            name: "10",
+            help: "a rope of ten knots",
+            start: |input| {
+                let input = parse_input(&normalize(input));
+                Ok(playback(Motions::<10>::new(&input, START_LONG)))
+            },
+        },
+    ],
+};
+
+export_plugin!(DAY);
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let result = solve_part_one(&parse_input(INPUT));
+        let result =
+            solve_part_one(&parse_input(&normalize(INPUT.get().unwrap())));
 
         #[cfg(debug_assertions)]
         assert_eq!(result, 88);
@@ -148,13 +246,15 @@ mod tests {
 
     #[bench]
     fn bench_part_one(b: &mut test::Bencher) {
-        let input = parse_input(INPUT);
+        let input = normalize(INPUT.get().unwrap());
+        let input = parse_input(&input);
         b.iter(|| solve_part_one(&input));
     }
 
     #[test]
     fn test_part_two() {
-        let result = solve_part_two(&parse_input(INPUT));
+        let result =
+            solve_part_two(&parse_input(&normalize(INPUT.get().unwrap())));
 
         #[cfg(debug_assertions)]
         assert_eq!(result, 36);
@@ -165,7 +265,36 @@ mod tests {
 
     #[bench]
     fn bench_part_two(b: &mut test::Bencher) {
-        let input = parse_input(INPUT);
+        let input = normalize(INPUT.get().unwrap());
+        let input = parse_input(&input);
         b.iter(|| solve_part_two(&input));
     }
+
+    #[test]
+    fn test_rope_replay() {
+        let mut rope =
+            (DAY.simulations[0].start)(INPUT.get().unwrap()).unwrap();
+        assert!(rope.seek(4));
+        assert_eq!(rope.render(), "s##TH\n4 visited");
+        assert!(rope.seek(5) && rope.seek(3));
+        assert_eq!(rope.render(), "s#TH\n3 visited");
+        let mut rope =
+            (DAY.simulations[1].start)(INPUT.get().unwrap()).unwrap();
+        assert!(rope.seek(5));
+        assert_eq!(rope.render(), "54321H\n1 visited");
+        assert!(!rope.seek(1000));
+    }
+
+    #[test]
+    fn test_generated_input_parses() {
+        for seed in 0..10 {
+            let input = parse_input(&gen::generate(seed, 50));
+            assert_eq!(input.len(), 50);
+        }
+    }
+
+    #[test]
+    fn test_sample_variants() {
+        common::input::assert_variants(&DAY, INPUT.get().unwrap());
+    }
 }
@@ -0,0 +1,50 @@
+//! Solvers reading the motions from a [`BufRead`], one at a time. Memory
+//! grows with the number of positions the tail visits, not with the input.
+
+use crate::{parse_line, Rope, Step, START_LONG, START_SHORT};
+use common::input::for_each_line;
+use std::io::{self, BufRead};
+
+pub fn solve_part_one(reader: impl BufRead) -> io::Result<usize> {
+    let mut rope = Rope::<2>::new(START_SHORT);
+    for_each_line(reader, |line| {
+        let Step(d, c) = parse_line(line)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        (0..c).for_each(|_| rope.pull(d));
+        Ok(())
+    })?;
+    Ok(rope.visited.len())
+}
+
+pub fn solve_part_two(reader: impl BufRead) -> io::Result<usize> {
+    let mut rope = Rope::<10>::new(START_LONG);
+    for_each_line(reader, |line| {
+        let Step(d, c) = parse_line(line)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        (0..c).for_each(|_| rope.pull(d));
+        Ok(())
+    })?;
+    Ok(rope.visited.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_input, INPUT};
+    use common::input::normalize;
+
+    #[test]
+    fn test_matches_in_memory() {
+        let input = parse_input(&normalize(INPUT.get().unwrap()));
+        for variant in common::input::variants(INPUT.get().unwrap()) {
+            assert_eq!(
+                solve_part_one(variant.as_bytes()).unwrap(),
+                crate::solve_part_one(&input)
+            );
+            assert_eq!(
+                solve_part_two(variant.as_bytes()).unwrap(),
+                crate::solve_part_two(&input)
+            );
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! The rope and its tail's trail as pictures: the visited squares in gray,
+//! the start in blue and the knots from red (the head) to orange.
+
+use crate::{parse_input, Motions, START_LONG, START_SHORT};
+use alloc::{format, string::String};
+use common::geometry::{BoundingBox, Point};
+use common::image::{Animation, Image, Rgb};
+use common::input::normalize;
+use common::simulation::Simulation;
+
+const BACKGROUND: Rgb = Rgb(16, 16, 24);
+const VISITED: Rgb = Rgb(110, 110, 120);
+const START: Rgb = Rgb(60, 120, 255);
+const HEAD: Rgb = Rgb(255, 40, 40);
+const TAIL: Rgb = Rgb(255, 190, 40);
+
+/// The tail's trail once every motion is done.
+pub fn image(input: &str, part: u8) -> Result<Image, String> {
+    let input = parse_input(&normalize(input));
+    match part {
+        1 => Ok(final_frame(Motions::<2>::new(&input, START_SHORT))),
+        2 => Ok(final_frame(Motions::<10>::new(&input, START_LONG))),
+        part => Err(format!("no part {}", part)),
+    }
+}
+
+/// The rope moving, a frame every `every` squares.
+pub fn animation(
+    input: &str,
+    part: u8,
+    every: usize,
+) -> Result<Animation, String> {
+    let input = parse_input(&normalize(input));
+    match part {
+        1 => record(Motions::<2>::new(&input, START_SHORT), every),
+        2 => record(Motions::<10>::new(&input, START_LONG), every),
+        part => Err(format!("no part {}", part)),
+    }
+}
+
+fn final_frame<const N: usize>(mut motions: Motions<N>) -> Image {
+    let bounds = bounds(&motions);
+    motions.run();
+    frame(&motions, bounds)
+}
+
+fn record<const N: usize>(
+    mut motions: Motions<N>,
+    every: usize,
+) -> Result<Animation, String> {
+    let bounds = bounds(&motions);
+    Animation::record(&mut motions, every, 4, |m| frame(m, bounds))
+}
+
+/// Every knot stays within the box around the head's path, since each one
+/// only ever steps towards the knot ahead of it.
+fn bounds<const N: usize>(motions: &Motions<N>) -> BoundingBox {
+    let path = motions.pulls.iter().scan(motions.start, |head, d| {
+        *head += d.delta();
+        Some(*head)
+    });
+    path.fold(BoundingBox::new(motions.start), BoundingBox::including)
+}
+
+fn frame<const N: usize>(motions: &Motions<N>, bounds: BoundingBox) -> Image {
+    let mut image = Image::new(
+        bounds.width() as usize,
+        bounds.height() as usize,
+        BACKGROUND,
+    );
+    let mut plot = |p: Point, color| {
+        let offset = p - bounds.min;
+        image.set(offset.x as usize, offset.y as usize, color);
+    };
+    let rope = &motions.state.0;
+    rope.visited.iter().for_each(|&p| plot(p, VISITED));
+    plot(motions.start, START);
+    for (i, &knot) in rope.knots.iter().enumerate().rev() {
+        plot(knot, HEAD.blend(TAIL, i as u32, N as u32 - 1));
+    }
+    image
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_images() {
+        let input = include_str!("../sample.TXT");
+        let trail = image(input, 2).unwrap();
+        let visited = (0..trail.width())
+            .flat_map(|x| (0..trail.height()).map(move |y| (x, y)))
+            .filter(|&(x, y)| trail.get(x, y) != Some(BACKGROUND))
+            .count();
+        assert!(visited >= 36);
+        assert_eq!((trail.width(), trail.height()), (26, 21));
+
+        let animation = animation(input, 2, 10).unwrap();
+        assert_eq!(animation.frames().len(), 11);
+        assert_eq!(animation.frames().last(), Some(&trail));
+        assert!(image(input, 3).is_err());
+    }
+}
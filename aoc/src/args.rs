@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Minimal command line parser: positional arguments, a declared set of
+/// `--name value` options and a declared set of boolean `--flag`s. Any other
+/// `--name` is an error, so a typo is not silently ignored.
+#[derive(Debug, Default)]
+pub struct Args {
+    positional: Vec<String>,
+    options: HashMap<String, String>,
+}
+
+impl Args {
+    pub fn parse<I>(
+        args: I,
+        options: &[&str],
+        flags: &[&str],
+    ) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut parsed = Args::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let Some(name) = arg.strip_prefix("--") else {
+                parsed.positional.push(arg);
+                continue;
+            };
+            let (name, value) = match name.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (name, None),
+            };
+            if !options.contains(&name) && !flags.contains(&name) {
+                return Err(format!("unknown option: --{}", name));
+            }
+            let value = match value {
+                Some(value) => value,
+                None if flags.contains(&name) => String::new(),
+                None => args
+                    .next()
+                    .ok_or_else(|| format!("missing value for --{}", name))?,
+            };
+            parsed.options.insert(name.to_string(), value);
+        }
+        Ok(parsed)
+    }
+
+    pub fn positional<T: FromStr>(
+        &self,
+        idx: usize,
+        name: &str,
+    ) -> Result<T, String> {
+        let value = self
+            .positional
+            .get(idx)
+            .ok_or_else(|| format!("missing <{}>", name))?;
+        value
+            .parse()
+            .map_err(|_| format!("invalid <{}>: {}", name, value))
+    }
+
+    /// Like [`positional`], with `None` when the argument is missing.
+    ///
+    /// [`positional`]: Self::positional
+    pub fn optional_positional<T: FromStr>(
+        &self,
+        idx: usize,
+        name: &str,
+    ) -> Result<Option<T>, String> {
+        match self.positional.get(idx) {
+            Some(_) => self.positional(idx, name).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    pub fn option<T: FromStr>(&self, name: &str) -> Result<Option<T>, String> {
+        self.options
+            .get(name)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| format!("invalid --{}: {}", name, value))
+            })
+            .transpose()
+    }
+
+    pub fn flag(&self, name: &str) -> bool {
+        self.options.contains_key(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Args {
+        let args = args.iter().map(|s| s.to_string());
+        Args::parse(args, &["seed", "size"], &["verbose"]).unwrap()
+    }
+
+    #[test]
+    fn test_positional_and_options() {
+        let args = parse(&["9", "--seed", "3", "--size=40", "--verbose"]);
+        assert_eq!(args.positional::<u8>(0, "day"), Ok(9));
+        assert_eq!(args.option::<u64>("seed"), Ok(Some(3)));
+        assert_eq!(args.option::<usize>("size"), Ok(Some(40)));
+        assert_eq!(args.option::<usize>("missing"), Ok(None));
+        assert!(args.flag("verbose"));
+    }
+
+    #[test]
+    fn test_errors() {
+        let try_parse = |args: &[&str]| {
+            let args = args.iter().map(|s| s.to_string());
+            Args::parse(args, &["seed"], &["verbose"])
+        };
+        assert!(try_parse(&["--seed"]).is_err());
+        assert_eq!(
+            try_parse(&["1", "--sed", "3"]).unwrap_err(),
+            "unknown option: --sed"
+        );
+        assert!(try_parse(&["--input=x"]).is_err());
+        let args = parse(&["x", "--seed", "y"]);
+        assert!(args.positional::<u8>(0, "day").is_err());
+        assert!(args.positional::<u8>(1, "day").is_err());
+        assert!(args.optional_positional::<u8>(0, "day").is_err());
+        assert_eq!(args.optional_positional::<u8>(1, "day"), Ok(None));
+        assert!(args.option::<u64>("seed").is_err());
+    }
+}
@@ -0,0 +1,83 @@
+use aoc::args::Args;
+use aoc::history::{self, Record, Revision};
+use aoc::RunError;
+use std::time::Duration;
+
+const DEFAULT_RUNS: usize = 10;
+const DEFAULT_TIMEOUT: f64 = 60.0;
+
+/// Times every implementation of a day's parts side by side, adding the
+/// timings to the history unless `--no-history` is given. An
+/// implementation that panics, or takes over `--timeout` seconds a run, is
+/// reported and left out.
+pub fn run(args: impl Iterator<Item = String>) -> Result<(), String> {
+    let args = Args::parse(
+        args,
+        &["runs", "seed", "size", "timeout"],
+        &["no-history"],
+    )?;
+    let day = super::day(args.positional(0, "day")?)?;
+    let runs = args.option("runs")?.unwrap_or(DEFAULT_RUNS).max(1);
+    let timeout = args.option("timeout")?.unwrap_or(DEFAULT_TIMEOUT);
+    let timeout = Some(Duration::from_secs_f64(timeout));
+    let (input, source) =
+        match (args.option::<u64>("seed")?, args.option("size")?) {
+            (None, None) => match aoc::read_input(day.day)? {
+                Some(input) => (input, "input".to_string()),
+                None => (day.sample.to_string(), "sample".to_string()),
+            },
+            (seed, size) => {
+                let (seed, size) = (seed.unwrap_or(0), size.unwrap_or(100));
+                let source = format!("seed {} size {}", seed, size);
+                ((day.generate)(seed, size), source)
+            }
+        };
+
+    let revision = Revision::current(&aoc::workspace_dir());
+    let mut records = Vec::new();
+    for (part, implementations) in [(1, day.part_one), (2, day.part_two)] {
+        println!("day {} part {}:", day.day, part);
+        let mut reference = None;
+        for implementation in implementations {
+            let timings =
+                match aoc::timings(implementation, &input, runs, timeout) {
+                    Ok(timings) => timings,
+                    Err(RunError::Panicked(err)) => {
+                        println!(
+                            "  {:<12} panic: {}",
+                            implementation.name, err
+                        );
+                        continue;
+                    }
+                    Err(err) => {
+                        println!("  {:<12} {}", implementation.name, err);
+                        continue;
+                    }
+                };
+            let median = timings[runs / 2];
+            let reference = *reference.get_or_insert(median);
+            println!(
+                "  {:<12} median {:>12?}  min {:>12?}  {:>6.2}x",
+                implementation.name,
+                median,
+                timings[0],
+                ratio(reference, median),
+            );
+            records.push(Record::new(
+                &revision,
+                (day.day, part),
+                implementation.name,
+                &source,
+                &timings,
+            ));
+        }
+    }
+    if !args.flag("no-history") {
+        history::append(&history::path(), &records)?;
+    }
+    Ok(())
+}
+
+fn ratio(reference: Duration, timing: Duration) -> f64 {
+    reference.as_secs_f64() / timing.as_secs_f64().max(f64::EPSILON)
+}
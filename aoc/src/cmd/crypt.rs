@@ -0,0 +1,102 @@
+use aoc::args::Args;
+use common::crypt::{self, Secret, ENCRYPTED_INPUT, INPUT};
+use common::Day;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+/// Encrypts the days' `input.TXT` to the `input.TXT.enc` next to them,
+/// creating a key file first if there is no key yet.
+pub fn encrypt(args: impl Iterator<Item = String>) -> Result<(), String> {
+    let days = days(&Args::parse(args, &[], &[])?)?;
+    let root = aoc::workspace_dir();
+    let secret = match Secret::find(&root)? {
+        Some(secret) => secret,
+        None => {
+            let path = crypt::key_path(&root);
+            let secret = Secret::generate(&path)?;
+            eprintln!(
+                "wrote a new key to {}, keep a copy: the encrypted inputs \
+                 cannot be read without it",
+                path.display()
+            );
+            secret
+        }
+    };
+    for day in days {
+        let dir = aoc::day_dir(day.day);
+        let Some(input) = read(&dir.join(INPUT))? else {
+            continue;
+        };
+        let path = dir.join(ENCRYPTED_INPUT);
+        // Rewritten only when the input changed, encrypting again would
+        // change every byte.
+        let current = read(&path)?.map(|data| crypt::decrypt(&data, &secret));
+        if let Some(Ok(current)) = current {
+            if current == input {
+                println!("day {}: {} is up to date", day.day, ENCRYPTED_INPUT);
+                continue;
+            }
+        }
+        let data = crypt::encrypt(&input, &secret)?;
+        fs::write(&path, data)
+            .map_err(|err| format!("{}: {}", path.display(), err))?;
+        println!("day {}: wrote {}", day.day, ENCRYPTED_INPUT);
+    }
+    Ok(())
+}
+
+/// Writes the days' `input.TXT` from their `input.TXT.enc`, never over a
+/// different one unless `--force` is given.
+pub fn decrypt(args: impl Iterator<Item = String>) -> Result<(), String> {
+    let args = Args::parse(args, &[], &["force"])?;
+    let days = days(&args)?;
+    let root = aoc::workspace_dir();
+    let secret =
+        Secret::find(&root)?.ok_or_else(|| crypt::missing_key(&root))?;
+    for day in days {
+        let dir = aoc::day_dir(day.day);
+        let path = dir.join(ENCRYPTED_INPUT);
+        let Some(data) = read(&path)? else {
+            continue;
+        };
+        let input = crypt::decrypt(&data, &secret)
+            .map_err(|err| format!("{}: {}", path.display(), err))?;
+        let plain = dir.join(INPUT);
+        match read(&plain)? {
+            Some(current) if current == input => {
+                println!("day {}: {} is up to date", day.day, INPUT);
+                continue;
+            }
+            Some(_) if !args.flag("force") => {
+                return Err(format!(
+                    "{}: differs from {}, --force to overwrite it",
+                    plain.display(),
+                    ENCRYPTED_INPUT
+                ))
+            }
+            _ => {}
+        }
+        fs::write(&plain, input)
+            .map_err(|err| format!("{}: {}", plain.display(), err))?;
+        println!("day {}: wrote {}", day.day, INPUT);
+    }
+    Ok(())
+}
+
+/// The day given on the command line, or all of them.
+fn days(args: &Args) -> Result<Vec<&'static Day>, String> {
+    match args.optional_positional(0, "day")? {
+        Some(day) => Ok(vec![super::day(day)?]),
+        None => Ok(aoc::days()),
+    }
+}
+
+/// The contents of a file, `None` if there is none.
+fn read(path: &Path) -> Result<Option<Vec<u8>>, String> {
+    match fs::read(path) {
+        Ok(data) => Ok(Some(data)),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(format!("{}: {}", path.display(), err)),
+    }
+}
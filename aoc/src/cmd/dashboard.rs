@@ -0,0 +1,91 @@
+use super::terminal::TerminalGuard;
+use aoc::args::Args;
+use aoc::dashboard::{Dashboard, Line, Style};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::style::{
+    Attribute, Color, Print, SetAttribute, SetForegroundColor,
+};
+use crossterm::{cursor, queue, terminal};
+use std::io::{self, Write};
+use std::time::Duration;
+
+/// How often the screen checks for finished runs while waiting for a key.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Full-screen table of every day, run from the keyboard. Plain escape
+/// sequences only, so it works in any terminal, including over SSH.
+pub fn run(args: impl Iterator<Item = String>) -> Result<(), String> {
+    let args = Args::parse(args, &[], &[])?;
+    let mut dashboard = Dashboard::new()?;
+    if let Ok(day) = args.positional(0, "day") {
+        dashboard.select(day);
+    }
+    let _terminal = TerminalGuard::enter().map_err(|err| err.to_string())?;
+    event_loop(&mut dashboard, &mut io::stdout()).map_err(|err| err.to_string())
+}
+
+fn event_loop(
+    dashboard: &mut Dashboard,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    draw(dashboard, out)?;
+    loop {
+        // Parts run on the dashboard's worker, keys are still read meanwhile.
+        if !event::poll(POLL_INTERVAL)? {
+            if dashboard.poll() {
+                draw(dashboard, out)?;
+            }
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            draw(dashboard, out)?;
+            continue;
+        };
+        if key.kind == KeyEventKind::Release {
+            continue;
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+            KeyCode::Char('c') if key.modifiers == KeyModifiers::CONTROL => {
+                return Ok(())
+            }
+            KeyCode::Up | KeyCode::Char('k') => dashboard.move_selection(-1),
+            KeyCode::Down | KeyCode::Char('j') => dashboard.move_selection(1),
+            KeyCode::PageUp => dashboard.move_selection(-5),
+            KeyCode::PageDown => dashboard.move_selection(5),
+            KeyCode::Home => dashboard.select(1),
+            KeyCode::End => dashboard.select(25),
+            KeyCode::Enter | KeyCode::Char('r') => dashboard.run_selected(),
+            KeyCode::Char('a') => dashboard.run_all(),
+            KeyCode::Char('s') => dashboard.toggle_input(),
+            _ => {}
+        }
+        dashboard.poll();
+        draw(dashboard, out)?;
+    }
+}
+
+/// Redraws the whole screen.
+fn draw(dashboard: &Dashboard, out: &mut impl Write) -> io::Result<()> {
+    let (width, height) = terminal::size()?;
+    let lines = dashboard.render(width as usize, height as usize);
+    queue!(out, terminal::Clear(terminal::ClearType::All))?;
+    for (y, Line { text, style }) in lines.iter().enumerate() {
+        queue!(out, cursor::MoveTo(0, y as u16))?;
+        match style {
+            Style::Normal => {}
+            Style::Header => queue!(out, SetAttribute(Attribute::Bold))?,
+            Style::Selected => queue!(out, SetAttribute(Attribute::Reverse))?,
+            Style::Good => queue!(out, SetForegroundColor(Color::Green))?,
+            Style::Bad => queue!(out, SetForegroundColor(Color::Red))?,
+            Style::Dim => queue!(out, SetForegroundColor(Color::DarkGrey))?,
+        }
+        queue!(
+            out,
+            Print(text),
+            SetAttribute(Attribute::Reset),
+            SetForegroundColor(Color::Reset)
+        )?;
+    }
+    out.flush()
+}
@@ -0,0 +1,81 @@
+use aoc::args::Args;
+use aoc::RunError;
+use common::{Day, Implementation};
+use std::time::Duration;
+
+const DEFAULT_SEEDS: u64 = 20;
+const DEFAULT_SIZE: usize = 50;
+const DEFAULT_TIMEOUT: f64 = 10.0;
+
+/// Runs every implementation of each part on the sample, the personal input
+/// and generated inputs, reporting inputs on which they disagree. Each run
+/// is cancelled after `--timeout` seconds.
+pub fn run(args: impl Iterator<Item = String>) -> Result<(), String> {
+    let args = Args::parse(args, &["seeds", "size", "timeout"], &[])?;
+    let days = match args.positional::<u8>(0, "day") {
+        Ok(day) => vec![super::day(day)?],
+        Err(_) => aoc::days(),
+    };
+    let seeds = args.option("seeds")?.unwrap_or(DEFAULT_SEEDS);
+    let size = args.option("size")?.unwrap_or(DEFAULT_SIZE);
+    let timeout = args.option("timeout")?.unwrap_or(DEFAULT_TIMEOUT);
+    let timeout = Some(Duration::from_secs_f64(timeout));
+
+    let mut disagreements = 0;
+    for day in days {
+        let mut inputs = vec![("sample".to_string(), day.sample.to_string())];
+        if let Some(input) = aoc::read_input(day.day)? {
+            inputs.push(("input".to_string(), input));
+        }
+        inputs.extend((0..seeds).map(|seed| {
+            let name = format!("seed {} size {}", seed, size);
+            (name, (day.generate)(seed, size))
+        }));
+
+        for (part, implementations) in parts(day) {
+            if implementations.len() < 2 {
+                continue;
+            }
+            let names = implementations.iter().map(|i| i.name);
+            println!(
+                "day {} part {}: {}",
+                day.day,
+                part,
+                names.collect::<Vec<_>>().join(", ")
+            );
+            for (name, input) in &inputs {
+                let results = implementations
+                    .iter()
+                    .map(|i| {
+                        let result =
+                            aoc::run_with_timeout(i, input, timeout, |_| {});
+                        (i.name, result)
+                    })
+                    .collect::<Vec<_>>();
+                if results.iter().all(|(_, r)| *r == results[0].1) {
+                    continue;
+                }
+                disagreements += 1;
+                println!("  {}: implementations disagree", name);
+                for (name, result) in results {
+                    match result {
+                        Ok(answer) => println!("    {:<12} {}", name, answer),
+                        Err(RunError::Panicked(err)) => {
+                            println!("    {:<12} panic: {}", name, err)
+                        }
+                        Err(err) => println!("    {:<12} {}", name, err),
+                    }
+                }
+            }
+            println!("  {} inputs checked", inputs.len());
+        }
+    }
+    match disagreements {
+        0 => Ok(()),
+        n => Err(format!("{} disagreement(s)", n)),
+    }
+}
+
+fn parts(day: &Day) -> [(u8, &'static [Implementation]); 2] {
+    [(1, day.part_one), (2, day.part_two)]
+}
@@ -0,0 +1,12 @@
+use aoc::args::Args;
+
+const DEFAULT_SIZE: usize = 100;
+
+pub fn run(args: impl Iterator<Item = String>) -> Result<(), String> {
+    let args = Args::parse(args, &["seed", "size"], &[])?;
+    let day = super::day(args.positional(0, "day")?)?;
+    let seed = args.option("seed")?.unwrap_or(0);
+    let size = args.option("size")?.unwrap_or(DEFAULT_SIZE);
+    print!("{}", (day.generate)(seed, size));
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use aoc::args::Args;
+use aoc::history::{self, Series};
+
+/// Prints how the timings of a part evolved across commits, or charts them
+/// as an SVG with `--svg`. The timings come from `aoc bench` and
+/// `aoc report`, `aoc solve` records none.
+pub fn run(args: impl Iterator<Item = String>) -> Result<(), String> {
+    let args = Args::parse(args, &["build", "impl", "input", "svg"], &[])?;
+    let day: u8 = args.positional(0, "day")?;
+    let part: u8 = args.positional(1, "part")?;
+    let path = history::path();
+    let records = history::load(&path)?;
+    let implementation = match args.option::<String>("impl")? {
+        Some(name) => name,
+        None => aoc::find_day(day)
+            .and_then(|day| match part {
+                1 => day.part_one.first(),
+                _ => day.part_two.first(),
+            })
+            .map_or("default", |implementation| implementation.name)
+            .to_string(),
+    };
+    // The input and build timed last, unless given.
+    let latest = records.iter().rev().find(|record| {
+        (record.day, record.part) == (day, part)
+            && record.implementation == implementation
+    });
+    let Some(latest) = latest else {
+        return Err(format!(
+            "no timings of day {} part {} ({}) in {} yet, run `aoc bench {}` \
+             or `aoc report`",
+            day,
+            part,
+            implementation,
+            path.display(),
+            day
+        ));
+    };
+    let input = args.option::<String>("input")?;
+    let build = args.option::<String>("build")?;
+    let series = Series {
+        day,
+        part,
+        implementation: &implementation,
+        input: input.as_deref().unwrap_or(&latest.input),
+        build: build.as_deref().unwrap_or(&latest.build),
+    };
+    let points = series.points(&records);
+    if points.is_empty() {
+        return Err(format!(
+            "no timings of day {} part {} ({}) on {} in a {} build",
+            day, part, implementation, series.input, series.build
+        ));
+    }
+
+    if let Some(out) = args.option::<String>("svg")? {
+        std::fs::write(&out, history::chart(&points))
+            .map_err(|err| format!("{}: {}", out, err))?;
+        eprintln!("Chart written to {}", out);
+        return Ok(());
+    }
+    println!(
+        "day {} part {}, {} on {}, {} build:\n",
+        day, part, implementation, series.input, series.build
+    );
+    print!("{}", history::table(&points));
+    Ok(())
+}
@@ -0,0 +1,21 @@
+pub mod bench;
+pub mod crypt;
+pub mod dashboard;
+pub mod difftest;
+pub mod gen;
+pub mod history;
+pub mod play;
+#[cfg(feature = "visualize")]
+pub mod render;
+pub mod repl;
+pub mod report;
+pub mod scale;
+pub mod solve;
+pub mod stream;
+mod terminal;
+
+use common::Day;
+
+fn day(day: u8) -> Result<&'static Day, String> {
+    aoc::find_day(day).ok_or_else(|| format!("day {} is not solved yet", day))
+}
@@ -0,0 +1,118 @@
+use super::terminal::TerminalGuard;
+use aoc::args::Args;
+use aoc::player::{self, Action, Player};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::style::Print;
+use crossterm::{cursor, queue, terminal};
+use std::io::{self, Write};
+
+/// Plays one of a day's simulations in the terminal, or writes its frames
+/// to a file with `--dump`.
+pub fn run(args: impl Iterator<Item = String>) -> Result<(), String> {
+    let args = Args::parse(
+        args,
+        &["crop", "dump", "every", "fps", "frames", "input"],
+        &[],
+    )?;
+    let day = super::day(args.positional(0, "day")?)?;
+    let simulator = match args.positional::<String>(1, "simulation") {
+        Ok(name) => day
+            .simulations
+            .iter()
+            .find(|s| s.name == name)
+            .ok_or_else(|| format!("unknown simulation: {}", name))?,
+        Err(_) => day
+            .simulations
+            .first()
+            .ok_or_else(|| format!("day {} has no simulations", day.day))?,
+    };
+    let input = match args.option::<String>("input")? {
+        Some(path) => std::fs::read_to_string(&path)
+            .map_err(|err| format!("{}: {}", path, err))?,
+        None => aoc::read_input(day.day)?.unwrap_or(day.sample.to_string()),
+    };
+    let sim = aoc::catch_panic(|| (simulator.start)(&input))
+        .map_err(|err| format!("panic: {}", err))??;
+    let fps = args.option("fps")?.unwrap_or(player::DEFAULT_FPS);
+    let mut player = Player::new(simulator.name, sim).with_fps(fps);
+    if let Some(view) = args.option::<String>("crop")? {
+        player = player.with_viewport(player::parse_viewport(&view)?);
+    }
+
+    if let Some(path) = args.option::<String>("dump")? {
+        let every = args.option("every")?.unwrap_or(1);
+        let frames = args.option("frames")?;
+        let error = |err: io::Error| format!("{}: {}", path, err);
+        let mut out =
+            io::BufWriter::new(std::fs::File::create(&path).map_err(error)?);
+        let written = player.dump(every, frames, &mut out).map_err(error)?;
+        out.flush().map_err(error)?;
+        eprintln!("wrote {} frames to {}", written, path);
+        return Ok(());
+    }
+    let _terminal = TerminalGuard::enter().map_err(|err| err.to_string())?;
+    event_loop(&mut player, &mut io::stdout()).map_err(|err| err.to_string())
+}
+
+fn event_loop(player: &mut Player, out: &mut impl Write) -> io::Result<()> {
+    let (width, height) = terminal::size()?;
+    // The last line is for the status.
+    player.fit(width as usize, (height as usize).saturating_sub(1));
+    loop {
+        draw(player, out)?;
+        // Waits for a key for at most a frame while playing.
+        let key = match player.frame_delay() {
+            Some(delay) if !event::poll(delay)? => {
+                player.tick();
+                continue;
+            }
+            _ => event::read()?,
+        };
+        let Event::Key(key) = key else {
+            continue;
+        };
+        if key.kind == KeyEventKind::Release {
+            continue;
+        }
+        let action = match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+            KeyCode::Char('c') if key.modifiers == KeyModifiers::CONTROL => {
+                return Ok(())
+            }
+            KeyCode::Char(' ') => Action::TogglePause,
+            KeyCode::Char('n') | KeyCode::Char('.') => Action::Step,
+            KeyCode::Char('b') | KeyCode::Char(',') => Action::Back,
+            KeyCode::Char('+') | KeyCode::Char('=') => Action::Faster,
+            KeyCode::Char('-') => Action::Slower,
+            KeyCode::Char('r') => Action::Restart,
+            KeyCode::Left | KeyCode::Char('h') => Action::Pan(-1, 0),
+            KeyCode::Right | KeyCode::Char('l') => Action::Pan(1, 0),
+            KeyCode::Up | KeyCode::Char('k') => Action::Pan(0, -1),
+            KeyCode::Down | KeyCode::Char('j') => Action::Pan(0, 1),
+            _ => continue,
+        };
+        player.apply(action);
+    }
+}
+
+/// Redraws the frame over the previous one, then the status line.
+fn draw(player: &Player, out: &mut impl Write) -> io::Result<()> {
+    let (width, _) = terminal::size()?;
+    queue!(out, cursor::MoveTo(0, 0))?;
+    for line in player.frame() {
+        let line = line.chars().take(width as usize).collect::<String>();
+        queue!(
+            out,
+            Print(line),
+            terminal::Clear(terminal::ClearType::UntilNewLine),
+            cursor::MoveToNextLine(1)
+        )?;
+    }
+    let status = player.status().chars().take(width as usize).collect();
+    queue!(
+        out,
+        terminal::Clear(terminal::ClearType::FromCursorDown),
+        Print::<String>(status)
+    )?;
+    out.flush()
+}
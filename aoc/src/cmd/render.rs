@@ -0,0 +1,60 @@
+use aoc::args::Args;
+use common::image::Image;
+use std::path::Path;
+
+/// Draws a part of a day into an image, or an animation for the simulated
+/// days, the format following the extension of `--out`.
+pub fn run(args: impl Iterator<Item = String>) -> Result<(), String> {
+    let args = Args::parse(args, &["every", "input", "out", "scale"], &[])?;
+    let day = super::day(args.positional(0, "day")?)?;
+    let part: u8 = args.positional(1, "part")?;
+    let renderer = aoc::renderer(day.day)
+        .ok_or_else(|| format!("day {} cannot be drawn", day.day))?;
+    let out = args.option::<String>("out")?.ok_or("missing --out")?;
+    let scale = args.option("scale")?.unwrap_or(4);
+    let input = match args.option::<String>("input")? {
+        Some(path) => std::fs::read_to_string(&path)
+            .map_err(|err| format!("{}: {}", path, err))?,
+        None => aoc::read_input(day.day)?.unwrap_or(day.sample.to_string()),
+    };
+
+    let path = Path::new(&out);
+    let extension = path.extension().and_then(|e| e.to_str());
+    let image = |input: &str| -> Result<Image, String> {
+        Ok((renderer.image)(input, part)?.scaled(scale))
+    };
+    let bytes = match extension {
+        Some("ppm") => image(&input)?.to_ppm(),
+        Some("pgm") => image(&input)?.to_pgm(),
+        Some("png") => image(&input)?.to_png(),
+        Some("gif") | None => {
+            let animate = renderer.animation.ok_or_else(|| {
+                format!("day {} is not animated, use a .png", day.day)
+            })?;
+            let every = args.option("every")?.unwrap_or(1);
+            let animation = animate(&input, part, every)?.scaled(scale);
+            if extension.is_none() {
+                return write_frames(path, animation.frames());
+            }
+            animation.to_gif()?
+        }
+        Some(extension) => {
+            return Err(format!("unknown image format: .{}", extension))
+        }
+    };
+    std::fs::write(path, &bytes).map_err(|err| format!("{}: {}", out, err))?;
+    eprintln!("wrote {} bytes to {}", bytes.len(), out);
+    Ok(())
+}
+
+/// The frames as numbered PNGs in `dir`.
+fn write_frames(dir: &Path, frames: &[Image]) -> Result<(), String> {
+    let error = |err: std::io::Error| format!("{}: {}", dir.display(), err);
+    std::fs::create_dir_all(dir).map_err(error)?;
+    for (i, frame) in frames.iter().enumerate() {
+        let path = dir.join(format!("frame-{:05}.png", i));
+        std::fs::write(path, frame.to_png()).map_err(error)?;
+    }
+    eprintln!("wrote {} frames to {}", frames.len(), dir.display());
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use aoc::args::Args;
+use aoc::repl::{Outcome, Repl};
+use std::io::{self, BufRead, Write};
+
+pub fn run(args: impl Iterator<Item = String>) -> Result<(), String> {
+    let args = Args::parse(args, &["input"], &[])?;
+    let day = super::day(args.positional(0, "day").unwrap_or(1))?;
+    let mut repl = Repl::new(day);
+    if let Some(path) = args.option::<String>("input")? {
+        repl.execute(&format!("load {}", path))?;
+    }
+
+    println!("type `help` for the list of commands");
+    let mut lines = io::stdin().lock().lines();
+    loop {
+        print!("{}", repl.prompt());
+        io::stdout().flush().map_err(|err| err.to_string())?;
+        let Some(line) = lines.next() else {
+            println!();
+            return Ok(());
+        };
+        match repl.execute(&line.map_err(|err| err.to_string())?) {
+            Ok(Outcome::Continue(output)) if output.is_empty() => {}
+            Ok(Outcome::Continue(output)) => println!("{}", output),
+            Ok(Outcome::Quit) => return Ok(()),
+            Err(err) => println!("error: {}", err),
+        }
+    }
+}
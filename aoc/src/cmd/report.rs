@@ -0,0 +1,59 @@
+use aoc::args::Args;
+use aoc::history::{self, Revision};
+use aoc::report::{self, DayReport};
+use std::path::Path;
+use std::time::Duration;
+
+const DEFAULT_OUT: &str = "report";
+const DEFAULT_RUNS: usize = 5;
+const DEFAULT_TIMEOUT: f64 = 60.0;
+
+/// Writes a static HTML report of every day to a directory: `index.html`
+/// and a page per day. The timings are added to the history, unless
+/// `--no-history` is given, and charted across commits. A part still
+/// running after `--timeout` seconds is marked as timed out.
+pub fn run(args: impl Iterator<Item = String>) -> Result<(), String> {
+    let args = Args::parse(
+        args,
+        &["out", "runs", "timeout"],
+        &["sample", "no-history"],
+    )?;
+    let out = args.option::<String>("out")?;
+    let out = Path::new(out.as_deref().unwrap_or(DEFAULT_OUT));
+    let runs = args.option("runs")?.unwrap_or(DEFAULT_RUNS).max(1);
+    let timeout = args.option("timeout")?.unwrap_or(DEFAULT_TIMEOUT);
+    let timeout = Some(Duration::from_secs_f64(timeout));
+
+    let mut reports = Vec::new();
+    for day in aoc::days() {
+        eprintln!("day {}...", day.day);
+        let input = match args.flag("sample") {
+            true => None,
+            false => aoc::read_input(day.day)?,
+        };
+        let expected = aoc::read_answers(day.day);
+        reports.push(DayReport::new(day, input, expected, runs, timeout));
+    }
+
+    let path = history::path();
+    if !args.flag("no-history") {
+        let revision = Revision::current(&aoc::workspace_dir());
+        let records = reports
+            .iter()
+            .flat_map(|report| report.records(&revision))
+            .collect::<Vec<_>>();
+        history::append(&path, &records)?;
+    }
+    let history = history::load(&path)?;
+
+    let error = |err: std::io::Error| format!("{}: {}", out.display(), err);
+    std::fs::create_dir_all(out).map_err(error)?;
+    for report in &reports {
+        let path = out.join(report::file_name(report.day.day));
+        std::fs::write(path, report.page(&history)).map_err(error)?;
+    }
+    let index = out.join("index.html");
+    std::fs::write(&index, report::index(&reports)).map_err(error)?;
+    eprintln!("wrote {} pages to {}", reports.len() + 1, index.display());
+    Ok(())
+}
@@ -0,0 +1,134 @@
+use aoc::alloc::TrackingAllocator;
+use aoc::args::Args;
+use aoc::complexity::{self, Fit};
+use aoc::RunError;
+use std::time::Duration;
+
+const DEFAULT_TIMEOUT: f64 = 60.0;
+
+/// Runs a part on generated inputs of growing size and fits the measured
+/// time and peak memory to common complexity classes. The sizes stop
+/// growing once a run takes longer than `--timeout` seconds.
+pub fn run(args: impl Iterator<Item = String>) -> Result<(), String> {
+    let args = Args::parse(
+        args,
+        &["factor", "from", "impl", "runs", "seed", "steps", "timeout"],
+        &[],
+    )?;
+    let day = super::day(args.positional(0, "day")?)?;
+    let implementations = match args.positional(1, "part")? {
+        1 => day.part_one,
+        2 => day.part_two,
+        part => return Err(format!("invalid <part>: {}", part)),
+    };
+    let implementation = match args.option::<String>("impl")? {
+        Some(name) => implementations
+            .iter()
+            .find(|i| i.name == name)
+            .ok_or_else(|| format!("unknown implementation: {}", name))?,
+        None => &implementations[0],
+    };
+    let from = args.option("from")?.unwrap_or(10usize).max(1);
+    let factor = args.option("factor")?.unwrap_or(2.0f64);
+    let steps = args.option("steps")?.unwrap_or(6usize);
+    let seed = args.option("seed")?.unwrap_or(0);
+    let runs = args.option("runs")?.unwrap_or(3usize).max(1);
+    let timeout = args.option("timeout")?.unwrap_or(DEFAULT_TIMEOUT);
+    let timeout = Some(Duration::from_secs_f64(timeout));
+    if steps < 3 || factor <= 1.0 {
+        return Err("need --steps >= 3 and --factor > 1".to_string());
+    }
+
+    println!(
+        "day {} part {} ({}), n = generator size",
+        day.day,
+        args.positional::<u8>(1, "part")?,
+        implementation.name
+    );
+    println!(
+        "{:>10} {:>12} {:>14} {:>12}",
+        "n", "bytes", "time", "memory"
+    );
+    let mut times = vec![];
+    let mut memory = vec![];
+    let mut size = from as f64;
+    for _ in 0..steps {
+        let n = size.round() as usize;
+        let input = (day.generate)(seed, n);
+
+        match aoc::run_with_timeout(implementation, &input, timeout, |_| {}) {
+            Ok(_) => {}
+            Err(RunError::Panicked(err)) => {
+                return Err(format!("size {}: panic: {}", n, err))
+            }
+            Err(err) => {
+                println!("{:>10} {:>12} {}", n, input.len(), err);
+                break;
+            }
+        }
+
+        // Measured on the timed runs, less the copy of the input they are
+        // handed on their thread.
+        let base = TrackingAllocator::reset_peak();
+        let time = match aoc::timings(implementation, &input, runs, timeout) {
+            Ok(timings) => timings[runs / 2],
+            Err(RunError::Panicked(err)) => {
+                return Err(format!("size {}: panic: {}", n, err))
+            }
+            Err(err) => {
+                println!("{:>10} {:>12} {}", n, input.len(), err);
+                break;
+            }
+        };
+        let peak =
+            (TrackingAllocator::peak() - base).saturating_sub(input.len());
+
+        println!(
+            "{:>10} {:>12} {:>14?} {:>12}",
+            n,
+            input.len(),
+            time,
+            format_bytes(peak)
+        );
+        times.push((n as f64, time.as_secs_f64().max(1e-9)));
+        memory.push((n as f64, (peak as f64).max(1.0)));
+        size *= factor;
+    }
+
+    if times.len() < 3 {
+        return Err("need at least 3 sizes within --timeout".to_string());
+    }
+    println!();
+    report("time", &times);
+    report("memory", &memory);
+    println!();
+    let best = complexity::fit(&times)[0];
+    println!("time (log-log), fitted {}:", best.class);
+    print!("{}", complexity::plot(&times, &best, 60, 16));
+    Ok(())
+}
+
+fn report(name: &str, samples: &[(f64, f64)]) {
+    let fits = complexity::fit(samples);
+    let ranking = fits
+        .iter()
+        .take(3)
+        .map(|Fit { class, error, .. }| {
+            format!("{} ({:.1}%)", class, error * 100.0)
+        })
+        .collect::<Vec<_>>();
+    println!(
+        "{:<7} best fit {}, n^{:.2} empirically",
+        name,
+        ranking.join(", "),
+        complexity::exponent(samples)
+    );
+}
+
+fn format_bytes(bytes: usize) -> String {
+    match bytes {
+        b if b < 1 << 10 => format!("{} B", b),
+        b if b < 1 << 20 => format!("{:.1} KiB", b as f64 / 1024.0),
+        b => format!("{:.1} MiB", b as f64 / (1 << 20) as f64),
+    }
+}
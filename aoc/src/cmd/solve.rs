@@ -0,0 +1,99 @@
+use aoc::args::Args;
+use common::context::Context;
+use common::Implementation;
+use std::io::{self, IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+const BAR_WIDTH: usize = 30;
+
+/// Solves a day's parts, stopping each one after `--timeout` seconds and
+/// showing the progress of the parts that report it.
+pub fn run(args: impl Iterator<Item = String>) -> Result<(), String> {
+    let args = Args::parse(args, &["impl", "input", "timeout"], &[])?;
+    let day = super::day(args.positional(0, "day")?)?;
+    let parts = match args.optional_positional::<u8>(1, "part")? {
+        Some(part @ 1..=2) => vec![part],
+        Some(part) => return Err(format!("no part {}", part)),
+        None => vec![1, 2],
+    };
+    let input = match args.option::<String>("input")? {
+        Some(path) => std::fs::read_to_string(&path)
+            .map_err(|err| format!("{}: {}", path, err))?,
+        None => aoc::read_input(day.day)?.unwrap_or(day.sample.to_string()),
+    };
+    let timeout = args.option::<f64>("timeout")?.map(Duration::from_secs_f64);
+    let name = args.option::<String>("impl")?;
+
+    let mut failed = false;
+    for part in parts {
+        let implementations = if part == 1 {
+            day.part_one
+        } else {
+            day.part_two
+        };
+        let implementation = match &name {
+            Some(name) => implementations
+                .iter()
+                .find(|i| i.name == name)
+                .ok_or_else(|| format!("unknown implementation: {}", name))?,
+            None => &implementations[0],
+        };
+        let label = format!("day {} part {}", day.day, part);
+        match solve(implementation, &input, timeout, &label) {
+            (Ok(answer), elapsed) if answer.to_string().contains('\n') => {
+                println!("{} ({:.2?}):\n{}", label, elapsed, answer)
+            }
+            (Ok(answer), elapsed) => {
+                println!("{}: {} ({:.2?})", label, answer, elapsed)
+            }
+            (Err(err), _) => {
+                println!("{}: {}", label, err);
+                failed = true;
+            }
+        }
+    }
+    match failed {
+        true => Err("some parts did not finish".to_string()),
+        false => Ok(()),
+    }
+}
+
+fn solve(
+    implementation: &Implementation,
+    input: &str,
+    timeout: Option<Duration>,
+    label: &str,
+) -> (Result<common::answer::Answer, aoc::RunError>, Duration) {
+    let start = Instant::now();
+    let mut stderr = io::stderr();
+    // Only drawn on a terminal, where it can be erased afterwards.
+    let show = stderr.is_terminal();
+    let mut drawn = false;
+    let result =
+        aoc::run_with_timeout(implementation, input, timeout, |context| {
+            if let Some(bar) = show.then(|| progress_bar(context)).flatten() {
+                let _ = write!(stderr, "\r{} {}", label, bar);
+                let _ = stderr.flush();
+                drawn = true;
+            }
+        });
+    let elapsed = start.elapsed();
+    if drawn {
+        let _ = write!(stderr, "\r\x1b[K");
+    }
+    (result, elapsed)
+}
+
+/// `[#####.....]  50% 5000/10000` for a solver that reports its progress.
+fn progress_bar(context: &Context) -> Option<String> {
+    let (done, total) = context.progress()?;
+    let filled = (done as f64 / total as f64 * BAR_WIDTH as f64) as usize;
+    Some(format!(
+        "[{}{}] {:>3}% {}/{}",
+        "#".repeat(filled),
+        ".".repeat(BAR_WIDTH - filled),
+        done * 100 / total,
+        done,
+        total
+    ))
+}
@@ -0,0 +1,35 @@
+use aoc::alloc::TrackingAllocator;
+use aoc::args::Args;
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::time::Instant;
+
+/// Solves a part from a file or stdin without reading it into memory.
+pub fn run(args: impl Iterator<Item = String>) -> Result<(), String> {
+    let args = Args::parse(args, &[], &[])?;
+    let day: u8 = args.positional(0, "day")?;
+    let part: u8 = args.positional(1, "part")?;
+    let solve = aoc::stream_solver(day, part).ok_or_else(|| {
+        format!("day {} part {} cannot be streamed", day, part)
+    })?;
+    let path = args.positional::<String>(2, "file").ok();
+
+    let base = TrackingAllocator::reset_peak();
+    let start = Instant::now();
+    let answer = match &path {
+        Some(path) => {
+            let file =
+                File::open(path).map_err(|err| format!("{}: {}", path, err))?;
+            aoc::catch_panic(|| solve(&mut BufReader::new(file)))
+        }
+        None => aoc::catch_panic(|| solve(&mut io::stdin().lock())),
+    }
+    .map_err(|err| format!("panic: {}", err))?
+    .map_err(|err| err.to_string())?;
+    let time = start.elapsed();
+    let peak = TrackingAllocator::peak() - base;
+
+    println!("{}", answer);
+    eprintln!("{:?}, peak memory {} B", time, peak);
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use crossterm::{cursor, execute, terminal};
+use std::io::{self, Write};
+
+/// Raw mode on the alternate screen for as long as it lives. Dropping it,
+/// on return, an error or a panic alike, gives the terminal back as it was.
+pub struct TerminalGuard(());
+
+impl TerminalGuard {
+    pub fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        let guard = Self(());
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(guard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let mut stdout = io::stdout();
+        let _ = execute!(stdout, cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = stdout.flush();
+        let _ = terminal::disable_raw_mode();
+    }
+}
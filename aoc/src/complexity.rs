@@ -0,0 +1,215 @@
+use std::fmt;
+
+/// Common growth classes a measured curve is matched against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Class {
+    Constant,
+    Logarithmic,
+    Linear,
+    Linearithmic,
+    Quadratic,
+    Cubic,
+}
+
+impl Class {
+    pub const ALL: [Class; 6] = [
+        Class::Constant,
+        Class::Logarithmic,
+        Class::Linear,
+        Class::Linearithmic,
+        Class::Quadratic,
+        Class::Cubic,
+    ];
+
+    pub fn eval(self, n: f64) -> f64 {
+        let log = n.max(2.0).log2();
+        match self {
+            Class::Constant => 1.0,
+            Class::Logarithmic => log,
+            Class::Linear => n,
+            Class::Linearithmic => n * log,
+            Class::Quadratic => n * n,
+            Class::Cubic => n * n * n,
+        }
+    }
+}
+
+impl fmt::Display for Class {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Class::Constant => "O(1)",
+            Class::Logarithmic => "O(log n)",
+            Class::Linear => "O(n)",
+            Class::Linearithmic => "O(n log n)",
+            Class::Quadratic => "O(n^2)",
+            Class::Cubic => "O(n^3)",
+        };
+        f.pad(name)
+    }
+}
+
+/// How well `coefficient * class(n)` explains the measurements.
+#[derive(Debug, Clone, Copy)]
+pub struct Fit {
+    pub class: Class,
+    pub coefficient: f64,
+    /// Root mean square of the relative residuals.
+    pub error: f64,
+}
+
+/// Fits `(n, cost)` samples to every class, best fit first.
+///
+/// Residuals are taken relative to the measurement so the small sizes weigh
+/// as much as the large ones.
+pub fn fit(samples: &[(f64, f64)]) -> Vec<Fit> {
+    let mut fits = Class::ALL
+        .iter()
+        .map(|&class| {
+            let (num, den) =
+                samples.iter().fold((0.0, 0.0), |(a, b), &(n, y)| {
+                    let f = class.eval(n) / y;
+                    (a + f, b + f * f)
+                });
+            let coefficient = num / den;
+            let error = (samples
+                .iter()
+                .map(|&(n, y)| (1.0 - coefficient * class.eval(n) / y).powi(2))
+                .sum::<f64>()
+                / samples.len() as f64)
+                .sqrt();
+            Fit {
+                class,
+                coefficient,
+                error,
+            }
+        })
+        .collect::<Vec<_>>();
+    fits.sort_by(|a, b| a.error.total_cmp(&b.error));
+    fits
+}
+
+/// Slope of the log-log regression line, i.e. `k` in `cost ~ n^k`.
+pub fn exponent(samples: &[(f64, f64)]) -> f64 {
+    let points = samples
+        .iter()
+        .map(|&(n, y)| (n.ln(), y.ln()))
+        .collect::<Vec<_>>();
+    let len = points.len() as f64;
+    let mx = points.iter().map(|p| p.0).sum::<f64>() / len;
+    let my = points.iter().map(|p| p.1).sum::<f64>() / len;
+    let cov = points.iter().map(|p| (p.0 - mx) * (p.1 - my)).sum::<f64>();
+    let var = points.iter().map(|p| (p.0 - mx).powi(2)).sum::<f64>();
+    cov / var
+}
+
+/// Log-log scatter plot of the samples (`*`) over a fitted curve (`.`).
+pub fn plot(
+    samples: &[(f64, f64)],
+    fit: &Fit,
+    width: usize,
+    height: usize,
+) -> String {
+    let fitted = |n: f64| fit.coefficient * fit.class.eval(n);
+    let (lo_n, hi_n) = bounds(samples.iter().map(|s| s.0));
+    let (lo_y, hi_y) = bounds(
+        samples
+            .iter()
+            .flat_map(|&(n, y)| [y, fitted(n)])
+            .filter(|y| *y > 0.0),
+    );
+    let scale = |v: f64, lo: f64, hi: f64, cells: usize| {
+        let t = match hi > lo {
+            true => (v.ln() - lo.ln()) / (hi.ln() - lo.ln()),
+            false => 0.5,
+        };
+        ((t * (cells - 1) as f64).round() as usize).min(cells - 1)
+    };
+
+    let mut grid = vec![vec![' '; width]; height];
+    let curve = (0..width).filter_map(|col| {
+        let t = col as f64 / (width - 1) as f64;
+        let y = fitted((lo_n.ln() + t * (hi_n.ln() - lo_n.ln())).exp());
+        (lo_y..=hi_y)
+            .contains(&y)
+            .then(|| (height - 1 - scale(y, lo_y, hi_y, height), col))
+    });
+    for (row, col) in curve {
+        grid[row][col] = '.';
+    }
+    for &(n, y) in samples {
+        let row = height - 1 - scale(y, lo_y, hi_y, height);
+        grid[row][scale(n, lo_n, hi_n, width)] = '*';
+    }
+
+    let mut output = String::new();
+    for (i, row) in grid.iter().enumerate() {
+        let label = match i {
+            0 => format!("{:>10.3e}", hi_y),
+            i if i == height - 1 => format!("{:>10.3e}", lo_y),
+            _ => String::new(),
+        };
+        output.push_str(&format!(
+            "{:>10} |{}\n",
+            label,
+            String::from_iter(row)
+        ));
+    }
+    output.push_str(&format!("{:>10} +{}\n", "", "-".repeat(width)));
+    output.push_str(&format!(
+        "{:>10}  {:<w$}{}\n",
+        "",
+        lo_n,
+        hi_n,
+        w = width.saturating_sub(hi_n.to_string().len())
+    ));
+    output
+}
+
+fn bounds(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    values.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+        (lo.min(v), hi.max(v))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples(f: impl Fn(f64) -> f64) -> Vec<(f64, f64)> {
+        (0..8)
+            .map(|i| 10.0 * 2f64.powi(i))
+            .map(|n| (n, f(n)))
+            .collect()
+    }
+
+    #[test]
+    fn test_fit_picks_the_generating_class() {
+        for class in Class::ALL {
+            let data = samples(|n| 3.0 * class.eval(n));
+            let best = fit(&data)[0];
+            assert_eq!(best.class, class);
+            assert!((best.coefficient - 3.0).abs() < 1e-9);
+            assert!(best.error < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_fit_tolerates_noise_and_overhead() {
+        let data = samples(|n| 50.0 + n * n * (1.0 + (n % 3.0) / 50.0));
+        assert_eq!(fit(&data)[0].class, Class::Quadratic);
+    }
+
+    #[test]
+    fn test_exponent() {
+        assert!((exponent(&samples(|n| n * n)) - 2.0).abs() < 1e-9);
+        assert!((exponent(&samples(|n| 5.0 * n)) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_plot_marks_every_sample() {
+        let data = samples(|n| n);
+        let output = plot(&data, &fit(&data)[0], 40, 10);
+        assert_eq!(output.lines().count(), 12);
+        assert_eq!(output.matches('*').count(), data.len());
+    }
+}
@@ -0,0 +1,570 @@
+use crate::{find_day, read_answers, read_input, RunError, DAYS_IN_EVENT};
+use common::answer::Answer;
+use common::{Day, Implementation};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const ANSWER_WIDTH: usize = 18;
+/// Parts still running after this long are cancelled, so one slow day
+/// cannot freeze the table.
+const RUN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Outcome of the last run of a part.
+pub struct PartRun {
+    pub answer: Result<Answer, RunError>,
+    pub time: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Unsolved,
+    NotRun,
+    /// Waiting for the worker, or running on it.
+    Running,
+    Error,
+    /// Ran, but there is no expected answer to compare with.
+    Unchecked,
+    Correct,
+    Wrong,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    Normal,
+    Header,
+    Selected,
+    Good,
+    Bad,
+    Dim,
+}
+
+pub struct Line {
+    pub text: String,
+    pub style: Style,
+}
+
+struct Row {
+    day: u8,
+    entry: Option<&'static Day>,
+    input: Option<String>,
+    use_sample: bool,
+    expected: [Option<Answer>; 2],
+    runs: [Option<PartRun>; 2],
+    /// For each part handed to the worker and not back yet, whether it runs
+    /// on the sample.
+    pending: [Option<bool>; 2],
+}
+
+impl Row {
+    fn new(day: u8) -> Result<Self, String> {
+        let input = read_input(day)?;
+        Ok(Self {
+            day,
+            entry: find_day(day),
+            use_sample: input.is_none(),
+            input,
+            expected: read_answers(day),
+            runs: [None, None],
+            pending: [None, None],
+        })
+    }
+
+    fn source(&self) -> &'static str {
+        match self.use_sample {
+            true => "sample",
+            false => "input",
+        }
+    }
+
+    /// The answers file is for the personal input, so sample runs are never
+    /// checked.
+    fn expected(&self, part: usize) -> Option<&Answer> {
+        match self.use_sample {
+            true => None,
+            false => self.expected[part].as_ref(),
+        }
+    }
+
+    fn status(&self, part: usize) -> Status {
+        if self.entry.is_none() {
+            return Status::Unsolved;
+        }
+        if self.pending[part] == Some(self.use_sample) {
+            return Status::Running;
+        }
+        match (&self.runs[part], self.expected(part)) {
+            (None, _) => Status::NotRun,
+            (Some(PartRun { answer: Err(_), .. }), _) => Status::Error,
+            (Some(_), None) => Status::Unchecked,
+            (
+                Some(PartRun {
+                    answer: Ok(answer), ..
+                }),
+                Some(expected),
+            ) => match answer == expected {
+                true => Status::Correct,
+                false => Status::Wrong,
+            },
+        }
+    }
+
+    /// The runs of both parts not already handed to the worker for the input
+    /// shown, for the row at `index`.
+    fn jobs(&mut self, index: usize) -> Vec<Job> {
+        let Some(day) = self.entry else {
+            return Vec::new();
+        };
+        let input = match (self.use_sample, &self.input) {
+            (false, Some(input)) => input.as_str(),
+            _ => day.sample,
+        };
+        let mut jobs = Vec::new();
+        for (part, implementations) in
+            [day.part_one, day.part_two].iter().enumerate()
+        {
+            if self.pending[part] == Some(self.use_sample) {
+                continue;
+            }
+            self.pending[part] = Some(self.use_sample);
+            jobs.push(Job {
+                row: index,
+                part,
+                sample: self.use_sample,
+                implementation: implementations[0],
+                input: input.to_string(),
+            });
+        }
+        jobs
+    }
+
+    fn cell(&self, part: usize) -> String {
+        let marker = match self.status(part) {
+            Status::Correct => '*',
+            Status::Wrong => 'x',
+            Status::Error => '!',
+            _ => ' ',
+        };
+        let text = match &self.runs[part] {
+            _ if self.status(part) == Status::Running => "running".to_string(),
+            Some(PartRun {
+                answer: Ok(answer), ..
+            }) => {
+                // Grids show the letters they spell, or their first line.
+                let answer = answer.submission();
+                match answer.split_once('\n') {
+                    Some((first, _)) => format!("{}...", first),
+                    None => answer,
+                }
+            }
+            Some(PartRun {
+                answer: Err(RunError::TimedOut { .. }),
+                ..
+            }) => "timed out".to_string(),
+            Some(PartRun { answer: Err(_), .. }) => "panicked".to_string(),
+            None if self.entry.is_none() => String::new(),
+            None => "-".to_string(),
+        };
+        format!("{} {}", marker, truncate(&text, ANSWER_WIDTH))
+    }
+
+    fn style(&self) -> Style {
+        let statuses = [self.status(0), self.status(1)];
+        if statuses.contains(&Status::Unsolved) {
+            Style::Dim
+        } else if statuses
+            .iter()
+            .any(|s| [Status::Error, Status::Wrong].contains(s))
+        {
+            Style::Bad
+        } else if statuses == [Status::Correct; 2] {
+            Style::Good
+        } else {
+            Style::Normal
+        }
+    }
+}
+
+/// A part for the worker to run.
+struct Job {
+    row: usize,
+    part: usize,
+    sample: bool,
+    implementation: Implementation,
+    input: String,
+}
+
+/// Runs the jobs it is sent one at a time, on its own thread, so the screen
+/// keeps answering keys meanwhile.
+struct Worker {
+    jobs: Sender<Job>,
+    done: Receiver<(Job, PartRun)>,
+}
+
+impl Worker {
+    fn spawn() -> Self {
+        let (jobs, queue) = mpsc::channel::<Job>();
+        let (finished, done) = mpsc::channel();
+        thread::spawn(move || {
+            for job in queue {
+                let start = Instant::now();
+                let answer = crate::run_with_timeout(
+                    &job.implementation,
+                    &job.input,
+                    Some(RUN_TIMEOUT),
+                    |_| {},
+                );
+                let time = start.elapsed();
+                if finished.send((job, PartRun { answer, time })).is_err() {
+                    return;
+                }
+            }
+        });
+        Self { jobs, done }
+    }
+}
+
+/// State of the `aoc dashboard` screen: one row per day of the event, the
+/// last run of each part and the selected day.
+pub struct Dashboard {
+    rows: Vec<Row>,
+    selected: usize,
+    worker: Worker,
+}
+
+impl Dashboard {
+    /// Fails if a day's input cannot be read, when it cannot be decrypted
+    /// for example.
+    pub fn new() -> Result<Self, String> {
+        Ok(Self {
+            rows: (1..=DAYS_IN_EVENT)
+                .map(Row::new)
+                .collect::<Result<_, _>>()?,
+            selected: 0,
+            worker: Worker::spawn(),
+        })
+    }
+
+    pub fn selected_day(&self) -> u8 {
+        self.rows[self.selected].day
+    }
+
+    pub fn select(&mut self, day: u8) {
+        self.selected = (day.clamp(1, DAYS_IN_EVENT) - 1) as usize;
+    }
+
+    pub fn move_selection(&mut self, delta: isize) {
+        let last = self.rows.len() as isize - 1;
+        self.selected =
+            (self.selected as isize + delta).clamp(0, last) as usize;
+    }
+
+    /// Queues both parts of the selected day on the worker, see [`poll`].
+    ///
+    /// [`poll`]: Self::poll
+    pub fn run_selected(&mut self) {
+        let jobs = self.rows[self.selected].jobs(self.selected);
+        self.queue(jobs);
+    }
+
+    /// Queues every day on the worker.
+    pub fn run_all(&mut self) {
+        let jobs = (0..self.rows.len())
+            .flat_map(|i| self.rows[i].jobs(i))
+            .collect();
+        self.queue(jobs);
+    }
+
+    fn queue(&mut self, jobs: Vec<Job>) {
+        for job in jobs {
+            // The worker only stops when the dashboard is dropped.
+            let _ = self.worker.jobs.send(job);
+        }
+    }
+
+    /// Takes in the runs the worker finished since the last call, returning
+    /// whether there were any. A run superseded by one on the other input is
+    /// dropped.
+    pub fn poll(&mut self) -> bool {
+        let mut changed = false;
+        while let Ok((job, run)) = self.worker.done.try_recv() {
+            let row = &mut self.rows[job.row];
+            if row.pending[job.part] != Some(job.sample) {
+                continue;
+            }
+            row.pending[job.part] = None;
+            if row.use_sample == job.sample {
+                row.runs[job.part] = Some(run);
+            }
+            changed = true;
+        }
+        changed
+    }
+
+    /// How many parts are queued or running.
+    pub fn running(&self) -> usize {
+        self.rows
+            .iter()
+            .flat_map(|row| row.pending)
+            .filter(Option::is_some)
+            .count()
+    }
+
+    /// Switches the selected day between its sample and personal input,
+    /// dropping the results of the other one.
+    pub fn toggle_input(&mut self) {
+        let row = &mut self.rows[self.selected];
+        if row.input.is_some() {
+            row.use_sample = !row.use_sample;
+            row.runs = [None, None];
+        }
+    }
+
+    pub fn stars(&self) -> usize {
+        self.rows
+            .iter()
+            .flat_map(|row| [row.status(0), row.status(1)])
+            .filter(|&status| status == Status::Correct)
+            .count()
+    }
+
+    /// Lays the screen out for a terminal of the given size: the day table,
+    /// scrolled to keep the selection visible, then the selected day's
+    /// output.
+    pub fn render(&self, width: usize, height: usize) -> Vec<Line> {
+        let mut lines = vec![
+            line(
+                format!(
+                    "Advent of Code 2022{:>w$}",
+                    format!("{}/{} stars", self.stars(), 2 * DAYS_IN_EVENT),
+                    w = width.saturating_sub(19)
+                ),
+                Style::Header,
+            ),
+            line(
+                format!(
+                    "  Day  Input   {:<w$}  {:<w$}  Time",
+                    "Part 1",
+                    "Part 2",
+                    w = ANSWER_WIDTH + 2
+                ),
+                Style::Header,
+            ),
+        ];
+
+        let table_height = (height / 2).clamp(1, self.rows.len());
+        let first = (self.selected + 1).saturating_sub(table_height);
+        let visible = self.rows.iter().enumerate().skip(first);
+        for (i, row) in visible.take(table_height) {
+            let time: Duration =
+                row.runs.iter().flatten().map(|r| r.time).sum();
+            let text = format!(
+                "{} {:>3}  {:<6}  {}  {}  {}",
+                if i == self.selected { '>' } else { ' ' },
+                row.day,
+                if row.entry.is_some() {
+                    row.source()
+                } else {
+                    ""
+                },
+                row.cell(0),
+                row.cell(1),
+                match row.runs.iter().all(Option::is_none) {
+                    true => String::new(),
+                    false => format!("{:.2?}", time),
+                }
+            );
+            let style = match i == self.selected {
+                true => Style::Selected,
+                false => row.style(),
+            };
+            lines.push(line(text, style));
+        }
+
+        lines.push(line("-".repeat(width), Style::Dim));
+        lines.extend(self.details());
+        lines.truncate(height.saturating_sub(1));
+        lines.resize_with(height.saturating_sub(1), || {
+            line(String::new(), Style::Normal)
+        });
+        let help = match self.running() {
+            0 => String::new(),
+            n => format!("running {} part(s)...  ", n),
+        };
+        lines.push(line(
+            help + "up/down select  enter run  a run all  s sample/input  q quit",
+            Style::Dim,
+        ));
+        for line in &mut lines {
+            line.text = truncate(&line.text, width);
+        }
+        lines
+    }
+
+    fn details(&self) -> Vec<Line> {
+        let row = &self.rows[self.selected];
+        if row.entry.is_none() {
+            return vec![line(
+                format!("Day {} is not solved yet", row.day),
+                Style::Dim,
+            )];
+        }
+        let mut lines = vec![line(
+            format!("Day {} on the {}", row.day, row.source()),
+            Style::Header,
+        )];
+        for part in 0..2 {
+            let status = row.status(part);
+            let style = match status {
+                Status::Correct => Style::Good,
+                Status::Wrong | Status::Error => Style::Bad,
+                _ => Style::Normal,
+            };
+            if status == Status::Running {
+                lines.push(line(format!("Part {}: running", part + 1), style));
+                continue;
+            }
+            let Some(run) = &row.runs[part] else {
+                lines.push(line(format!("Part {}: not run", part + 1), style));
+                continue;
+            };
+            lines.push(line(
+                format!("Part {} ({:.2?}):", part + 1, run.time),
+                style,
+            ));
+            match &run.answer {
+                Ok(answer) => lines.extend(
+                    answer
+                        .to_string()
+                        .lines()
+                        .map(|l| line(format!("  {}", l), style)),
+                ),
+                Err(RunError::Panicked(err)) => {
+                    lines.push(line(format!("  panicked: {}", err), style))
+                }
+                Err(err) => lines.push(line(format!("  {}", err), style)),
+            }
+            if let (Status::Wrong, Some(expected)) =
+                (status, row.expected(part))
+            {
+                lines.push(line("  expected:".to_string(), Style::Dim));
+                lines.extend(
+                    expected
+                        .to_string()
+                        .lines()
+                        .map(|l| line(format!("  {}", l), Style::Dim)),
+                );
+            }
+        }
+        lines
+    }
+}
+
+fn line(text: String, style: Style) -> Line {
+    Line { text, style }
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    match text.chars().count() > width {
+        true => text.chars().take(width).collect(),
+        false => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Polls until the worker has run everything queued.
+    fn wait(dashboard: &mut Dashboard) {
+        while dashboard.running() > 0 {
+            if !dashboard.poll() {
+                thread::sleep(Duration::from_millis(1));
+            }
+        }
+    }
+
+    fn text(lines: &[Line]) -> String {
+        lines
+            .iter()
+            .map(|l| l.text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_run_and_render() {
+        let mut dashboard = Dashboard::new().unwrap();
+        dashboard.select(10);
+        dashboard.rows[9].use_sample = true;
+        dashboard.run_selected();
+        assert_eq!(dashboard.rows[9].status(0), Status::Running);
+        assert!(text(&dashboard.render(80, 40)).contains("running 2 part(s)"));
+        wait(&mut dashboard);
+        let lines = dashboard.render(80, 40);
+        assert_eq!(lines.len(), 40);
+        assert!(lines.iter().all(|l| l.text.chars().count() <= 80));
+        let screen = text(&lines);
+        assert!(screen.contains(">  10  sample"));
+        assert!(screen.contains("  13140\n"));
+        assert!(screen.contains("  ##..##..##..##..##..##..##..##..##..##..\n"));
+    }
+
+    #[test]
+    fn test_answers_are_checked() {
+        let mut dashboard = Dashboard::new().unwrap();
+        let row = &mut dashboard.rows[0];
+        row.input = Some(day01::DAY.sample.to_string());
+        row.use_sample = false;
+        row.expected = [Some(Answer::Int(24000)), Some(Answer::Int(1))];
+        dashboard.run_selected();
+        wait(&mut dashboard);
+        assert_eq!(dashboard.rows[0].status(0), Status::Correct);
+        assert_eq!(dashboard.rows[0].status(1), Status::Wrong);
+        assert_eq!(dashboard.stars(), 1);
+        assert!(text(&dashboard.render(80, 40)).contains("  expected:\n  1"));
+
+        dashboard.toggle_input();
+        assert_eq!(dashboard.rows[0].status(0), Status::NotRun);
+        dashboard.run_selected();
+        wait(&mut dashboard);
+        assert_eq!(dashboard.rows[0].status(0), Status::Unchecked);
+    }
+
+    #[test]
+    fn test_timeouts_are_not_panics() {
+        let mut dashboard = Dashboard::new().unwrap();
+        let after = Duration::from_secs(10);
+        dashboard.rows[0].runs = [
+            Some(PartRun {
+                answer: Err(RunError::TimedOut {
+                    after,
+                    still_running: false,
+                }),
+                time: after,
+            }),
+            Some(PartRun {
+                answer: Err(RunError::Panicked("bad input".to_string())),
+                time: Duration::ZERO,
+            }),
+        ];
+        let screen = text(&dashboard.render(80, 40));
+        assert!(screen.contains("! timed out"));
+        assert!(screen.contains("! panicked"));
+        assert!(screen.contains("  timed out after 10s\n"));
+        assert!(screen.contains("  panicked: bad input\n"));
+    }
+
+    #[test]
+    fn test_selection_scrolls_table() {
+        let mut dashboard = Dashboard::new().unwrap();
+        dashboard.move_selection(-1);
+        assert_eq!(dashboard.selected_day(), 1);
+        dashboard.move_selection(100);
+        assert_eq!(dashboard.selected_day(), 25);
+        let screen = text(&dashboard.render(80, 20));
+        assert!(screen.contains(">  25"));
+        assert!(screen.contains("Day 25 is not solved yet"));
+        assert!(!screen.contains("   1  "));
+    }
+}
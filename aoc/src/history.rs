@@ -0,0 +1,456 @@
+//! Timings kept across runs, keyed by the git commit they were measured at
+//! and whether the tree had uncommitted changes, to see how a part evolved
+//! over refactors. The store is a tab-separated file, a line per
+//! implementation timed, only ever appended to. Only `aoc bench` and
+//! `aoc report` add to it: they time several runs, where `aoc solve` times
+//! a single one.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{ErrorKind, Write as _};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const FILE: &str = ".aoc-history.tsv";
+/// Where the history is kept instead, if set.
+pub const VAR: &str = "AOC_HISTORY";
+const CHART_WIDTH: usize = 40;
+
+/// The state of the tree timings were measured on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Revision {
+    /// The full hash of `HEAD`, `unknown` outside of a git repository.
+    pub commit: String,
+    /// Whether tracked files had uncommitted changes.
+    pub dirty: bool,
+}
+
+impl Revision {
+    /// The revision checked out in `dir`.
+    pub fn current(dir: &Path) -> Self {
+        let git = |args: &[&str]| {
+            let output = Command::new("git")
+                .arg("-C")
+                .arg(dir)
+                .args(args)
+                .output()
+                .ok()?;
+            output
+                .status
+                .success()
+                .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+        };
+        match git(&["rev-parse", "HEAD"]) {
+            Some(commit) => Self {
+                commit: commit.trim().to_string(),
+                dirty: git(&["status", "--porcelain", "--untracked-files=no"])
+                    .is_some_and(|status| !status.trim().is_empty()),
+            },
+            None => Self {
+                commit: "unknown".to_string(),
+                dirty: false,
+            },
+        }
+    }
+
+    /// The abbreviated hash, with a `+` if the tree was dirty.
+    pub fn short(&self) -> String {
+        let commit = &self.commit[..self.commit.len().min(7)];
+        format!("{}{}", commit, if self.dirty { "+" } else { "" })
+    }
+}
+
+/// The timings of one implementation of a part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    /// Seconds since the Unix epoch.
+    pub time: u64,
+    pub revision: Revision,
+    /// `release` or `debug`, timings of the two are not comparable.
+    pub build: String,
+    pub day: u8,
+    pub part: u8,
+    pub implementation: String,
+    /// `input`, `sample`, or the seed and size of a generated input.
+    pub input: String,
+    pub runs: usize,
+    pub median: Duration,
+    pub min: Duration,
+}
+
+impl Record {
+    /// A record of timings measured now, by this build of the runner, with
+    /// `timings` sorted fastest first.
+    pub fn new(
+        revision: &Revision,
+        (day, part): (u8, u8),
+        implementation: &str,
+        input: &str,
+        timings: &[Duration],
+    ) -> Self {
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs());
+        Self {
+            time,
+            revision: revision.clone(),
+            build: build().to_string(),
+            day,
+            part,
+            implementation: implementation.to_string(),
+            input: input.to_string(),
+            runs: timings.len(),
+            median: timings[timings.len() / 2],
+            min: timings[0],
+        }
+    }
+
+    fn line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.time,
+            self.revision.commit,
+            self.revision.dirty as u8,
+            self.build,
+            self.day,
+            self.part,
+            self.implementation,
+            self.input,
+            self.runs,
+            self.median.as_nanos(),
+            self.min.as_nanos()
+        )
+    }
+
+    fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.split('\t');
+        let mut next = || fields.next();
+        let nanos = |field: &str| field.parse().ok().map(Duration::from_nanos);
+        let record = Self {
+            time: next()?.parse().ok()?,
+            revision: Revision {
+                commit: next()?.to_string(),
+                dirty: next()? == "1",
+            },
+            build: next()?.to_string(),
+            day: next()?.parse().ok()?,
+            part: next()?.parse().ok()?,
+            implementation: next()?.to_string(),
+            input: next()?.to_string(),
+            runs: next()?.parse().ok()?,
+            median: nanos(next()?)?,
+            min: nanos(next()?)?,
+        };
+        next().is_none().then_some(record)
+    }
+}
+
+/// The build the runner was compiled as.
+pub fn build() -> &'static str {
+    match cfg!(debug_assertions) {
+        true => "debug",
+        false => "release",
+    }
+}
+
+/// The history file: `AOC_HISTORY`, else `.aoc-history.tsv` at the root of
+/// the workspace.
+pub fn path() -> PathBuf {
+    std::env::var_os(VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| crate::workspace_dir().join(FILE))
+}
+
+pub fn append(path: &Path, records: &[Record]) -> Result<(), String> {
+    let mut lines = String::new();
+    for record in records {
+        let _ = writeln!(lines, "{}", record.line());
+    }
+    fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(lines.as_bytes()))
+        .map_err(|err| format!("{}: {}", path.display(), err))
+}
+
+/// Every record in the history, oldest first. Empty if there is no history
+/// yet. A line that is not a record, say one cut short or edited by hand, is
+/// skipped with a warning on stderr.
+pub fn load(path: &Path) -> Result<Vec<Record>, String> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(format!("{}: {}", path.display(), err)),
+    };
+    Ok(text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .filter_map(|(i, line)| {
+            let record = Record::parse(line);
+            if record.is_none() {
+                eprintln!(
+                    "warning: {}:{}: invalid record, skipped",
+                    path.display(),
+                    i + 1
+                );
+            }
+            record
+        })
+        .collect())
+}
+
+/// The records of one series, comparable with each other: the same part,
+/// implementation, input and build.
+pub struct Series<'a> {
+    pub day: u8,
+    pub part: u8,
+    pub implementation: &'a str,
+    pub input: &'a str,
+    pub build: &'a str,
+}
+
+impl Series<'_> {
+    pub fn matches(&self, record: &Record) -> bool {
+        (record.day, record.part) == (self.day, self.part)
+            && record.implementation == self.implementation
+            && record.input == self.input
+            && record.build == self.build
+    }
+
+    /// A point per revision in the order they were first timed, with the
+    /// latest timing of each.
+    pub fn points<'r>(&self, records: &'r [Record]) -> Vec<&'r Record> {
+        let mut points: Vec<&Record> = Vec::new();
+        for record in records.iter().filter(|record| self.matches(record)) {
+            match points.iter_mut().find(|p| p.revision == record.revision) {
+                Some(point) => *point = record,
+                None => points.push(record),
+            }
+        }
+        points
+    }
+}
+
+/// A line per revision: when it was timed, its median and fastest run, and
+/// a bar for the median.
+pub fn table(points: &[&Record]) -> String {
+    let slowest = points.iter().map(|p| p.median).max().unwrap_or_default();
+    let mut table = format!(
+        "{:<9} {:<10} {:>12} {:>12}\n",
+        "commit", "date", "median", "min"
+    );
+    for point in points {
+        let bar = point.median.as_secs_f64()
+            / slowest.as_secs_f64().max(f64::EPSILON)
+            * CHART_WIDTH as f64;
+        let _ = writeln!(
+            table,
+            "{:<9} {:<10} {:>12} {:>12} {}",
+            point.revision.short(),
+            date(point.time),
+            format!("{:.2?}", point.median),
+            format!("{:.2?}", point.min),
+            "#".repeat((bar.round() as usize).max(1))
+        );
+    }
+    table
+}
+
+/// The medians as a line chart across revisions, a standalone SVG. Each
+/// point tells its revision, date and timings when hovered.
+pub fn chart(points: &[&Record]) -> String {
+    const WIDTH: f64 = 720.0;
+    const HEIGHT: f64 = 240.0;
+    // Room for the axis labels on the left and the commits below.
+    const LEFT: f64 = 80.0;
+    const BOTTOM: f64 = 70.0;
+    const TOP: f64 = 10.0;
+    let slowest = points.iter().map(|p| p.median).max().unwrap_or_default();
+    let slowest = slowest.as_secs_f64().max(f64::EPSILON);
+    let plot = (WIDTH - LEFT - 20.0, HEIGHT - BOTTOM - TOP);
+    let step = plot.0 / points.len().max(2).saturating_sub(1) as f64;
+    let x = |i: usize| LEFT + i as f64 * step;
+    let y = |d: Duration| TOP + plot.1 * (1.0 - d.as_secs_f64() / slowest);
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" \
+         height=\"{h}\" viewBox=\"0 0 {w} {h}\" font-family=\"monospace\" \
+         font-size=\"11\">\n<rect width=\"{w}\" height=\"{h}\" \
+         fill=\"#0f0f23\"/>\n",
+        w = WIDTH,
+        h = HEIGHT
+    );
+    let _ = writeln!(
+        svg,
+        "<g stroke=\"#333340\"><line x1=\"{l}\" y1=\"{t}\" x2=\"{l}\" \
+         y2=\"{b}\"/><line x1=\"{l}\" y1=\"{b}\" x2=\"{r}\" y2=\"{b}\"/>\
+         </g>\n<g fill=\"#cccccc\" text-anchor=\"end\"><text x=\"{tx}\" \
+         y=\"{ty}\">{max:.2?}</text><text x=\"{tx}\" y=\"{b}\">0</text></g>",
+        l = LEFT,
+        t = TOP,
+        b = TOP + plot.1,
+        r = LEFT + plot.0,
+        tx = LEFT - 6.0,
+        ty = TOP + 8.0,
+        max = Duration::from_secs_f64(slowest)
+    );
+    let line = points
+        .iter()
+        .enumerate()
+        .map(|(i, p)| format!("{:.1},{:.1}", x(i), y(p.median)))
+        .collect::<Vec<_>>();
+    let _ = writeln!(
+        svg,
+        "<polyline points=\"{}\" fill=\"none\" stroke=\"#009900\" \
+         stroke-width=\"2\"/>",
+        line.join(" ")
+    );
+    for (i, point) in points.iter().enumerate() {
+        let _ = writeln!(
+            svg,
+            "<circle cx=\"{x:.1}\" cy=\"{y:.1}\" r=\"4\" fill=\"#ffff66\">\
+             <title>{rev} {date}: median {median:.2?}, min {min:.2?} over \
+             {runs} runs</title></circle><text x=\"{x:.1}\" y=\"{ly:.1}\" \
+             fill=\"#cccccc\" text-anchor=\"end\" \
+             transform=\"rotate(-45 {x:.1} {ly:.1})\">{rev}</text>",
+            x = x(i),
+            y = y(point.median),
+            ly = TOP + plot.1 + 14.0,
+            rev = point.revision.short(),
+            date = date(point.time),
+            median = point.median,
+            min = point.min,
+            runs = point.runs
+        );
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// `YYYY-MM-DD` of a Unix time, in UTC.
+pub fn date(time: u64) -> String {
+    // Howard Hinnant's days_from_civil, backwards.
+    let days = (time / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524
+        - day_of_era / 146_096)
+        / 365;
+    let day_of_year =
+        day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + (month <= 2) as i64;
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(commit: &str, dirty: bool, time: u64, median_ms: u64) -> Record {
+        Record {
+            time,
+            revision: Revision {
+                commit: commit.to_string(),
+                dirty,
+            },
+            build: "release".to_string(),
+            day: 15,
+            part: 2,
+            implementation: "default".to_string(),
+            input: "input".to_string(),
+            runs: 10,
+            median: Duration::from_millis(median_ms),
+            min: Duration::from_millis(median_ms - 1),
+        }
+    }
+
+    #[test]
+    fn test_store() {
+        let path = std::env::temp_dir()
+            .join(format!("aoc-history-{}.tsv", std::process::id()));
+        let _ = fs::remove_file(&path);
+        assert_eq!(load(&path), Ok(Vec::new()));
+        let records = [
+            record("a1b2c3d4e5", false, 1, 30),
+            record("f00", true, 2, 20),
+        ];
+        append(&path, &records[..1]).unwrap();
+        append(&path, &records[1..]).unwrap();
+        assert_eq!(load(&path).unwrap(), records);
+        let mut text = fs::read_to_string(&path).unwrap();
+        text.insert_str(0, "1\tabc\n");
+        fs::write(&path, text).unwrap();
+        assert_eq!(load(&path).unwrap(), records);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_series() {
+        let mut other_part = record("bbb", false, 3, 5);
+        other_part.part = 1;
+        let records = [
+            record("aaa", false, 1, 30),
+            record("aaa", true, 2, 25),
+            other_part,
+            record("bbb", false, 4, 12),
+            record("aaa", true, 5, 24),
+        ];
+        let series = Series {
+            day: 15,
+            part: 2,
+            implementation: "default",
+            input: "input",
+            build: "release",
+        };
+        let points = series.points(&records);
+        let times = points.iter().map(|p| p.time).collect::<Vec<_>>();
+        assert_eq!(times, [1, 5, 4]);
+        let table = table(&points);
+        let lines = table.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[1].starts_with("aaa       1970-01-01      30.00ms"));
+        assert!(lines[1].ends_with(&"#".repeat(CHART_WIDTH)));
+        assert!(lines[2].starts_with("aaa+"));
+        assert!(lines[3].ends_with(" ################"));
+    }
+
+    #[test]
+    fn test_chart() {
+        let records =
+            [record("aaa", false, 1, 30), record("bbb", false, 2, 15)];
+        let chart = chart(&records.iter().collect::<Vec<_>>());
+        assert!(chart.starts_with("<svg") && chart.ends_with("</svg>\n"));
+        assert!(chart.contains("<polyline points=\"80.0,10.0 700.0,90.0\""));
+        assert!(chart.contains(">30.00ms</text>"));
+        assert!(chart.contains("<title>bbb 1970-01-01: median 15.00ms"));
+    }
+
+    #[test]
+    fn test_date() {
+        assert_eq!(date(0), "1970-01-01");
+        assert_eq!(date(951_782_400), "2000-02-29");
+        assert_eq!(date(1_669_852_800), "2022-12-01");
+        assert_eq!(date(1_798_761_599), "2026-12-31");
+    }
+
+    #[test]
+    fn test_revision() {
+        let unknown = Revision::current(&std::env::temp_dir().join("none"));
+        assert_eq!(unknown.short(), "unknown");
+        let revision = Revision {
+            commit: "8f3ed32c0ffee".to_string(),
+            dirty: true,
+        };
+        assert_eq!(revision.short(), "8f3ed32+");
+    }
+}
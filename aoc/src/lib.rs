@@ -0,0 +1,516 @@
+pub mod alloc;
+pub mod args;
+pub mod complexity;
+pub mod dashboard;
+pub mod history;
+pub mod player;
+pub mod plugin;
+pub mod repl;
+pub mod report;
+
+use common::answer::Answer;
+use common::context::{Cancelled, Context};
+#[cfg(feature = "visualize")]
+use common::image::{Animation, Image};
+#[cfg(feature = "visualize")]
+use common::svg::Svg;
+use common::{Day, Implementation};
+use std::cell::Cell;
+use std::fmt;
+use std::io::{self, BufRead};
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Once};
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub const DAYS_IN_EVENT: u8 = 25;
+
+pub const DAYS: [&Day; 15] = [
+    &day01::DAY,
+    &day02::DAY,
+    &day03::DAY,
+    &day04::DAY,
+    &day05::DAY,
+    &day06::DAY,
+    &day07::DAY,
+    &day08::DAY,
+    &day09::DAY,
+    &day10::DAY,
+    &day11::DAY,
+    &day12::DAY,
+    &day13::DAY,
+    &day14::DAY,
+    &day15::DAY,
+];
+
+/// Looks a day up among the registered plugins, then the built-in days.
+pub fn find_day(day: u8) -> Option<&'static Day> {
+    plugin::registered()
+        .iter()
+        .chain(DAYS.iter())
+        .copied()
+        .find(|d| d.day == day)
+}
+
+/// Every available day in order, plugins replacing built-in days.
+pub fn days() -> Vec<&'static Day> {
+    (1..=25).filter_map(find_day).collect()
+}
+
+/// Solves a part of a line-oriented day from a reader, holding only what
+/// the day's `stream` module needs instead of the whole input.
+pub type StreamSolver = fn(&mut dyn BufRead) -> io::Result<Answer>;
+
+macro_rules! stream_solvers {
+    ($($day:literal => $krate:ident,)+) => {
+        /// The streaming solver of a part, for the days that have one.
+        pub fn stream_solver(day: u8, part: u8) -> Option<StreamSolver> {
+            match (day, part) {
+                $(
+                    ($day, 1) => Some(|reader| {
+                        $krate::stream::solve_part_one(reader)
+                            .map(Answer::from)
+                    }),
+                    ($day, 2) => Some(|reader| {
+                        $krate::stream::solve_part_two(reader)
+                            .map(Answer::from)
+                    }),
+                )+
+                _ => None,
+            }
+        }
+    };
+}
+
+stream_solvers! {
+    1 => day01,
+    2 => day02,
+    3 => day03,
+    4 => day04,
+    6 => day06,
+    9 => day09,
+    10 => day10,
+}
+
+/// Draws a day's input for one part, see `common::image`.
+#[cfg(feature = "visualize")]
+#[derive(Debug, Clone, Copy)]
+pub struct Renderer {
+    pub image: fn(&str, u8) -> Result<Image, String>,
+    /// For the simulated days.
+    pub animation: Option<Animate>,
+}
+
+/// Records a part with a frame every so many steps.
+#[cfg(feature = "visualize")]
+pub type Animate = fn(&str, u8, usize) -> Result<Animation, String>;
+
+#[cfg(feature = "visualize")]
+pub fn renderer(day: u8) -> Option<Renderer> {
+    Some(match day {
+        8 => Renderer {
+            image: day08::visualize::image,
+            animation: None,
+        },
+        9 => Renderer {
+            image: day09::visualize::image,
+            animation: Some(day09::visualize::animation),
+        },
+        12 => Renderer {
+            image: day12::visualize::image,
+            animation: None,
+        },
+        14 => Renderer {
+            image: day14::visualize::image,
+            animation: Some(day14::visualize::animation),
+        },
+        _ => return None,
+    })
+}
+
+/// Draws a day's input and answers as an SVG, see `common::svg`.
+#[cfg(feature = "visualize")]
+pub type Drawing = fn(&str) -> Result<Svg, String>;
+
+#[cfg(feature = "visualize")]
+pub fn drawing(day: u8) -> Option<Drawing> {
+    match day {
+        12 => Some(day12::visualize::svg),
+        14 => Some(day14::visualize::svg),
+        15 => Some(day15::visualize::svg),
+        _ => None,
+    }
+}
+
+/// The root of the workspace, with a directory per day.
+pub fn workspace_dir() -> PathBuf {
+    let aoc = Path::new(env!("CARGO_MANIFEST_DIR"));
+    aoc.parent().unwrap_or(aoc).to_path_buf()
+}
+
+/// Directory of a day's crate, where its `input.TXT` lives.
+pub fn day_dir(day: u8) -> PathBuf {
+    workspace_dir().join(day.to_string())
+}
+
+/// The personal puzzle input of a day, if it has been downloaded: its
+/// `input.TXT`, or its `input.TXT.enc` decrypted, see [`common::crypt`].
+pub fn read_input(day: u8) -> Result<Option<String>, String> {
+    common::input::read_personal(&day_dir(day))
+}
+
+/// The expected answers to a day's `input.TXT`, from the `answers.TXT` next
+/// to it.
+pub fn read_answers(day: u8) -> [Option<Answer>; 2] {
+    std::fs::read_to_string(day_dir(day).join("answers.TXT"))
+        .map(|answers| parse_answers(&answers))
+        .unwrap_or_default()
+}
+
+/// Splits an answers file into part one and part two. The parts are
+/// separated by a blank line, so part two can span several lines (day 10's
+/// CRT, or the letters it spells); an empty part is unknown.
+pub fn parse_answers(answers: &str) -> [Option<Answer>; 2] {
+    let answers = answers.replace("\r\n", "\n");
+    let (one, two) = answers.split_once("\n\n").unwrap_or((&answers, ""));
+    [one, two].map(|answer| {
+        let answer = answer.trim_end();
+        (!answer.is_empty()).then(|| Answer::parse(answer))
+    })
+}
+
+/// Runs an implementation, turning a panic into an error message.
+pub fn run(
+    implementation: &Implementation,
+    input: &str,
+) -> Result<Answer, String> {
+    catch_panic(|| implementation.solve(input))
+}
+
+/// How often [`run_with_timeout`] checks on the solver.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// How long a cancelled solver has to stop before it is abandoned.
+const CANCEL_GRACE: Duration = Duration::from_secs(1);
+
+/// Why [`run_with_timeout`] has no answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunError {
+    /// The solver panicked, with its message.
+    Panicked(String),
+    /// The solver was cancelled once the timeout had passed, and left
+    /// running if it did not stop.
+    TimedOut {
+        after: Duration,
+        still_running: bool,
+    },
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Panicked(message) => f.write_str(message),
+            Self::TimedOut {
+                after,
+                still_running: false,
+            } => write!(f, "timed out after {:?}", after),
+            Self::TimedOut {
+                after,
+                still_running: true,
+            } => write!(f, "timed out after {:?}, still running", after),
+        }
+    }
+}
+
+/// Runs an implementation on its own thread, cancelling it once `timeout`
+/// has passed. `tick` is called with the solver's context every few
+/// milliseconds, to show its progress. A solver that does not stop when
+/// cancelled is left running in the background.
+pub fn run_with_timeout(
+    implementation: &Implementation,
+    input: &str,
+    timeout: Option<Duration>,
+    tick: impl FnMut(&Context),
+) -> Result<Answer, RunError> {
+    let solver = *implementation;
+    on_thread(input, timeout, tick, move |input, context| {
+        solver.run_in(input, context)
+    })
+}
+
+/// Runs `f` on a copy of `input` the way [`run_with_timeout`] runs a
+/// solver.
+fn on_thread<T: Send + 'static>(
+    input: &str,
+    timeout: Option<Duration>,
+    mut tick: impl FnMut(&Context),
+    f: impl FnOnce(&str, &Context) -> Result<T, Cancelled> + Send + 'static,
+) -> Result<T, RunError> {
+    let context = Arc::new(Context::new());
+    let (tx, rx) = mpsc::channel();
+    let (input, solver_context) = (input.to_string(), Arc::clone(&context));
+    thread::spawn(move || {
+        let _ = tx.send(catch_panic(|| f(&input, &solver_context)));
+    });
+    let timed_out = |still_running: bool| RunError::TimedOut {
+        after: timeout.unwrap_or_default(),
+        still_running,
+    };
+    let mut deadline = timeout.map(|timeout| Instant::now() + timeout);
+    loop {
+        match rx.recv_timeout(POLL_INTERVAL) {
+            Ok(Ok(Ok(value))) => return Ok(value),
+            Ok(Ok(Err(Cancelled))) => return Err(timed_out(false)),
+            Ok(Err(panic)) => return Err(RunError::Panicked(panic)),
+            Err(RecvTimeoutError::Disconnected) => {
+                let died = "the solver thread died".to_string();
+                return Err(RunError::Panicked(died));
+            }
+            Err(RecvTimeoutError::Timeout) => tick(&context),
+        }
+        match deadline {
+            Some(at) if Instant::now() >= at => {
+                if context.is_cancelled() {
+                    return Err(timed_out(true));
+                }
+                context.cancel();
+                deadline = Some(Instant::now() + CANCEL_GRACE);
+            }
+            _ => {}
+        }
+    }
+}
+
+thread_local! {
+    /// How many calls of [`catch_panic`] the thread is in.
+    static CATCHING: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Calls `f`, turning a panic into an error message. The panic is not
+/// printed, the caller reports the message; any other panic still goes to
+/// the hook that was installed before.
+pub fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    static QUIET_HOOK: Once = Once::new();
+    QUIET_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING.with(Cell::get) == 0 {
+                previous(info);
+            }
+        }));
+    });
+    CATCHING.with(|catching| catching.set(catching.get() + 1));
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(f));
+    CATCHING.with(|catching| catching.set(catching.get() - 1));
+    result.map_err(|err| {
+        err.downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| err.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string())
+    })
+}
+
+/// Wall times of `runs` runs of an implementation, fastest first. The runs
+/// happen on their own thread, as with [`run_with_timeout`], and are
+/// cancelled once they have taken `timeout` each on average.
+pub fn timings(
+    implementation: &Implementation,
+    input: &str,
+    runs: usize,
+    timeout: Option<Duration>,
+) -> Result<Vec<Duration>, RunError> {
+    let solver = *implementation;
+    let timeout = timeout.map(|timeout| timeout * runs.max(1) as u32);
+    let mut timings = on_thread(
+        input,
+        timeout,
+        |_| {},
+        move |input, context| {
+            (0..runs)
+                .map(|_| {
+                    let start = Instant::now();
+                    solver.run_in(input, context)?;
+                    Ok(start.elapsed())
+                })
+                .collect::<Result<Vec<_>, _>>()
+        },
+    )?;
+    timings.sort_unstable();
+    Ok(timings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_days_are_registered_in_order() {
+        for (i, day) in DAYS.iter().enumerate() {
+            assert_eq!(day.day as usize, i + 1);
+        }
+        assert!(find_day(16).is_none());
+    }
+
+    #[test]
+    fn test_run_reports_panics() {
+        let panics = Implementation {
+            name: "panics",
+            run: |input, _| panic!("bad input: {}", input),
+        };
+        assert_eq!(run(&panics, "x"), Err("bad input: x".to_string()));
+        let answer = DAYS[0].part_one[0];
+        assert_eq!(run(&answer, DAYS[0].sample), Ok(Answer::Int(24000)));
+    }
+
+    #[test]
+    fn test_run_with_timeout() {
+        let counts = Implementation {
+            name: "counts",
+            run: |_, context| {
+                context.set_total(4);
+                for done in 1.. {
+                    context.check()?;
+                    context.set_done(done);
+                    thread::sleep(POLL_INTERVAL);
+                }
+                unreachable!()
+            },
+        };
+        let mut progress = Vec::new();
+        let timeout = Some(Duration::from_millis(300));
+        assert_eq!(
+            run_with_timeout(&counts, "", timeout, |context| {
+                progress.extend(context.progress())
+            }),
+            Err(RunError::TimedOut {
+                after: Duration::from_millis(300),
+                still_running: false
+            })
+        );
+        assert!(progress.contains(&(4, 4)));
+        assert!(progress.windows(2).all(|w| w[0] <= w[1]));
+
+        let hangs = Implementation {
+            name: "hangs",
+            run: |_, _| loop {
+                thread::sleep(POLL_INTERVAL);
+            },
+        };
+        assert_eq!(
+            run_with_timeout(&hangs, "", Some(Duration::ZERO), |_| {})
+                .unwrap_err()
+                .to_string(),
+            "timed out after 0ns, still running"
+        );
+        let answer = DAYS[0].part_one[0];
+        assert_eq!(
+            run_with_timeout(&answer, DAYS[0].sample, timeout, |_| {}),
+            Ok(Answer::Int(24000))
+        );
+    }
+
+    #[test]
+    fn test_timings_are_guarded() {
+        let panics = Implementation {
+            name: "panics",
+            run: |_, _| panic!("boom"),
+        };
+        assert_eq!(
+            timings(&panics, "", 3, None),
+            Err(RunError::Panicked("boom".to_string()))
+        );
+        let waits = Implementation {
+            name: "waits",
+            run: |_, context| loop {
+                context.check()?;
+                thread::sleep(POLL_INTERVAL);
+            },
+        };
+        let timeout = Some(Duration::from_millis(100));
+        assert!(matches!(
+            timings(&waits, "", 3, timeout),
+            Err(RunError::TimedOut { .. })
+        ));
+        let answer = DAYS[0].part_one[0];
+        let runs = timings(&answer, DAYS[0].sample, 3, timeout).unwrap();
+        assert!(runs.len() == 3 && runs.windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    #[test]
+    fn test_parse_answers() {
+        assert_eq!(
+            parse_answers("24000\n\n45000\n"),
+            [Some(Answer::Int(24000)), Some(Answer::Int(45000))]
+        );
+        assert_eq!(
+            parse_answers("13140\r\n\r\n##..\r\n#..#\r\n"),
+            [Some(Answer::Int(13140)), Some(Answer::parse("##..\n#..#"))]
+        );
+        assert_eq!(parse_answers("24000\n"), [Some(Answer::Int(24000)), None]);
+        assert_eq!(parse_answers(""), [None, None]);
+    }
+
+    #[test]
+    fn test_stream_solvers_match() {
+        for day in DAYS {
+            for (part, implementations) in
+                [(1, day.part_one), (2, day.part_two)]
+            {
+                let Some(solve) = stream_solver(day.day, part) else {
+                    continue;
+                };
+                assert_eq!(
+                    solve(&mut day.sample.as_bytes()).unwrap(),
+                    implementations[0].solve(day.sample),
+                    "day {} part {}",
+                    day.day,
+                    part
+                );
+            }
+        }
+        assert!(stream_solver(5, 1).is_none());
+    }
+
+    #[test]
+    fn test_stream_solvers_reject_malformed_lines() {
+        for day in [1, 2, 4, 9, 10] {
+            for part in [1, 2] {
+                let solve = stream_solver(day, part).unwrap();
+                let err = solve(&mut "100\nabc\n".as_bytes()).unwrap_err();
+                assert_eq!(
+                    err.kind(),
+                    io::ErrorKind::InvalidData,
+                    "day {}",
+                    day
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "visualize")]
+    #[test]
+    fn test_renderers_draw_samples() {
+        for day in DAYS {
+            let Some(renderer) = renderer(day.day) else {
+                continue;
+            };
+            for part in [1, 2] {
+                let image = (renderer.image)(day.sample, part).unwrap();
+                assert!(image.width() > 0 && image.height() > 0);
+                if let Some(animate) = renderer.animation {
+                    let animation = animate(day.sample, part, 10).unwrap();
+                    assert_eq!(animation.frames().last(), Some(&image));
+                }
+            }
+        }
+        assert!(renderer(1).is_none());
+    }
+
+    #[test]
+    fn test_generators_are_deterministic() {
+        for day in DAYS {
+            assert_eq!((day.generate)(7, 20), (day.generate)(7, 20));
+        }
+    }
+}
@@ -0,0 +1,111 @@
+mod cmd;
+
+use aoc::alloc::TrackingAllocator;
+use std::process::ExitCode;
+
+#[global_allocator]
+static ALLOC: TrackingAllocator = TrackingAllocator;
+
+const USAGE: &str = "\
+Usage: aoc <command> [args]
+
+Commands:
+    gen <day> [--seed N] [--size M]   Print a generated puzzle input
+    difftest [<day>] [--seeds N] [--size M] [--timeout SECS]
+                                      Check that implementations agree,
+                                      giving up on a run after SECS (10)
+    bench <day> [--runs N] [--seed N] [--size M] [--no-history]
+                [--timeout SECS]      Time implementations side by side,
+                                      adding the timings to the history,
+                                      skipping any taking over SECS (60)
+                                      a run
+    history <day> <part> [--impl NAME] [--input SOURCE] [--build BUILD]
+            [--svg FILE]              Print the timings of a part at each
+                                      commit timed, or chart them; only
+                                      bench and report record timings,
+                                      solve does not
+    scale <day> <part> [--impl NAME] [--from N] [--factor F] [--steps K]
+                       [--seed S] [--runs R] [--timeout SECS]
+                                      Fit time and memory growth on
+                                      generated inputs, up to the first
+                                      size taking over SECS (60)
+    repl [<day>] [--input PATH]       Explore a day's parsed input
+    dashboard [<day>]                 Run days from a full-screen table
+    report [--out DIR] [--runs N] [--sample] [--no-history]
+           [--timeout SECS]           Write a static HTML site of every
+                                      day's answers, timings and drawings
+                                      to DIR (report by default), giving
+                                      up on a part after SECS (60)
+    play <day> [<simulation>] [--input PATH] [--fps N]
+         [--crop X0,Y0,X1,Y1] [--dump FILE [--every N] [--frames N]]
+                                      Animate a simulation in the terminal
+                                      (space pause, n/b step, +/- speed,
+                                      arrows pan), or write its frames to
+                                      FILE
+    solve <day> [<part>] [--input PATH] [--impl NAME] [--timeout SECS]
+                                      Solve parts, giving up on each after
+                                      SECS, with a progress bar for the
+                                      parts that report progress
+    stream <day> <part> [FILE]        Solve a part reading FILE (or stdin)
+                                      line by line
+    encrypt [<day>]                   Encrypt each input.TXT to the
+                                      input.TXT.enc next to it, with the
+                                      key in .aoc-key (created if needed)
+                                      or AOC_PASSPHRASE
+    decrypt [<day>] [--force]         Write each input.TXT back from its
+                                      input.TXT.enc
+    render <day> <part> --out PATH [--input PATH] [--scale N] [--every N]
+                                      Draw days 8, 9, 12 and 14 as .png,
+                                      .ppm or .pgm, animate days 9 and 14
+                                      as .gif or a directory of frames
+                                      (built with --features visualize)
+";
+
+fn main() -> ExitCode {
+    load_plugins();
+    let mut args = std::env::args().skip(1);
+    let result = match args.next().as_deref() {
+        Some("gen") => cmd::gen::run(args),
+        Some("difftest") => cmd::difftest::run(args),
+        Some("bench") => cmd::bench::run(args),
+        Some("history") => cmd::history::run(args),
+        Some("scale") => cmd::scale::run(args),
+        Some("repl") => cmd::repl::run(args),
+        Some("dashboard") => cmd::dashboard::run(args),
+        Some("report") => cmd::report::run(args),
+        Some("play") => cmd::play::run(args),
+        Some("solve") => cmd::solve::run(args),
+        Some("stream") => cmd::stream::run(args),
+        Some("encrypt") => cmd::crypt::encrypt(args),
+        Some("decrypt") => cmd::crypt::decrypt(args),
+        #[cfg(feature = "visualize")]
+        Some("render") => cmd::render::run(args),
+        _ => {
+            eprint!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn load_plugins() {
+    let mut days = Vec::new();
+    for (path, day) in aoc::plugin::load_dir(&aoc::plugin::dir()) {
+        match day {
+            Ok(day) => {
+                eprintln!("plugin {}: day {}", path.display(), day.day);
+                days.push(day);
+            }
+            Err(err) => {
+                eprintln!("plugin {}: skipped, {}", path.display(), err)
+            }
+        }
+    }
+    aoc::plugin::register(days);
+}
@@ -0,0 +1,292 @@
+use common::geometry::{BoundingBox, Point, Vec2};
+use common::simulation::Playback;
+use std::io::{self, Write};
+use std::time::Duration;
+
+pub const DEFAULT_FPS: u32 = 10;
+const MAX_FPS: u32 = 240;
+
+/// What the keys of the player do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    TogglePause,
+    /// One step forward, pausing.
+    Step,
+    /// One step back, pausing.
+    Back,
+    Faster,
+    Slower,
+    Restart,
+    /// Moves the viewport by that many columns and lines.
+    Pan(i64, i64),
+}
+
+/// A day's simulation played back frame by frame, a step per frame, with
+/// the rendered states cropped to a viewport.
+pub struct Player {
+    name: &'static str,
+    sim: Box<dyn Playback>,
+    fps: u32,
+    paused: bool,
+    /// Set once stepping stops, with the reason if the simulation panicked.
+    ended: Option<Result<(), String>>,
+    /// The columns and lines of the rendered state to show, all of it if
+    /// `None`.
+    viewport: Option<BoundingBox>,
+}
+
+impl Player {
+    pub fn new(name: &'static str, sim: Box<dyn Playback>) -> Self {
+        Self {
+            name,
+            sim,
+            fps: DEFAULT_FPS,
+            paused: false,
+            ended: None,
+            viewport: None,
+        }
+    }
+
+    pub fn with_fps(mut self, fps: u32) -> Self {
+        self.fps = fps.clamp(1, MAX_FPS);
+        self
+    }
+
+    pub fn with_viewport(mut self, viewport: BoundingBox) -> Self {
+        self.viewport = Some(viewport);
+        self
+    }
+
+    /// Shows `width` by `height` characters from the top left corner,
+    /// unless a viewport was already set.
+    pub fn fit(&mut self, width: usize, height: usize) {
+        let corner = Point::new(
+            width.saturating_sub(1) as i64,
+            height.saturating_sub(1) as i64,
+        );
+        self.viewport
+            .get_or_insert(BoundingBox::new(Point::ZERO).including(corner));
+    }
+
+    pub fn steps(&self) -> usize {
+        self.sim.steps()
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.ended.is_some()
+    }
+
+    /// How long a frame stays on screen, `None` when paused or over.
+    pub fn frame_delay(&self) -> Option<Duration> {
+        (!self.paused && !self.is_over())
+            .then(|| Duration::from_secs(1) / self.fps)
+    }
+
+    /// Advances to the next frame. Returns `false` once the simulation is
+    /// over.
+    pub fn tick(&mut self) -> bool {
+        if self.is_over() {
+            return false;
+        }
+        match crate::catch_panic(|| self.sim.step()) {
+            Ok(true) => return true,
+            Ok(false) => self.ended = Some(Ok(())),
+            Err(err) => self.ended = Some(Err(err)),
+        }
+        false
+    }
+
+    pub fn apply(&mut self, action: Action) {
+        match action {
+            Action::TogglePause => self.paused = !self.paused,
+            Action::Step => {
+                self.paused = true;
+                self.tick();
+            }
+            Action::Back => {
+                self.paused = true;
+                let back = self.sim.steps().saturating_sub(1);
+                self.seek(back);
+            }
+            Action::Faster => self.fps = (self.fps * 2).min(MAX_FPS),
+            Action::Slower => self.fps = (self.fps / 2).max(1),
+            Action::Restart => self.seek(0),
+            Action::Pan(dx, dy) => {
+                if let Some(view) = &mut self.viewport {
+                    // Never above or left of the first line and column.
+                    let delta =
+                        Vec2::new(dx.max(-view.min.x), dy.max(-view.min.y));
+                    view.min += delta;
+                    view.max += delta;
+                }
+            }
+        }
+    }
+
+    fn seek(&mut self, step: usize) {
+        if let Ok(true) = crate::catch_panic(|| self.sim.seek(step)) {
+            self.ended = None;
+        }
+    }
+
+    /// The current state, cropped to the viewport.
+    pub fn frame(&self) -> Vec<String> {
+        let state = self.sim.render();
+        match self.viewport {
+            Some(view) => crop(&state, view),
+            None => state.lines().map(str::to_string).collect(),
+        }
+    }
+
+    /// One line about the playback and the keys.
+    pub fn status(&self) -> String {
+        let state = match &self.ended {
+            Some(Ok(())) => "over".to_string(),
+            Some(Err(err)) => format!("panicked: {}", err),
+            None if self.paused => "paused".to_string(),
+            None => "playing".to_string(),
+        };
+        format!(
+            "{} | step {} | {} fps | {} | space pause, n/b step, +/- speed, \
+             arrows pan, r restart, q quit",
+            self.name,
+            self.sim.steps(),
+            self.fps,
+            state
+        )
+    }
+
+    /// Plays the simulation to its end, or up to `max_frames` frames,
+    /// writing the state every `every` steps and the last one, each after
+    /// a `--- step N ---` line. Returns the number of frames written.
+    pub fn dump(
+        &mut self,
+        every: usize,
+        max_frames: Option<usize>,
+        out: &mut impl Write,
+    ) -> io::Result<usize> {
+        let every = every.max(1);
+        let max_frames = max_frames.unwrap_or(usize::MAX);
+        self.write_frame(out)?;
+        let (mut frames, mut last_written) = (1, self.steps());
+        while frames < max_frames && self.tick() {
+            if self.steps().is_multiple_of(every) {
+                self.write_frame(out)?;
+                (frames, last_written) = (frames + 1, self.steps());
+            }
+        }
+        if let Some(Err(err)) = &self.ended {
+            return Err(io::Error::other(format!("panic: {}", err)));
+        }
+        if frames < max_frames && last_written != self.steps() {
+            self.write_frame(out)?;
+            frames += 1;
+        }
+        Ok(frames)
+    }
+
+    fn write_frame(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "--- step {} ---", self.steps())?;
+        for line in self.frame() {
+            writeln!(out, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+/// The part of `text` inside `view`, columns and lines counted from zero.
+/// Lines are padded so every frame covers the same area.
+pub fn crop(text: &str, view: BoundingBox) -> Vec<String> {
+    let width = view.width() as usize;
+    let lines = text.lines().chain(std::iter::repeat(""));
+    lines
+        .skip(view.min.y as usize)
+        .take(view.height() as usize)
+        .map(|line| {
+            let visible = line.chars().skip(view.min.x as usize).take(width);
+            format!("{:width$}", visible.collect::<String>())
+        })
+        .collect()
+}
+
+/// Parses a viewport as `X0,Y0,X1,Y1`, bounds included.
+pub fn parse_viewport(viewport: &str) -> Result<BoundingBox, String> {
+    let invalid =
+        || format!("invalid viewport, expected X0,Y0,X1,Y1: {}", viewport);
+    let [x0, y0, x1, y1] =
+        common::parse::extract::<i64, 4>(viewport).map_err(|_| invalid())?;
+    if x0 > x1 || y0 > y1 || x0.min(y0) < 0 {
+        return Err(invalid());
+    }
+    Ok(BoundingBox::new(Point::new(x0, y0)).including(Point::new(x1, y1)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn crane() -> Player {
+        let simulator = &day05::DAY.simulations[1];
+        Player::new(
+            simulator.name,
+            (simulator.start)(day05::DAY.sample).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_crop() {
+        let view = parse_viewport("1,1,3,3").unwrap();
+        assert_eq!(crop("abcd\nefgh\nij", view), ["fgh", "j  ", "   "]);
+        assert!(parse_viewport("1,1,0,3").is_err());
+        assert!(parse_viewport("1,-1,3,3").is_err());
+        assert!(parse_viewport("1,1,3").is_err());
+    }
+
+    #[test]
+    fn test_keys() {
+        let mut player = crane().with_fps(8);
+        assert_eq!(player.frame_delay(), Some(Duration::from_millis(125)));
+        player.apply(Action::Step);
+        player.apply(Action::Step);
+        assert_eq!(player.frame_delay(), None);
+        player.apply(Action::Back);
+        assert_eq!(player.steps(), 1);
+        player.apply(Action::Faster);
+        player.apply(Action::TogglePause);
+        assert_eq!(
+            player.frame_delay(),
+            Some(Duration::from_millis(62) + Duration::from_micros(500))
+        );
+        while player.tick() {}
+        assert!(player.is_over() && player.status().contains("| over |"));
+        player.apply(Action::Restart);
+        assert!(!player.is_over() && player.steps() == 0);
+
+        player.fit(3, 2);
+        player.apply(Action::Pan(-5, 1));
+        assert_eq!(player.viewport, parse_viewport("0,1,2,2").ok());
+    }
+
+    #[test]
+    fn test_dump() {
+        let mut out = Vec::new();
+        let frames = crane().dump(3, None, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let headers = out.lines().filter(|l| l.starts_with("---"));
+        assert_eq!(
+            headers.collect::<Vec<_>>(),
+            ["--- step 0 ---", "--- step 3 ---", "--- step 4 ---"]
+        );
+        assert_eq!(frames, 3);
+        assert!(out.ends_with("done: MCD\n"));
+
+        let mut out = Vec::new();
+        let view = parse_viewport("0,0,4,1").unwrap();
+        let mut player = crane().with_viewport(view);
+        assert_eq!(player.dump(1, Some(2), &mut out).unwrap(), 2);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "--- step 0 ---\n    [\n[N] [\n--- step 1 ---\n[D]  \n[N] [\n"
+        );
+    }
+}
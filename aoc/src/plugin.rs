@@ -0,0 +1,104 @@
+//! Days loaded at startup from dynamic libraries built with
+//! `common::export_plugin!`. Plugins must be built with the same toolchain
+//! as the runner, `Day` only has a Rust layout. Their solvers' panics come
+//! back as values and they allocate with the runner's allocator, see
+//! `common::plugin`.
+
+use common::plugin::Allocator;
+use common::{Day, Plugin, PLUGIN_ABI_VERSION};
+use libloading::Library;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+static PLUGINS: OnceLock<Vec<&'static Day>> = OnceLock::new();
+
+/// Where the runner looks for plugins: `$AOC_PLUGINS`, or `plugins/` at the
+/// root of the repository.
+pub fn dir() -> PathBuf {
+    match std::env::var_os("AOC_PLUGINS") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../plugins"),
+    }
+}
+
+/// Loads a plugin. The library is never unloaded, the day points into it.
+pub fn load(path: &Path) -> Result<&'static Day, String> {
+    let library = unsafe { Library::new(path) }.map_err(|e| e.to_string())?;
+    let plugin = unsafe { library.get::<*const Plugin>(b"AOC_PLUGIN\0") }
+        .map_err(|_| "no AOC_PLUGIN symbol, not built as a plugin?")?;
+    let plugin = unsafe { &**plugin };
+    let day = check(plugin)?;
+    (plugin.init)(Allocator::global());
+    std::mem::forget(library);
+    Ok(day)
+}
+
+fn check(plugin: &Plugin) -> Result<&'static Day, String> {
+    match plugin.abi_version {
+        PLUGIN_ABI_VERSION => Ok(plugin.day),
+        version => Err(format!(
+            "plugin ABI version {}, the runner expects {}",
+            version, PLUGIN_ABI_VERSION
+        )),
+    }
+}
+
+/// Loads every library in `dir`, in file name order, with the outcome for
+/// each file. A missing directory has no plugins.
+pub fn load_dir(dir: &Path) -> Vec<(PathBuf, Result<&'static Day, String>)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths = entries
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| {
+            path.extension() == Some(std::env::consts::DLL_EXTENSION.as_ref())
+        })
+        .collect::<Vec<_>>();
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| {
+            let day = load(&path);
+            (path, day)
+        })
+        .collect()
+}
+
+/// Makes `days` visible through `crate::find_day` and `crate::days`, ahead
+/// of the built-in ones. Only the first call has an effect.
+pub fn register(days: Vec<&'static Day>) {
+    let _ = PLUGINS.set(days);
+}
+
+pub fn registered() -> &'static [&'static Day] {
+    PLUGINS.get().map_or(&[], Vec::as_slice)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_abi_version_is_checked() {
+        let plugin = Plugin {
+            abi_version: PLUGIN_ABI_VERSION,
+            day: &day01::DAY,
+            init: |_| {},
+        };
+        assert_eq!(check(&plugin).unwrap().day, 1);
+        let plugin = Plugin {
+            abi_version: PLUGIN_ABI_VERSION + 1,
+            ..plugin
+        };
+        assert!(check(&plugin).unwrap_err().contains("ABI version"));
+    }
+
+    #[test]
+    fn test_load_rejects_other_files() {
+        let path = std::env::temp_dir().join("aoc-not-a-plugin.so");
+        std::fs::write(&path, "not a library").unwrap();
+        assert!(load(&path).is_err());
+        assert!(load_dir(Path::new("/nonexistent")).is_empty());
+    }
+}
@@ -0,0 +1,310 @@
+use crate::{find_day, RunError};
+use common::simulation::Playback;
+use common::Day;
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+/// A part still running after this long is cancelled, for the prompt to
+/// come back.
+const PART_TIMEOUT: Duration = Duration::from_secs(30);
+
+const HELP: &str = "\
+day <n>                 switch to another day (loads its sample)
+load sample|input|<path>
+                        load another input for the current day
+gen <seed> <size>       load a generated input
+raw [lines]             print the raw input
+show                    print the parsed input
+part <1|2> [impl]       run a part, optionally a named implementation,
+                        giving up after 30 seconds
+sim [name]              start one of the day's simulations (the first one
+                        by default)
+step [n]                advance the simulation n steps (1 by default)
+back [n]                rewind the simulation n steps (1 by default)
+seek <step>             jump to a step of the simulation
+help                    show this help
+quit                    leave the REPL";
+
+pub enum Outcome {
+    Continue(String),
+    Quit,
+}
+
+/// Line-oriented session exploring one day's input at a time.
+pub struct Repl {
+    day: &'static Day,
+    source: String,
+    input: String,
+    /// The simulation being played and its name.
+    sim: Option<(&'static str, Box<dyn Playback>)>,
+}
+
+impl Repl {
+    pub fn new(day: &'static Day) -> Self {
+        Self {
+            day,
+            source: "sample".to_string(),
+            input: day.sample.to_string(),
+            sim: None,
+        }
+    }
+
+    pub fn prompt(&self) -> String {
+        match &self.sim {
+            Some((name, sim)) => format!(
+                "day{} ({}, {} at step {})> ",
+                self.day.day,
+                self.source,
+                name,
+                sim.steps()
+            ),
+            None => format!("day{} ({})> ", self.day.day, self.source),
+        }
+    }
+
+    pub fn execute(&mut self, line: &str) -> Result<Outcome, String> {
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        let Some((&command, args)) = words.split_first() else {
+            return Ok(Outcome::Continue(String::new()));
+        };
+        let output = match command {
+            "quit" | "exit" => return Ok(Outcome::Quit),
+            "help" => self.help(),
+            "day" => {
+                let day = parse_arg::<u8>(args, 0, "day")?;
+                *self = Repl::new(
+                    find_day(day)
+                        .ok_or(format!("day {} is not solved", day))?,
+                );
+                format!("switched to day {}", day)
+            }
+            "load" => self.load(args.first().ok_or("missing input")?)?,
+            "gen" => {
+                let seed = parse_arg(args, 0, "seed")?;
+                let size = parse_arg(args, 1, "size")?;
+                self.input = (self.day.generate)(seed, size);
+                self.source = format!("seed {} size {}", seed, size);
+                self.sim = None;
+                format!("generated {} bytes", self.input.len())
+            }
+            "raw" => match args.first() {
+                Some(n) => {
+                    let n = n.parse().map_err(|_| "invalid line count")?;
+                    self.input.lines().take(n).collect::<Vec<_>>().join("\n")
+                }
+                None => self.input.trim_end().to_string(),
+            },
+            "show" => {
+                let input = &self.input;
+                crate::catch_panic(|| (self.day.inspect)(input))
+                    .map_err(|err| format!("panic: {}", err))?
+            }
+            "part" => self.part(args)?,
+            "sim" => self.start_simulation(args)?,
+            "step" => {
+                let n = parse_arg_or(args, 0, "step count", 1)?;
+                self.seek(|steps| steps + n)?
+            }
+            "back" => {
+                let n = parse_arg_or(args, 0, "step count", 1)?;
+                self.seek(|steps| steps.saturating_sub(n))?
+            }
+            "seek" => {
+                let step = parse_arg(args, 0, "step")?;
+                self.seek(|_| step)?
+            }
+            name => {
+                let command = self
+                    .day
+                    .commands
+                    .iter()
+                    .find(|c| c.name == name)
+                    .ok_or_else(|| format!("unknown command: {}", name))?;
+                let input = &self.input;
+                crate::catch_panic(|| (command.run)(input, args))
+                    .map_err(|err| format!("panic: {}", err))??
+            }
+        };
+        Ok(Outcome::Continue(output))
+    }
+
+    fn help(&self) -> String {
+        let mut help = HELP.to_string();
+        for command in self.day.commands {
+            let _ = write!(help, "\n{:<23} {}", command.usage, command.help);
+        }
+        if !self.day.simulations.is_empty() {
+            help.push_str("\n\nsimulations:");
+        }
+        for simulator in self.day.simulations {
+            let _ = write!(help, "\n{:<23} {}", simulator.name, simulator.help);
+        }
+        help
+    }
+
+    fn load(&mut self, source: &str) -> Result<String, String> {
+        self.input = match source {
+            "sample" => self.day.sample.to_string(),
+            "input" => crate::read_input(self.day.day)?
+                .ok_or("no input.TXT for this day")?,
+            path => std::fs::read_to_string(path)
+                .map_err(|err| format!("{}: {}", path, err))?,
+        };
+        self.source = source.to_string();
+        self.sim = None;
+        Ok(format!("loaded {} bytes", self.input.len()))
+    }
+
+    fn start_simulation(&mut self, args: &[&str]) -> Result<String, String> {
+        let simulators = self.day.simulations;
+        let simulator = match args.first() {
+            Some(name) => simulators
+                .iter()
+                .find(|s| s.name == *name)
+                .ok_or_else(|| format!("unknown simulation: {}", name))?,
+            None => simulators.first().ok_or("this day has no simulations")?,
+        };
+        let input = &self.input;
+        let sim = crate::catch_panic(|| (simulator.start)(input))
+            .map_err(|err| format!("panic: {}", err))??;
+        let state = sim.render();
+        self.sim = Some((simulator.name, sim));
+        Ok(state)
+    }
+
+    /// Moves the simulation to the step `to` picks from the current one.
+    fn seek(
+        &mut self,
+        to: impl FnOnce(usize) -> usize,
+    ) -> Result<String, String> {
+        let (_, sim) = self.sim.as_mut().ok_or("no simulation, see `sim`")?;
+        let target = to(sim.steps());
+        let reached = crate::catch_panic(|| sim.seek(target))
+            .map_err(|err| format!("panic: {}", err))?;
+        let state = sim.render();
+        match reached {
+            true => Ok(state),
+            false => Ok(format!("{}\nended at step {}", state, sim.steps())),
+        }
+    }
+
+    fn part(&self, args: &[&str]) -> Result<String, String> {
+        let implementations = match parse_arg(args, 0, "part")? {
+            1 => self.day.part_one,
+            2 => self.day.part_two,
+            part => return Err(format!("invalid part: {}", part)),
+        };
+        let implementation = match args.get(1) {
+            Some(name) => implementations
+                .iter()
+                .find(|i| i.name == *name)
+                .ok_or_else(|| format!("unknown implementation: {}", name))?,
+            None => &implementations[0],
+        };
+        let start = Instant::now();
+        let answer = crate::run_with_timeout(
+            implementation,
+            &self.input,
+            Some(PART_TIMEOUT),
+            |_| {},
+        )
+        .map_err(|err| match err {
+            RunError::Panicked(panic) => format!("panic: {}", panic),
+            timed_out => timed_out.to_string(),
+        })?;
+        let elapsed = start.elapsed();
+        match answer.submission() {
+            // A grid spelling letters.
+            letters if letters != answer.to_string() => {
+                Ok(format!("{}\n= {}\n({:?})", answer, letters, elapsed))
+            }
+            _ => Ok(format!("{}\n({:?})", answer, elapsed)),
+        }
+    }
+}
+
+fn parse_arg<T: std::str::FromStr>(
+    args: &[&str],
+    idx: usize,
+    name: &str,
+) -> Result<T, String> {
+    let arg = args.get(idx).ok_or_else(|| format!("missing {}", name))?;
+    arg.parse()
+        .map_err(|_| format!("invalid {}: {}", name, arg))
+}
+
+/// Like [`parse_arg`], with `default` when the argument is missing.
+fn parse_arg_or<T: std::str::FromStr>(
+    args: &[&str],
+    idx: usize,
+    name: &str,
+    default: T,
+) -> Result<T, String> {
+    match args.get(idx) {
+        Some(_) => parse_arg(args, idx, name),
+        None => Ok(default),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(repl: &mut Repl, line: &str) -> Result<String, String> {
+        match repl.execute(line)? {
+            Outcome::Continue(output) => Ok(output),
+            Outcome::Quit => Ok("<quit>".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_parts_and_inputs() {
+        let mut repl = Repl::new(find_day(1).unwrap());
+        assert!(run(&mut repl, "part 1").unwrap().starts_with("24000\n"));
+        assert_eq!(run(&mut repl, "raw 2").unwrap(), "1000\n2000");
+        run(&mut repl, "gen 3 10").unwrap();
+        assert_eq!(repl.prompt(), "day1 (seed 3 size 10)> ");
+        assert!(run(&mut repl, "part 3").is_err());
+        assert_eq!(run(&mut repl, "quit").unwrap(), "<quit>");
+    }
+
+    #[test]
+    fn test_day_commands() {
+        let mut repl = Repl::new(find_day(1).unwrap());
+        assert!(run(&mut repl, "tree").is_err());
+        run(&mut repl, "day 7").unwrap();
+        assert!(run(&mut repl, "tree 0").unwrap().starts_with("- / "));
+        assert!(run(&mut repl, "help").unwrap().contains("tree [max depth]"));
+        assert!(run(&mut repl, "show").unwrap().contains("name: \"e\""));
+        assert!(run(&mut repl, "day 42").is_err());
+    }
+
+    #[test]
+    fn test_panics_are_errors() {
+        let mut repl = Repl::new(find_day(15).unwrap());
+        repl.input = "Sensor at x=a\n".to_string();
+        assert!(run(&mut repl, "show").unwrap_err().starts_with("panic: "));
+        let mut repl = Repl::new(find_day(7).unwrap());
+        repl.input = "$ cd missing\n".to_string();
+        assert!(run(&mut repl, "tree").unwrap_err().starts_with("panic: "));
+    }
+
+    #[test]
+    fn test_simulations() {
+        let mut repl = Repl::new(find_day(5).unwrap());
+        assert!(run(&mut repl, "step").is_err());
+        assert!(run(&mut repl, "sim 9002").is_err());
+        assert!(run(&mut repl, "help").unwrap().contains("\n9001 "));
+        assert!(run(&mut repl, "sim 9001").unwrap().starts_with("    [D]"));
+        assert!(run(&mut repl, "step 3").unwrap().ends_with("from 1 to 2"));
+        assert_eq!(repl.prompt(), "day5 (sample, 9001 at step 3)> ");
+        assert!(run(&mut repl, "back 2").unwrap().ends_with("from 1 to 3"));
+        let end = run(&mut repl, "seek 10").unwrap();
+        assert!(end.ends_with("done: MCD\nended at step 4"));
+        assert!(run(&mut repl, "step x").is_err());
+        run(&mut repl, "load sample").unwrap();
+        assert_eq!(repl.prompt(), "day5 (sample)> ");
+        run(&mut repl, "day 1").unwrap();
+        assert!(run(&mut repl, "sim").is_err());
+    }
+}
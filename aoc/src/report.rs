@@ -0,0 +1,840 @@
+//! A static site about every day: a page per day with its answers, timings
+//! and drawings, and an index with the star calendar. Each page is a single
+//! HTML file with its style and images inlined, so it can be shared as is.
+
+use crate::dashboard::Status;
+use crate::history::{self, Record, Revision, Series};
+use crate::{RunError, DAYS_IN_EVENT};
+use common::answer::Answer;
+use common::Day;
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+/// The title of each puzzle and what its two parts ask.
+const PUZZLES: [(&str, &str); 15] = [
+    (
+        "Calorie Counting",
+        "The Calories carried by the Elf with the most, then by the top \
+         three Elves together.",
+    ),
+    (
+        "Rock Paper Scissors",
+        "The score of a strategy guide read as the moves to play, then as \
+         the outcomes to reach.",
+    ),
+    (
+        "Rucksack Reorganization",
+        "The priorities of the item in both compartments of each rucksack, \
+         then of the badge of each group of three.",
+    ),
+    (
+        "Camp Cleanup",
+        "The pairs of section ranges where one contains the other, then \
+         those that overlap at all.",
+    ),
+    (
+        "Supply Stacks",
+        "The crates on top of the stacks once the crane moves them one at a \
+         time, then several at once.",
+    ),
+    (
+        "Tuning Trouble",
+        "Where the first start-of-packet marker ends, then the first \
+         start-of-message marker.",
+    ),
+    (
+        "No Space Left On Device",
+        "The total size of the directories of at most 100000, then the \
+         smallest directory to delete to make room for the update.",
+    ),
+    (
+        "Treetop Tree House",
+        "The trees visible from outside the grid, then the best scenic \
+         score.",
+    ),
+    (
+        "Rope Bridge",
+        "The positions visited by the tail of a rope of two knots, then of \
+         ten knots.",
+    ),
+    (
+        "Cathode-Ray Tube",
+        "The sum of six signal strengths, then the letters drawn on the CRT.",
+    ),
+    (
+        "Monkey in the Middle",
+        "The monkey business after 20 rounds, then after 10000 rounds \
+         without relief.",
+    ),
+    (
+        "Hill Climbing Algorithm",
+        "The fewest steps from the start to the best signal, then from any \
+         square of the lowest elevation.",
+    ),
+    (
+        "Distress Signal",
+        "The pairs of packets in the right order, then the decoder key of \
+         the sorted packets.",
+    ),
+    (
+        "Regolith Reservoir",
+        "The units of sand at rest before sand flows into the abyss, then \
+         once the floor stops it at the source.",
+    ),
+    (
+        "Beacon Exclusion Zone",
+        "The positions of a row that cannot hold a beacon, then the tuning \
+         frequency of the distress beacon.",
+    ),
+];
+
+/// Simulations still running after this many steps, or once the timeout
+/// has passed, are shown where they stopped.
+const MAX_STEPS: usize = 1_000_000;
+/// The final state of a simulation is cut to this many lines and columns.
+const FRAME_SIZE: (usize, usize) = (60, 160);
+#[cfg(feature = "visualize")]
+const IMAGE_SCALE: usize = 4;
+
+const STYLE: &str = "\
+body { background: #0f0f23; color: #cccccc; font-family: monospace;
+       max-width: 1100px; margin: 2em auto; padding: 0 1em; }
+a { color: #009900; text-decoration: none; }
+a:hover { color: #99ff99; }
+h1, h2 { color: #ffffff; font-weight: normal; }
+table { border-collapse: collapse; margin: 1em 0; }
+th, td { border-bottom: 1px solid #333340; padding: 0.3em 0.8em;
+         text-align: left; vertical-align: top; }
+td.number { text-align: right; }
+pre { margin: 0; }
+.correct { color: #ffff66; }
+.bad { color: #ff6666; }
+.dim { color: #666666; }
+.calendar { display: grid; grid-template-columns: repeat(5, 1fr);
+            gap: 0.5em; margin: 1em 0; }
+.calendar > * { border: 1px solid #333340; padding: 0.6em; min-height: 4em; }
+.visual { margin: 1em 0; overflow-x: auto; }
+.visual img, .visual svg { max-width: 100%; height: auto; }
+.drawing img, .drawing svg { image-rendering: pixelated;
+                             background: #ffffff; }
+";
+
+/// Timings of one implementation of a part, fastest first.
+pub struct Benchmark {
+    pub name: &'static str,
+    pub timings: Vec<Duration>,
+}
+
+impl Benchmark {
+    pub fn median(&self) -> Duration {
+        self.timings[self.timings.len() / 2]
+    }
+
+    pub fn mean(&self) -> Duration {
+        self.timings.iter().sum::<Duration>() / self.timings.len() as u32
+    }
+}
+
+pub struct PartReport {
+    pub answer: Result<Answer, String>,
+    /// Whether the answer is missing because the solver ran out of time.
+    pub timed_out: bool,
+    /// From `answers.TXT`, only for the personal input.
+    pub expected: Option<Answer>,
+    /// Empty if the part failed.
+    pub benchmarks: Vec<Benchmark>,
+}
+
+impl PartReport {
+    pub fn status(&self) -> Status {
+        match (&self.answer, &self.expected) {
+            (Err(_), _) => Status::Error,
+            (Ok(_), None) => Status::Unchecked,
+            (Ok(answer), Some(expected)) if answer == expected => {
+                Status::Correct
+            }
+            (Ok(_), Some(_)) => Status::Wrong,
+        }
+    }
+
+    /// The answer cell of the tables, short for the index.
+    fn error(&self, short: bool) -> String {
+        let Err(err) = &self.answer else {
+            return String::new();
+        };
+        match (self.timed_out, short) {
+            (true, true) => "timed out".to_string(),
+            (true, false) => escape(err),
+            (false, true) => "panicked".to_string(),
+            (false, false) => format!("panicked: {}", escape(err)),
+        }
+    }
+
+    /// An hourglass for a part that timed out, else its status.
+    fn mark(&self) -> &'static str {
+        match self.timed_out {
+            true => "&#8987;",
+            false => status_mark(self.status()),
+        }
+    }
+}
+
+/// Something a day draws from its input.
+pub enum Visual {
+    /// The final state of a simulation.
+    Text {
+        title: String,
+        text: String,
+    },
+    Svg {
+        title: String,
+        svg: String,
+    },
+    Png {
+        title: String,
+        png: Vec<u8>,
+    },
+}
+
+pub struct DayReport {
+    pub day: &'static Day,
+    /// Whether the personal input was used, or the sample.
+    pub personal: bool,
+    pub input_bytes: usize,
+    pub input_lines: usize,
+    pub parts: [PartReport; 2],
+    pub visuals: Vec<Visual>,
+}
+
+impl DayReport {
+    /// Solves both parts of a day, times each implementation over `runs`
+    /// runs and draws what the day can draw. Without a personal input the
+    /// sample is used and nothing is checked. A solver still running after
+    /// `timeout` is cancelled and left out of the timings, and simulations
+    /// are shown where they are when it has passed.
+    pub fn new(
+        day: &'static Day,
+        input: Option<String>,
+        expected: [Option<Answer>; 2],
+        runs: usize,
+        timeout: Option<Duration>,
+    ) -> Self {
+        let personal = input.is_some();
+        let input = input.unwrap_or_else(|| day.sample.to_string());
+        let [one, two] = expected.map(|answer| answer.filter(|_| personal));
+        let run = |implementation| {
+            let answer = crate::run_with_timeout(
+                implementation,
+                &input,
+                timeout,
+                |_| {},
+            );
+            let timed_out = matches!(answer, Err(RunError::TimedOut { .. }));
+            (answer.map_err(|err| err.to_string()), timed_out)
+        };
+        let part = |implementations: &'static [common::Implementation],
+                    expected: Option<Answer>| {
+            let (answer, timed_out) = run(&implementations[0]);
+            let benchmarks = match answer {
+                Ok(_) => implementations
+                    .iter()
+                    .enumerate()
+                    .filter(|&(i, implementation)| {
+                        i == 0 || run(implementation).0.is_ok()
+                    })
+                    .filter_map(|(_, implementation)| {
+                        let runs = runs.max(1);
+                        crate::timings(implementation, &input, runs, timeout)
+                            .ok()
+                            .map(|timings| Benchmark {
+                                name: implementation.name,
+                                timings,
+                            })
+                    })
+                    .collect(),
+                Err(_) => Vec::new(),
+            };
+            PartReport {
+                answer,
+                timed_out,
+                expected,
+                benchmarks,
+            }
+        };
+        Self {
+            day,
+            personal,
+            input_bytes: input.len(),
+            input_lines: input.lines().count(),
+            parts: [part(day.part_one, one), part(day.part_two, two)],
+            visuals: visuals(day, &input, timeout),
+        }
+    }
+
+    pub fn stars(&self) -> usize {
+        self.parts
+            .iter()
+            .filter(|part| part.status() == Status::Correct)
+            .count()
+    }
+
+    /// The timings, for the history.
+    pub fn records(&self, revision: &Revision) -> Vec<Record> {
+        let mut records = Vec::new();
+        for (part, report) in (1..).zip(&self.parts) {
+            for benchmark in &report.benchmarks {
+                records.push(Record::new(
+                    revision,
+                    (self.day.day, part),
+                    benchmark.name,
+                    self.source(),
+                    &benchmark.timings,
+                ));
+            }
+        }
+        records
+    }
+
+    fn source(&self) -> &'static str {
+        match self.personal {
+            true => "input",
+            false => "sample",
+        }
+    }
+
+    /// The day's page, with the timings of the reference implementations
+    /// across commits taken from `history`.
+    pub fn page(&self, history: &[Record]) -> String {
+        let day = self.day.day;
+        let (title, summary) = puzzle(day);
+        let mut html = String::new();
+        let _ = write!(
+            html,
+            "<nav><a href=\"index.html\">Advent of Code 2022</a></nav>\n\
+             <h1>Day {}: {}</h1>\n<p>{}</p>\n<p class=\"dim\">{}, {} bytes \
+             in {} lines.</p>\n",
+            day,
+            escape(&title),
+            escape(summary),
+            match self.personal {
+                true => "Personal input",
+                false => "Sample input, there is no personal input",
+            },
+            self.input_bytes,
+            self.input_lines
+        );
+
+        html.push_str(
+            "<h2>Answers</h2>\n<table>\n<tr><th>Part</th><th>Answer</th>\
+             <th>Expected</th><th></th></tr>\n",
+        );
+        for (i, part) in self.parts.iter().enumerate() {
+            let answer = match &part.answer {
+                Ok(answer) => answer_html(answer),
+                Err(_) => part.error(false),
+            };
+            let expected = part.expected.as_ref().map(answer_html);
+            let _ = writeln!(
+                html,
+                "<tr class=\"{}\"><td>{}</td><td>{}</td><td>{}</td>\
+                 <td>{}</td></tr>",
+                status_class(part.status()),
+                i + 1,
+                answer,
+                expected.unwrap_or_default(),
+                part.mark()
+            );
+        }
+        html.push_str("</table>\n");
+
+        html.push_str("<h2>Timings</h2>\n");
+        for (i, part) in self.parts.iter().enumerate() {
+            if part.benchmarks.is_empty() {
+                continue;
+            }
+            let _ = writeln!(html, "<h3>Part {}</h3>", i + 1);
+            html.push_str(&timings_chart(&part.benchmarks));
+            html.push_str(&timings_table(&part.benchmarks));
+            let series = Series {
+                day,
+                part: i as u8 + 1,
+                implementation: part.benchmarks[0].name,
+                input: self.source(),
+                build: history::build(),
+            };
+            let points = series.points(history);
+            if points.len() > 1 {
+                let _ = writeln!(
+                    html,
+                    "<h4>{} across commits</h4>\n\
+                     <div class=\"visual\">{}</div>",
+                    escape(series.implementation),
+                    history::chart(&points)
+                );
+            }
+        }
+
+        if !self.visuals.is_empty() {
+            html.push_str("<h2>Drawings</h2>\n");
+        }
+        for visual in &self.visuals {
+            html.push_str(&visual_html(visual));
+        }
+        document(&format!("Day {}: {}", day, title), &html)
+    }
+}
+
+/// The title of a day's puzzle and what it asks, if it is known.
+fn puzzle(day: u8) -> (String, &'static str) {
+    match PUZZLES.get(day as usize - 1) {
+        Some(&(title, summary)) => (title.to_string(), summary),
+        None => (format!("Day {}", day), ""),
+    }
+}
+
+/// The page a day's report is written to.
+pub fn file_name(day: u8) -> String {
+    format!("day{:02}.html", day)
+}
+
+/// The star calendar of the event, linking to the days in `reports`, then
+/// a table of their answers and timings.
+pub fn index(reports: &[DayReport]) -> String {
+    let stars = reports.iter().map(DayReport::stars).sum::<usize>();
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<h1>Advent of Code 2022</h1>\n<p><span class=\"correct\">{}/{} \
+         &#9733;</span></p>\n<div class=\"calendar\">\n",
+        stars,
+        2 * DAYS_IN_EVENT
+    );
+    for day in 1..=DAYS_IN_EVENT {
+        match reports.iter().find(|report| report.day.day == day) {
+            Some(report) => {
+                let marks = report
+                    .parts
+                    .iter()
+                    .map(|part| {
+                        format!(
+                            "<span class=\"{}\">{}</span>",
+                            status_class(part.status()),
+                            part.mark()
+                        )
+                    })
+                    .collect::<String>();
+                let _ = writeln!(
+                    html,
+                    "<a href=\"{}\">{}<br>{}<br>{}</a>",
+                    file_name(day),
+                    day,
+                    escape(&puzzle(day).0),
+                    marks
+                );
+            }
+            None => {
+                let _ = writeln!(html, "<div class=\"dim\">{}</div>", day);
+            }
+        }
+    }
+    html.push_str("</div>\n");
+
+    html.push_str(
+        "<table>\n<tr><th>Day</th><th>Input</th><th>Part 1</th>\
+         <th>Part 2</th><th>Median</th></tr>\n",
+    );
+    for report in reports {
+        let cells = report
+            .parts
+            .iter()
+            .map(|part| {
+                let answer = match &part.answer {
+                    Ok(answer) => escape(&answer.submission()),
+                    Err(_) => part.error(true),
+                };
+                format!(
+                    "<td class=\"{}\">{} {}</td>",
+                    status_class(part.status()),
+                    answer,
+                    part.mark()
+                )
+            })
+            .collect::<String>();
+        let median = report
+            .parts
+            .iter()
+            .filter_map(|part| part.benchmarks.first())
+            .map(Benchmark::median)
+            .sum::<Duration>();
+        let _ = writeln!(
+            html,
+            "<tr><td><a href=\"{}\">{}</a></td><td>{}</td>{}\
+             <td class=\"number\">{:.2?}</td></tr>",
+            file_name(report.day.day),
+            report.day.day,
+            if report.personal { "input" } else { "sample" },
+            cells,
+            median
+        );
+    }
+    html.push_str("</table>\n");
+    document("Advent of Code 2022", &html)
+}
+
+fn document(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\
+         \n<title>{}</title>\n<style>\n{}</style>\n</head>\n<body>\n{}</body>\
+         \n</html>\n",
+        escape(title),
+        STYLE,
+        body
+    )
+}
+
+/// Grids are shown as drawn, with the letters they spell.
+fn answer_html(answer: &Answer) -> String {
+    match answer {
+        Answer::Grid(_) => format!(
+            "<pre>{}</pre>{}",
+            escape(&answer.to_string()),
+            escape(&answer.submission())
+        ),
+        answer => format!("<code>{}</code>", escape(&answer.to_string())),
+    }
+}
+
+fn status_class(status: Status) -> &'static str {
+    match status {
+        Status::Correct => "correct",
+        Status::Wrong | Status::Error => "bad",
+        _ => "dim",
+    }
+}
+
+fn status_mark(status: Status) -> &'static str {
+    match status {
+        Status::Correct => "&#9733;",
+        Status::Wrong | Status::Error => "&#10007;",
+        Status::Unchecked => "&#9734;",
+        _ => "",
+    }
+}
+
+/// A bar per implementation for its median, with a line from its fastest
+/// to its slowest run.
+fn timings_chart(benchmarks: &[Benchmark]) -> String {
+    const ROW: usize = 24;
+    const LABEL: usize = 120;
+    const WIDTH: usize = 600;
+    let slowest = benchmarks
+        .iter()
+        .filter_map(|b| b.timings.last())
+        .max()
+        .map_or(f64::EPSILON, |d| d.as_secs_f64().max(f64::EPSILON));
+    let x =
+        |d: Duration| LABEL as f64 + d.as_secs_f64() / slowest * WIDTH as f64;
+    let height = benchmarks.len() * ROW;
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" \
+         height=\"{}\" font-family=\"monospace\" font-size=\"12\">\n",
+        LABEL + WIDTH + 80,
+        height
+    );
+    for (i, benchmark) in benchmarks.iter().enumerate() {
+        let y = (i * ROW) as f64;
+        let median = benchmark.median();
+        let (fastest, slowest) =
+            (benchmark.timings[0], *benchmark.timings.last().unwrap());
+        let _ = writeln!(
+            svg,
+            "<text x=\"0\" y=\"{:.1}\" fill=\"#cccccc\">{}</text>\
+             <rect x=\"{}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{}\" \
+             fill=\"#009900\"/>\
+             <line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" \
+             stroke=\"#ffff66\"/>\
+             <text x=\"{:.1}\" y=\"{:.1}\" fill=\"#cccccc\">{:.2?}</text>",
+            y + 16.0,
+            escape(benchmark.name),
+            LABEL,
+            y + 4.0,
+            x(median) - LABEL as f64,
+            ROW - 8,
+            x(fastest),
+            y + ROW as f64 / 2.0,
+            x(slowest),
+            y + ROW as f64 / 2.0,
+            x(slowest) + 6.0,
+            y + 16.0,
+            median
+        );
+    }
+    svg.push_str("</svg>\n");
+    format!("<div class=\"visual\">{}</div>\n", svg)
+}
+
+/// Median, fastest, slowest and mean run of each implementation, and how
+/// much faster than the first one it is.
+fn timings_table(benchmarks: &[Benchmark]) -> String {
+    let mut html = String::from(
+        "<table>\n<tr><th>Implementation</th><th>Runs</th><th>Median</th>\
+         <th>Min</th><th>Max</th><th>Mean</th><th>Speedup</th></tr>\n",
+    );
+    let reference = benchmarks[0].median().as_secs_f64();
+    for benchmark in benchmarks {
+        let median = benchmark.median();
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td class=\"number\">{}</td>\
+             <td class=\"number\">{:.2?}</td><td class=\"number\">{:.2?}</td>\
+             <td class=\"number\">{:.2?}</td><td class=\"number\">{:.2?}</td>\
+             <td class=\"number\">{:.2}x</td></tr>",
+            escape(benchmark.name),
+            benchmark.timings.len(),
+            median,
+            benchmark.timings[0],
+            benchmark.timings.last().unwrap(),
+            benchmark.mean(),
+            reference / median.as_secs_f64().max(f64::EPSILON)
+        );
+    }
+    html.push_str("</table>\n");
+    html
+}
+
+fn visual_html(visual: &Visual) -> String {
+    let (title, content) = match visual {
+        Visual::Text { title, text } => {
+            (title, format!("<pre>{}</pre>", escape(text)))
+        }
+        Visual::Svg { title, svg } => (title, svg.clone()),
+        Visual::Png { title, png } => (
+            title,
+            format!(
+                "<img alt=\"{}\" src=\"data:image/png;base64,{}\">",
+                escape(title),
+                base64(png)
+            ),
+        ),
+    };
+    format!(
+        "<h3>{}</h3>\n<div class=\"visual drawing\">{}</div>\n",
+        escape(title),
+        content
+    )
+}
+
+/// What a day can draw from an input: the images and drawings of the
+/// `visualize` feature, and the final state of its simulations.
+fn visuals(day: &Day, input: &str, timeout: Option<Duration>) -> Vec<Visual> {
+    let mut visuals = Vec::new();
+    #[cfg(feature = "visualize")]
+    {
+        if let Some(draw) = crate::drawing(day.day) {
+            if let Ok(Ok(svg)) = crate::catch_panic(|| draw(input)) {
+                visuals.push(Visual::Svg {
+                    title: "Drawing".to_string(),
+                    svg: svg.to_string(),
+                });
+            }
+        }
+        if let Some(renderer) = crate::renderer(day.day) {
+            for part in 1..=2 {
+                let image =
+                    crate::catch_panic(|| (renderer.image)(input, part));
+                if let Ok(Ok(image)) = image {
+                    visuals.push(Visual::Png {
+                        title: format!("Part {}", part),
+                        png: image.scaled(IMAGE_SCALE).to_png(),
+                    });
+                }
+            }
+        }
+    }
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    let running = || deadline.is_none_or(|deadline| Instant::now() < deadline);
+    for simulator in day.simulations {
+        let end = crate::catch_panic(|| {
+            let mut sim = (simulator.start)(input)?;
+            while sim.steps() < MAX_STEPS && running() && sim.step() {}
+            Ok::<_, String>((sim.steps(), sim.render()))
+        });
+        if let Ok(Ok((steps, state))) = end {
+            let (lines, columns) = FRAME_SIZE;
+            let text = state
+                .lines()
+                .take(lines)
+                .map(|line| line.chars().take(columns).collect::<String>())
+                .collect::<Vec<_>>()
+                .join("\n");
+            visuals.push(Visual::Text {
+                title: format!(
+                    "Simulation {} after {} steps",
+                    simulator.name, steps
+                ),
+                text,
+            });
+        }
+    }
+    visuals
+}
+
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8] =
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            match i <= chunk.len() {
+                true => {
+                    let sextet = (n >> (18 - 6 * i)) & 0x3f;
+                    encoded.push(ALPHABET[sextet as usize] as char);
+                }
+                false => encoded.push('='),
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_answers;
+
+    #[test]
+    fn test_day_page() {
+        let expected = parse_answers("24000\n\n45001\n");
+        let report = DayReport::new(
+            &day01::DAY,
+            Some(day01::DAY.sample.to_string()),
+            expected,
+            3,
+            None,
+        );
+        assert_eq!(report.parts[0].status(), Status::Correct);
+        assert_eq!(report.parts[1].status(), Status::Wrong);
+        assert_eq!(report.stars(), 1);
+        assert_eq!(report.parts[0].benchmarks[0].timings.len(), 3);
+        let page = report.page(&[]);
+        assert!(page.starts_with("<!DOCTYPE html>"));
+        assert!(page.contains("<h1>Day 1: Calorie Counting</h1>"));
+        assert!(page.contains("<code>24000</code>"));
+        assert!(page.contains("<code>45001</code>"));
+        assert!(page.contains("<h3>Part 2</h3>\n<div class=\"visual\"><svg"));
+
+        let sample = DayReport::new(&day10::DAY, None, [None, None], 1, None);
+        assert!(!sample.personal && sample.stars() == 0);
+        assert_eq!(sample.parts[0].status(), Status::Unchecked);
+        assert!(sample.page(&[]).contains("<pre>##..##..##"));
+
+        let revision = Revision {
+            commit: "8f3ed32".to_string(),
+            dirty: false,
+        };
+        let mut history = report.records(&revision);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[1].part, 2);
+        assert!(!report.page(&history).contains("across commits"));
+        history.extend(report.records(&Revision {
+            dirty: true,
+            ..revision
+        }));
+        let page = report.page(&history);
+        assert!(page.contains("<h4>default across commits</h4>"));
+        assert!(page.contains(">8f3ed32+</text>"));
+    }
+
+    #[test]
+    fn test_simulations_are_drawn() {
+        let report = DayReport::new(&day05::DAY, None, [None, None], 1, None);
+        let titles = report
+            .visuals
+            .iter()
+            .filter_map(|visual| match visual {
+                Visual::Text { title, .. } => Some(title.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            titles,
+            [
+                "Simulation 9000 after 4 steps",
+                "Simulation 9001 after 4 steps"
+            ]
+        );
+    }
+
+    fn forever(
+        _: &str,
+        context: &common::context::Context,
+    ) -> Result<Answer, common::context::Cancelled> {
+        loop {
+            context.check()?;
+        }
+    }
+
+    #[test]
+    fn test_timed_out_part() {
+        static SLOW: Day = Day {
+            part_two: &[common::Implementation {
+                name: "forever",
+                run: forever,
+            }],
+            ..day01::DAY
+        };
+        let timeout = Duration::from_millis(50);
+        let report =
+            DayReport::new(&SLOW, None, [None, None], 1, Some(timeout));
+        assert!(!report.parts[0].timed_out && report.parts[1].timed_out);
+        assert_eq!(report.parts[1].status(), Status::Error);
+        assert!(report.parts[1].benchmarks.is_empty());
+        assert!(report.page(&[]).contains("timed out after 50ms"));
+        assert!(index(&[report]).contains("timed out &#8987;"));
+    }
+
+    #[test]
+    fn test_index() {
+        let reports = [DayReport::new(
+            &day01::DAY,
+            Some(day01::DAY.sample.to_string()),
+            parse_answers("24000\n\n45000\n"),
+            1,
+            None,
+        )];
+        let index = index(&reports);
+        assert!(index.contains("2/50 &#9733;"));
+        assert!(index.contains("<a href=\"day01.html\">1<br>Calorie Counting"));
+        assert!(index.contains("<div class=\"dim\">25</div>"));
+    }
+
+    #[test]
+    fn test_escape_and_base64() {
+        assert_eq!(
+            escape("<a href=\"x\">&</a>"),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;"
+        );
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+    }
+}
@@ -0,0 +1,64 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// A target without `std`, where a dependency that quietly pulls it in
+/// fails to build.
+const NO_STD_TARGET: &str = "thumbv7em-none-eabi";
+
+/// Builds every day without its `std` feature, for `target` if given.
+fn build_days(target: Option<&str>) {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let mut cargo =
+        Command::new(std::env::var("CARGO").unwrap_or("cargo".into()));
+    cargo
+        .current_dir(&root)
+        .args(["build", "-q", "--no-default-features"])
+        .arg("--target-dir")
+        .arg(std::env::temp_dir().join("aoc-no-std-check"));
+    if let Some(target) = target {
+        cargo.args(["--lib", "--target", target]);
+    }
+    for day in aoc::DAYS {
+        cargo.args(["-p", &format!("day{:02}", day.day)]);
+    }
+    let output = cargo.output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+/// Whether the standard library of `target` is installed for the current
+/// toolchain.
+fn is_installed(target: &str) -> bool {
+    let sysroot = Command::new("rustc")
+        .args(["--print", "sysroot"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().into());
+    sysroot.is_some_and(|sysroot: PathBuf| {
+        sysroot.join("lib/rustlib").join(target).is_dir()
+    })
+}
+
+/// Every day builds without `std`, its binary skipped.
+#[test]
+fn test_days_build_without_std() {
+    build_days(None);
+}
+
+/// Every day's solver logic builds as a `no_std` + `alloc` library for a
+/// target that has no `std` at all.
+#[test]
+fn test_days_build_for_no_std_target() {
+    if !is_installed(NO_STD_TARGET) {
+        eprintln!(
+            "skipped: {} is not installed, `rustup target add {}`",
+            NO_STD_TARGET, NO_STD_TARGET
+        );
+        return;
+    }
+    build_days(Some(NO_STD_TARGET));
+}
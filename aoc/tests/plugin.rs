@@ -0,0 +1,57 @@
+use aoc::alloc::TrackingAllocator;
+use std::path::Path;
+use std::process::Command;
+
+#[global_allocator]
+static ALLOC: TrackingAllocator = TrackingAllocator;
+
+/// Builds day 1 as a plugin, the way a teammate would, and loads it.
+#[test]
+fn test_load_built_plugin() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let target = std::env::temp_dir().join("aoc-plugin-test");
+    let status = Command::new(std::env::var("CARGO").unwrap_or("cargo".into()))
+        .current_dir(&root)
+        .args([
+            "rustc",
+            "-q",
+            "-p",
+            "day01",
+            "--lib",
+            "--features",
+            "plugin",
+        ])
+        .args(["--crate-type", "cdylib", "--target-dir"])
+        .arg(&target)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let dir = target.join("debug");
+    let loaded = aoc::plugin::load_dir(&dir);
+    let [(path, day)] = &loaded[..] else {
+        panic!("expected one plugin in {}", dir.display());
+    };
+    let day = day.as_ref().unwrap();
+    assert!(
+        path.ends_with(format!("libday01.{}", std::env::consts::DLL_EXTENSION))
+    );
+    assert_eq!(day.day, 1);
+    assert_eq!(aoc::run(&day.part_one[0], day.sample), Ok("24000".into()));
+    assert_eq!(aoc::run(&day.part_two[0], day.sample), Ok("45000".into()));
+
+    // A panic in the plugin comes back as an error instead of aborting, and
+    // what the plugin allocates is counted and freed by the runner.
+    let error = aoc::run(&day.part_one[0], "not a number").unwrap_err();
+    assert!(error.contains("InvalidDigit"), "{}", error);
+    let before = TrackingAllocator::current();
+    let model = (day.inspect)(day.sample);
+    assert!(TrackingAllocator::current() >= before + model.len());
+    drop(model);
+    assert!(TrackingAllocator::current() <= before);
+
+    aoc::plugin::register(vec![day]);
+    assert!(std::ptr::eq(aoc::find_day(1).unwrap(), *day));
+    assert!(std::ptr::eq(aoc::days()[0], *day));
+    assert_eq!(aoc::days().len(), aoc::DAYS.len());
+}
@@ -0,0 +1,250 @@
+//! What the parts return: numbers, a line of text or a grid drawing
+//! letters, compared in the form they would be submitted in.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// The answer to a part.
+#[derive(Debug, Clone, Eq)]
+pub enum Answer {
+    Int(i128),
+    /// An integer too large for `Int`, as decimal digits without leading
+    /// zeros.
+    Big(String),
+    /// A single line of text, trimmed.
+    Text(String),
+    /// Several lines, like day 10's CRT, without trailing whitespace or
+    /// blank lines around them.
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    /// Reads an answer as a solver or an answers file would write it. Lines
+    /// lose their trailing whitespace, single lines are trimmed and parsed
+    /// as integers when they are one.
+    pub fn parse(text: &str) -> Self {
+        let lines = text
+            .lines()
+            .map(str::trim_end)
+            .skip_while(|line| line.is_empty())
+            .collect::<Vec<_>>();
+        let end = lines.iter().rposition(|line| !line.is_empty());
+        let lines = &lines[..end.map_or(0, |end| end + 1)];
+        match lines {
+            [] => Answer::Text(String::new()),
+            [line] => parse_line(line.trim_start()),
+            lines => {
+                Answer::Grid(lines.iter().map(|l| l.to_string()).collect())
+            }
+        }
+    }
+
+    /// The answer as it would be submitted: grids that spell letters are
+    /// read, see [`ocr`].
+    pub fn submission(&self) -> String {
+        match self {
+            Answer::Grid(lines) => {
+                ocr(lines).unwrap_or_else(|| lines.join("\n"))
+            }
+            answer => answer.to_string(),
+        }
+    }
+
+    pub fn as_int(&self) -> Option<i128> {
+        match self {
+            Answer::Int(n) => Some(*n),
+            _ => None,
+        }
+    }
+}
+
+fn parse_line(line: &str) -> Answer {
+    if let Ok(n) = line.parse() {
+        return Answer::Int(n);
+    }
+    let (sign, digits) = match line.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", line.strip_prefix('+').unwrap_or(line)),
+    };
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Answer::Text(line.to_string());
+    }
+    // Too large for an `i128` once the leading zeros are gone.
+    Answer::Big(sign.to_string() + digits.trim_start_matches('0'))
+}
+
+/// Answers are equal when they would be submitted the same, so a CRT grid
+/// matches the letters it draws.
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Int(a), Answer::Int(b)) => a == b,
+            (Answer::Text(a), Answer::Text(b)) => a == b,
+            (a, b) => a.submission() == b.submission(),
+        }
+    }
+}
+
+/// The answer as returned, grids line by line.
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::Big(digits) | Answer::Text(digits) => f.write_str(digits),
+            Answer::Grid(lines) => f.write_str(&lines.join("\n")),
+        }
+    }
+}
+
+macro_rules! from_int {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Answer::Int(n as i128)
+            }
+        }
+    )*};
+}
+
+from_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, i128);
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        match i128::try_from(n) {
+            Ok(n) => Answer::Int(n),
+            Err(_) => Answer::Big(n.to_string()),
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::parse(text)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::parse(&text)
+    }
+}
+
+impl From<char> for Answer {
+    fn from(c: char) -> Self {
+        Answer::parse(c.encode_utf8(&mut [0; 4]))
+    }
+}
+
+const GLYPH_WIDTH: usize = 4;
+const GLYPH_HEIGHT: usize = 6;
+
+/// The letters of the puzzles' 4x6 font, row by row.
+const GLYPHS: [(char, &str); 16] = [
+    ('A', ".##.#..##..######..##..#"),
+    ('B', "###.#..####.#..##..####."),
+    ('C', ".##.#..##...#...#..#.##."),
+    ('E', "#####...###.#...#...####"),
+    ('F', "#####...###.#...#...#..."),
+    ('G', ".##.#..##...#.###..#.###"),
+    ('H', "#..##..######..##..##..#"),
+    ('J', "..##...#...#...##..#.##."),
+    ('K', "#..##.#.##..#.#.#.#.#..#"),
+    ('L', "#...#...#...#...#...####"),
+    ('O', ".##.#..##..##..##..#.##."),
+    ('P', "###.#..##..####.#...#..."),
+    ('R', "###.#..##..####.#.#.#..#"),
+    ('S', ".####...#....##....####."),
+    ('U', "#..##..##..##..##..#.##."),
+    ('Z', "####...#..#..#..#...####"),
+];
+
+/// Reads the capital letters drawn by a grid of `#` in the puzzles' font,
+/// one letter every five columns. `None` if any of it is not a letter.
+pub fn ocr(lines: &[impl AsRef<str>]) -> Option<String> {
+    if lines.len() != GLYPH_HEIGHT {
+        return None;
+    }
+    let rows = lines
+        .iter()
+        .map(|line| line.as_ref().chars().map(|c| c == '#').collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let width = rows.iter().map(Vec::len).max()?;
+    let lit = |x: usize, y: usize| rows[y].get(x).copied().unwrap_or(false);
+    let mut text = String::new();
+    for left in (0..width).step_by(GLYPH_WIDTH + 1) {
+        let glyph = (0..GLYPH_HEIGHT)
+            .flat_map(|y| (left..left + GLYPH_WIDTH).map(move |x| (x, y)))
+            .map(|(x, y)| if lit(x, y) { '#' } else { '.' })
+            .collect::<String>();
+        let gap = (0..GLYPH_HEIGHT).any(|y| lit(left + GLYPH_WIDTH, y));
+        let (letter, _) = GLYPHS.iter().find(|(_, g)| *g == glyph)?;
+        if gap {
+            return None;
+        }
+        text.push(*letter);
+    }
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    const CRT: &str = "\
+####..##..####.#..#.####..##..#....###..
+#....#..#....#.#..#....#.#..#.#....#..#.
+###..#......#..#..#...#..#..#.#....#..#.
+#....#.....#...#..#..#...####.#....###..
+#....#..#.#....#..#.#....#..#.#....#.#..
+####..##..####..##..####.#..#.####.#..#.";
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Answer::parse(" 24000\n"), Answer::Int(24000));
+        assert_eq!(Answer::parse("-7"), Answer::Int(-7));
+        assert_eq!(
+            Answer::parse("+00340282366920938463463374607431768211456"),
+            Answer::Big("340282366920938463463374607431768211456".into())
+        );
+        assert_eq!(Answer::parse("CMZ \r\n\n"), Answer::Text("CMZ".into()));
+        assert_eq!(Answer::parse("1-2"), Answer::Text("1-2".into()));
+        assert_eq!(Answer::parse("-"), Answer::Text("-".into()));
+        assert_eq!(
+            Answer::parse("\n#.. \n.#.\n\n"),
+            Answer::Grid(vec!["#..".into(), ".#.".into()])
+        );
+    }
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(Answer::from(13140i32), Answer::Int(13140));
+        assert_eq!(Answer::from(u64::MAX).as_int(), Some(u64::MAX as i128));
+        assert_eq!(Answer::from(u128::MAX), Answer::Big(u128::MAX.to_string()));
+        assert_eq!(Answer::from(String::from("MCD")), Answer::parse("MCD"));
+        assert_eq!(Answer::from('x').to_string(), "x");
+        assert_eq!(Answer::from("12").as_int(), Some(12));
+    }
+
+    #[test]
+    fn test_ocr() {
+        let crt = Answer::from(CRT);
+        assert_eq!(crt.submission(), "ECZUZALR");
+        assert_eq!(crt.to_string(), CRT);
+        assert_eq!(crt, Answer::from("ECZUZALR"));
+        assert_ne!(crt, Answer::from("ECZUZALS"));
+        let lines = CRT.lines().collect::<Vec<_>>();
+        assert_eq!(ocr(&lines[..5]), None);
+        let cropped = lines.iter().map(|l| &l[..4]).collect::<Vec<_>>();
+        assert_eq!(ocr(&cropped).as_deref(), Some("E"));
+        let smudged = CRT.replacen('.', "#", 1);
+        assert_eq!(Answer::from(smudged.as_str()).submission(), smudged);
+        for (letter, glyph) in GLYPHS {
+            let rows = glyph.as_bytes().chunks(GLYPH_WIDTH);
+            let lines = rows.map(|r| core::str::from_utf8(r).unwrap());
+            let lines = lines.collect::<Vec<_>>();
+            assert_eq!(ocr(&lines), Some(letter.to_string()));
+        }
+    }
+}
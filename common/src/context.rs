@@ -0,0 +1,131 @@
+//! Shared between a running solver and whoever runs it: the runner asks
+//! for cancellation, the solver reports how far along it is. Solvers check
+//! it between units of work, so it only works for the ones that do.
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use core::fmt;
+use core::ptr;
+use core::sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering};
+
+/// Returned by a solver that stopped because its context was cancelled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("cancelled")
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Context {
+    cancelled: AtomicBool,
+    /// `usize` rather than `u64`: 32-bit targets have no 64-bit atomics.
+    done: AtomicUsize,
+    /// Zero until the solver says how much work there is.
+    total: AtomicUsize,
+    /// The message of a panic that could not unwind into the runner.
+    panic: AtomicPtr<String>,
+}
+
+impl Context {
+    pub const fn new() -> Self {
+        Self {
+            cancelled: AtomicBool::new(false),
+            done: AtomicUsize::new(0),
+            total: AtomicUsize::new(0),
+            panic: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// `Err` once cancelled, for `?` in a solver's main loop.
+    pub fn check(&self) -> Result<(), Cancelled> {
+        match self.is_cancelled() {
+            true => Err(Cancelled),
+            false => Ok(()),
+        }
+    }
+
+    /// How many units of work the solver has, rounds or rows for example.
+    pub fn set_total(&self, total: u64) {
+        self.total.store(saturate(total), Ordering::Relaxed);
+    }
+
+    /// How many of them are done.
+    pub fn set_done(&self, done: u64) {
+        self.done.store(saturate(done), Ordering::Relaxed);
+    }
+
+    /// Work done out of the total, if the solver reports any.
+    pub fn progress(&self) -> Option<(u64, u64)> {
+        let total = self.total.load(Ordering::Relaxed) as u64;
+        let done = self.done.load(Ordering::Relaxed) as u64;
+        (total > 0).then_some((done.min(total), total))
+    }
+
+    /// Hands a panic over to the runner, for a solver of a plugin: it
+    /// cannot unwind out of the library, see [`crate::plugin`].
+    pub fn set_panic(&self, message: String) {
+        let message = Box::into_raw(Box::new(message));
+        let previous = self.panic.swap(message, Ordering::AcqRel);
+        if !previous.is_null() {
+            drop(unsafe { Box::from_raw(previous) });
+        }
+    }
+
+    /// The panic handed over with [`Context::set_panic`], if any.
+    pub fn take_panic(&self) -> Option<String> {
+        let message = self.panic.swap(ptr::null_mut(), Ordering::AcqRel);
+        (!message.is_null()).then(|| *unsafe { Box::from_raw(message) })
+    }
+}
+
+fn saturate(count: u64) -> usize {
+    usize::try_from(count).unwrap_or(usize::MAX)
+}
+
+impl Drop for Context {
+    fn drop(&mut self) {
+        self.take_panic();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context() {
+        let context = Context::new();
+        assert_eq!((context.progress(), context.check()), (None, Ok(())));
+        context.set_done(3);
+        assert_eq!(context.progress(), None);
+        context.set_total(10);
+        assert_eq!(context.progress(), Some((3, 10)));
+        context.set_done(12);
+        assert_eq!(context.progress(), Some((10, 10)));
+        context.cancel();
+        assert!(context.is_cancelled());
+        assert_eq!(context.check(), Err(Cancelled));
+    }
+
+    #[test]
+    fn test_panic_is_taken_once() {
+        let context = Context::new();
+        assert_eq!(context.take_panic(), None);
+        context.set_panic("first".into());
+        context.set_panic("second".into());
+        assert_eq!(context.take_panic().as_deref(), Some("second"));
+        assert_eq!(context.take_panic(), None);
+        context.set_panic("dropped with the context".into());
+    }
+}
@@ -0,0 +1,304 @@
+//! Personal inputs encrypted at rest, so they can be committed: a day's
+//! `input.TXT.enc` is read in place of a missing `input.TXT`. The key is a
+//! random key file, `.aoc-key` at the root of the workspace or wherever
+//! `AOC_KEY_FILE` points, or a passphrase in `AOC_PASSPHRASE`.
+//!
+//! An encrypted input is `AOCENC1`, `k` or `p` for how it was keyed, a salt
+//! for the passphrase and a nonce, then the input sealed with
+//! ChaCha20-Poly1305 with all of that as associated data.
+
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use std::fmt::{self, Write as _};
+use std::io::{ErrorKind, Write as _};
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+pub use crate::input::{ENCRYPTED_INPUT, INPUT};
+pub const KEY_FILE: &str = ".aoc-key";
+pub const KEY_FILE_VAR: &str = "AOC_KEY_FILE";
+pub const PASSPHRASE_VAR: &str = "AOC_PASSPHRASE";
+
+const MAGIC: &[u8] = b"AOCENC1";
+const KEY_LEN: usize = 32;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const HEADER_LEN: usize = MAGIC.len() + 1 + SALT_LEN + NONCE_LEN;
+
+/// What the inputs are encrypted with.
+#[derive(Clone, PartialEq, Eq)]
+pub enum Secret {
+    /// The bytes of a key file, used as they are.
+    Key([u8; KEY_LEN]),
+    /// Stretched into a key with Argon2id and the salt of each file.
+    Passphrase(String),
+}
+
+/// Never shows the secret itself.
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Secret::Key(_) => f.write_str("Key(..)"),
+            Secret::Passphrase(_) => f.write_str("Passphrase(..)"),
+        }
+    }
+}
+
+impl Secret {
+    /// The passphrase in `AOC_PASSPHRASE` if it is set, else the key file.
+    /// `None` if there is neither.
+    pub fn find(root: &Path) -> Result<Option<Self>, String> {
+        if let Some(passphrase) = env::var(PASSPHRASE_VAR)
+            .ok()
+            .filter(|passphrase| !passphrase.is_empty())
+        {
+            return Ok(Some(Secret::Passphrase(passphrase)));
+        }
+        let path = key_path(root);
+        let error = |err: String| format!("{}: {}", path.display(), err);
+        match fs::read_to_string(&path) {
+            Ok(text) => Self::parse_key(&text).map(Some).map_err(error),
+            // A key file given explicitly has to be there.
+            Err(err)
+                if err.kind() == ErrorKind::NotFound
+                    && env::var_os(KEY_FILE_VAR).is_none() =>
+            {
+                Ok(None)
+            }
+            Err(err) => Err(error(err.to_string())),
+        }
+    }
+
+    /// A key file's contents: the key as hexadecimal digits.
+    pub fn parse_key(text: &str) -> Result<Self, String> {
+        let digits = text.trim().as_bytes();
+        let invalid = || format!("expected {} hexadecimal digits", KEY_LEN * 2);
+        if digits.len() != KEY_LEN * 2 {
+            return Err(invalid());
+        }
+        let mut key = [0; KEY_LEN];
+        for (byte, pair) in key.iter_mut().zip(digits.chunks(2)) {
+            let pair = std::str::from_utf8(pair).map_err(|_| invalid())?;
+            *byte = u8::from_str_radix(pair, 16).map_err(|_| invalid())?;
+        }
+        Ok(Secret::Key(key))
+    }
+
+    /// Writes a new random key to `path`, which must not exist yet, readable
+    /// by its owner only.
+    pub fn generate(path: &Path) -> Result<Self, String> {
+        let mut key = [0; KEY_LEN];
+        random(&mut key)?;
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut digits = String::new();
+        for byte in key {
+            let _ = write!(digits, "{:02x}", byte);
+        }
+        options
+            .open(path)
+            .and_then(|mut file| writeln!(file, "{}", digits))
+            .map_err(|err| format!("{}: {}", path.display(), err))?;
+        Ok(Secret::Key(key))
+    }
+
+    fn tag(&self) -> u8 {
+        match self {
+            Secret::Key(_) => b'k',
+            Secret::Passphrase(_) => b'p',
+        }
+    }
+
+    fn cipher(&self, salt: &[u8]) -> Result<ChaCha20Poly1305, String> {
+        let key = match self {
+            Secret::Key(key) => *key,
+            Secret::Passphrase(passphrase) => {
+                let mut key = [0; KEY_LEN];
+                Argon2::default()
+                    .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+                    .map_err(|err| err.to_string())?;
+                key
+            }
+        };
+        Ok(ChaCha20Poly1305::new(&key.into()))
+    }
+}
+
+/// Where the key file is: `AOC_KEY_FILE`, else `.aoc-key` in `root`.
+pub fn key_path(root: &Path) -> PathBuf {
+    env::var_os(KEY_FILE_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| root.join(KEY_FILE))
+}
+
+pub fn encrypt(plain: &[u8], secret: &Secret) -> Result<Vec<u8>, String> {
+    let mut data = Vec::with_capacity(HEADER_LEN + plain.len() + 16);
+    data.extend_from_slice(MAGIC);
+    data.push(secret.tag());
+    let mut salt_and_nonce = [0; SALT_LEN + NONCE_LEN];
+    random(&mut salt_and_nonce)?;
+    data.extend_from_slice(&salt_and_nonce);
+    let (salt, nonce) = salt_and_nonce.split_at(SALT_LEN);
+    let sealed = secret
+        .cipher(salt)?
+        .encrypt(
+            Nonce::from_slice(nonce),
+            Payload {
+                msg: plain,
+                aad: &data,
+            },
+        )
+        .map_err(|_| "could not encrypt".to_string())?;
+    data.extend(sealed);
+    Ok(data)
+}
+
+pub fn decrypt(data: &[u8], secret: &Secret) -> Result<Vec<u8>, String> {
+    if data.len() < HEADER_LEN || !data.starts_with(MAGIC) {
+        return Err("not an encrypted input".to_string());
+    }
+    let (header, sealed) = data.split_at(HEADER_LEN);
+    match (header[MAGIC.len()], secret) {
+        (b'p', Secret::Key(_)) => {
+            return Err(format!(
+                "encrypted with a passphrase, set {}",
+                PASSPHRASE_VAR
+            ))
+        }
+        (b'k', Secret::Passphrase(_)) => {
+            return Err(format!(
+                "encrypted with a key file, unset {} to use it",
+                PASSPHRASE_VAR
+            ))
+        }
+        (b'k' | b'p', _) => {}
+        _ => return Err("not an encrypted input".to_string()),
+    }
+    let (salt, nonce) = header[MAGIC.len() + 1..].split_at(SALT_LEN);
+    secret
+        .cipher(salt)?
+        .decrypt(
+            Nonce::from_slice(nonce),
+            Payload {
+                msg: sealed,
+                aad: header,
+            },
+        )
+        .map_err(|_| "wrong key, or the file is damaged".to_string())
+}
+
+/// A day's `input.TXT.enc` decrypted, `None` if it has none. The key file
+/// is looked for in the directory above, the root of the workspace.
+pub fn read_encrypted(dir: &Path) -> Result<Option<String>, String> {
+    let path = dir.join(ENCRYPTED_INPUT);
+    let error = |err: String| format!("{}: {}", path.display(), err);
+    let data = match fs::read(&path) {
+        Ok(data) => data,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(error(err.to_string())),
+    };
+    let root = dir.parent().unwrap_or(dir);
+    let secret = Secret::find(root)?.ok_or_else(|| error(missing_key(root)))?;
+    let input = decrypt(&data, &secret).map_err(error)?;
+    String::from_utf8(input)
+        .map(Some)
+        .map_err(|_| error("not UTF-8".to_string()))
+}
+
+/// What to do when [`Secret::find`] finds nothing.
+pub fn missing_key(root: &Path) -> String {
+    format!(
+        "no key to decrypt with, put one in {}, point {} at it or set {}",
+        key_path(root).display(),
+        KEY_FILE_VAR,
+        PASSPHRASE_VAR
+    )
+}
+
+fn random(bytes: &mut [u8]) -> Result<(), String> {
+    getrandom::getrandom(bytes).map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::read_personal;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!(
+            "aoc-crypt-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("1")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let key = Secret::Key([7; KEY_LEN]);
+        let passphrase = Secret::Passphrase("correct horse".to_string());
+        for secret in [&key, &passphrase] {
+            let sealed = encrypt(b"1000\n2000\n", secret).unwrap();
+            assert_ne!(sealed, encrypt(b"1000\n2000\n", secret).unwrap());
+            assert_eq!(decrypt(&sealed, secret).unwrap(), b"1000\n2000\n");
+        }
+
+        let sealed = encrypt(b"1000\n", &key).unwrap();
+        let other = Secret::Key([8; KEY_LEN]);
+        assert_eq!(
+            decrypt(&sealed, &other),
+            Err("wrong key, or the file is damaged".to_string())
+        );
+        let mut damaged = sealed.clone();
+        *damaged.last_mut().unwrap() ^= 1;
+        assert!(decrypt(&damaged, &key).is_err());
+        let mut retagged = sealed.clone();
+        retagged[MAGIC.len()] = b'p';
+        assert!(decrypt(&retagged, &passphrase).is_err());
+        assert_eq!(
+            decrypt(&sealed, &passphrase),
+            Err("encrypted with a key file, unset AOC_PASSPHRASE to use it"
+                .to_string())
+        );
+        assert!(decrypt(b"1000\n", &key).is_err());
+    }
+
+    #[test]
+    fn test_key_file() {
+        let root = temp_dir("key");
+        let path = root.join(KEY_FILE);
+        let key = Secret::generate(&path).unwrap();
+        assert!(Secret::generate(&path).is_err());
+        assert_eq!(
+            Secret::parse_key(&fs::read_to_string(&path).unwrap()),
+            Ok(key)
+        );
+        assert!(Secret::parse_key("abc").is_err());
+        assert!(Secret::parse_key(&"zz".repeat(KEY_LEN)).is_err());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_read_input() {
+        let root = temp_dir("read");
+        let dir = root.join("1");
+        assert_eq!(read_personal(&dir), Ok(None));
+
+        let key = Secret::Key([1; KEY_LEN]);
+        let sealed = encrypt(b"24000\n", &key).unwrap();
+        fs::write(dir.join(ENCRYPTED_INPUT), sealed).unwrap();
+        let missing = read_personal(&dir).unwrap_err();
+        assert!(missing.contains("no key to decrypt with"), "{}", missing);
+
+        fs::write(root.join(KEY_FILE), "01".repeat(KEY_LEN)).unwrap();
+        assert_eq!(read_personal(&dir), Ok(Some("24000\n".to_string())));
+        fs::write(dir.join(INPUT), "45000\n").unwrap();
+        assert_eq!(read_personal(&dir), Ok(Some("45000\n".to_string())));
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,264 @@
+use core::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A 2D integer vector, `y` growing downwards like in the puzzle maps.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vec2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// A position, as an offset from the origin.
+pub type Point = Vec2;
+
+/// A 3D integer vector.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vec3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+pub type Point3 = Vec3;
+
+macro_rules! vector {
+    ($name:ident { $($field:ident),+ }) => {
+        impl $name {
+            pub const ZERO: Self = Self { $($field: 0),+ };
+
+            pub const fn new($($field: i64),+) -> Self {
+                Self { $($field),+ }
+            }
+
+            /// Taxicab distance.
+            pub fn manhattan(self, other: Self) -> i64 {
+                0 $(+ (self.$field - other.$field).abs())+
+            }
+
+            /// Chessboard distance, the number of king moves.
+            pub fn chebyshev(self, other: Self) -> i64 {
+                0 $(.max((self.$field - other.$field).abs()))+
+            }
+
+            /// Each component reduced to -1, 0 or 1.
+            pub fn signum(self) -> Self {
+                Self { $($field: self.$field.signum()),+ }
+            }
+
+            /// One step towards `target`, moving along every axis where they
+            /// differ, diagonally if need be.
+            pub fn step_toward(self, target: Self) -> Self {
+                self + (target - self).signum()
+            }
+
+            /// Whether `other` is this point or touches it, diagonals
+            /// included.
+            pub fn touches(self, other: Self) -> bool {
+                self.chebyshev(other) <= 1
+            }
+        }
+
+        impl Add for $name {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self { $($field: self.$field + rhs.$field),+ }
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self { $($field: self.$field - rhs.$field),+ }
+            }
+        }
+
+        impl Mul<i64> for $name {
+            type Output = Self;
+
+            fn mul(self, rhs: i64) -> Self {
+                Self { $($field: self.$field * rhs),+ }
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($field: -self.$field),+ }
+            }
+        }
+
+        impl AddAssign for $name {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+    };
+}
+
+vector!(Vec2 { x, y });
+vector!(Vec3 { x, y, z });
+
+impl From<(i64, i64)> for Vec2 {
+    fn from((x, y): (i64, i64)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl From<(i64, i64, i64)> for Vec3 {
+    fn from((x, y, z): (i64, i64, i64)) -> Self {
+        Self { x, y, z }
+    }
+}
+
+/// The four orthogonal directions, clockwise from up.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// A step in this direction.
+    pub fn delta(self) -> Vec2 {
+        match self {
+            Direction::Up => Vec2::new(0, -1),
+            Direction::Right => Vec2::new(1, 0),
+            Direction::Down => Vec2::new(0, 1),
+            Direction::Left => Vec2::new(-1, 0),
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+}
+
+/// The smallest axis-aligned rectangle holding some points, bounds
+/// included.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct BoundingBox {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl BoundingBox {
+    pub fn new(point: Point) -> Self {
+        Self {
+            min: point,
+            max: point,
+        }
+    }
+
+    /// The box around `points`, if there are any.
+    pub fn from_points(
+        points: impl IntoIterator<Item = Point>,
+    ) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = Self::new(points.next()?);
+        Some(points.fold(first, Self::including))
+    }
+
+    /// This box grown to hold `point`.
+    pub fn including(self, point: Point) -> Self {
+        Self {
+            min: Point::new(self.min.x.min(point.x), self.min.y.min(point.y)),
+            max: Point::new(self.max.x.max(point.x), self.max.y.max(point.y)),
+        }
+    }
+
+    /// This box grown by `margin` on every side.
+    pub fn expand(self, margin: i64) -> Self {
+        Self {
+            min: self.min - Vec2::new(margin, margin),
+            max: self.max + Vec2::new(margin, margin),
+        }
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x)
+            && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    pub fn width(&self) -> i64 {
+        self.max.x - self.min.x + 1
+    }
+
+    pub fn height(&self) -> i64 {
+        self.max.y - self.min.y + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distances_and_steps() {
+        let (a, b) = (Vec2::new(1, 2), Vec2::new(4, -2));
+        assert_eq!(a + b, Vec2::new(5, 0));
+        assert_eq!(a - b, Vec2::new(-3, 4));
+        assert_eq!(-a * 2, Vec2::new(-2, -4));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(a.step_toward(b), Vec2::new(2, 1));
+        assert_eq!(b.step_toward(b), b);
+        assert!(a.touches(Vec2::new(2, 3)) && !a.touches(Vec2::new(3, 2)));
+
+        let c = Vec3::new(1, -1, 5);
+        assert_eq!(c.manhattan(Vec3::ZERO), 7);
+        assert_eq!(c.chebyshev(Vec3::ZERO), 5);
+        assert_eq!(Vec3::ZERO.step_toward(c), Vec3::new(1, -1, 1));
+    }
+
+    #[test]
+    fn test_directions() {
+        let mut pos = Point::ZERO;
+        let mut dir = Direction::Up;
+        for _ in 0..4 {
+            pos += dir.delta();
+            dir = dir.turn_right();
+        }
+        assert_eq!((pos, dir), (Point::ZERO, Direction::Up));
+        assert_eq!(Direction::Left.turn_left(), Direction::Down);
+        assert_eq!(Direction::Right.opposite(), Direction::Left);
+        assert_eq!(Direction::Down.delta(), Vec2::new(0, 1));
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let points = [(3, 4), (-1, 9), (2, 0)].map(Point::from);
+        let bounds = BoundingBox::from_points(points).unwrap();
+        assert_eq!(bounds.min, Point::new(-1, 0));
+        assert_eq!(bounds.max, Point::new(3, 9));
+        assert_eq!((bounds.width(), bounds.height()), (5, 10));
+        assert!(points.iter().all(|&p| bounds.contains(p)));
+        assert!(!bounds.contains(Point::new(4, 4)));
+        assert!(bounds.expand(1).contains(Point::new(4, 4)));
+        assert_eq!(BoundingBox::from_points([]), None);
+    }
+}
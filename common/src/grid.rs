@@ -0,0 +1,311 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::fmt;
+use core::ops::{Index, IndexMut};
+
+/// A position in a grid, `(x, y)` with `y` growing downwards.
+pub type Pos = (usize, usize);
+
+/// Up, left, right and down.
+pub const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// The orthogonal directions followed by the diagonals.
+pub const ALL_DIRECTIONS: [(isize, isize); 8] = [
+    (0, -1),
+    (-1, 0),
+    (1, 0),
+    (0, 1),
+    (-1, -1),
+    (1, -1),
+    (-1, 1),
+    (1, 1),
+];
+
+/// A dense, row-major 2D grid.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    pub fn from_fn(
+        width: usize,
+        height: usize,
+        mut f: impl FnMut(Pos) -> T,
+    ) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut f)
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses a character map, one row per line. Every row must have the
+    /// same number of characters.
+    pub fn parse(
+        input: &str,
+        mut f: impl FnMut(char) -> T,
+    ) -> Result<Self, String> {
+        let mut cells = Vec::with_capacity(input.len());
+        let mut width = None;
+        let mut height = 0;
+        for (y, line) in input.lines().enumerate() {
+            let len = cells.len();
+            cells.extend(line.chars().map(&mut f));
+            let row = cells.len() - len;
+            if *width.get_or_insert(row) != row {
+                return Err(format!(
+                    "row {} has {} cells, expected {}",
+                    y + 1,
+                    row,
+                    width.unwrap_or_default()
+                ));
+            }
+            height += 1;
+        }
+        Ok(Self {
+            width: width.unwrap_or_default(),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (x, y): Pos) -> Option<&T> {
+        (x < self.width && y < self.height)
+            .then(|| &self.cells[y * self.width + x])
+    }
+
+    pub fn get_mut(&mut self, (x, y): Pos) -> Option<&mut T> {
+        (x < self.width && y < self.height)
+            .then(|| &mut self.cells[y * self.width + x])
+    }
+
+    /// The position `(dx, dy)` away from `pos`, if it is inside the grid.
+    pub fn step(&self, (x, y): Pos, (dx, dy): (isize, isize)) -> Option<Pos> {
+        let x = x.checked_add_signed(dx).filter(|&x| x < self.width)?;
+        let y = y.checked_add_signed(dy).filter(|&y| y < self.height)?;
+        Some((x, y))
+    }
+
+    /// Like [`Grid::step`], wrapping around the edges.
+    pub fn wrapping_step(&self, (x, y): Pos, (dx, dy): (isize, isize)) -> Pos {
+        let wrap = |v: usize, d: isize, len: usize| {
+            (v as isize + d).rem_euclid(len as isize) as usize
+        };
+        (wrap(x, dx, self.width), wrap(y, dy, self.height))
+    }
+
+    /// The 4-connected neighbors of `pos` inside the grid.
+    pub fn neighbors(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ORTHOGONAL.iter().filter_map(move |&d| self.step(pos, d))
+    }
+
+    /// The 8-connected neighbors of `pos` inside the grid.
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ALL_DIRECTIONS
+            .iter()
+            .filter_map(move |&d| self.step(pos, d))
+    }
+
+    /// The 4-connected neighbors of `pos` on a torus.
+    pub fn wrapping_neighbors(
+        &self,
+        pos: Pos,
+    ) -> impl Iterator<Item = Pos> + '_ {
+        ORTHOGONAL.iter().map(move |&d| self.wrapping_step(pos, d))
+    }
+
+    /// The 8-connected neighbors of `pos` on a torus.
+    pub fn wrapping_neighbors8(
+        &self,
+        pos: Pos,
+    ) -> impl Iterator<Item = Pos> + '_ {
+        ALL_DIRECTIONS
+            .iter()
+            .map(move |&d| self.wrapping_step(pos, d))
+    }
+
+    /// The positions from `pos` (excluded) towards `dir`, up to the edge.
+    pub fn ray(
+        &self,
+        pos: Pos,
+        dir: (isize, isize),
+    ) -> impl Iterator<Item = Pos> + '_ {
+        let mut pos = Some(pos);
+        core::iter::from_fn(move || {
+            pos = self.step(pos?, dir);
+            pos
+        })
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> {
+        self.cells.chunks_exact(self.width.max(1))
+    }
+
+    /// The cells of column `x`, top to bottom. Panics if `x` is outside the
+    /// grid, like [`Grid::row`] does for `y`.
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(
+            x < self.width,
+            "column {} out of a grid {} wide",
+            x,
+            self.width
+        );
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn position(&self, f: impl FnMut(&T) -> bool) -> Option<Pos> {
+        let i = self.cells.iter().position(f)?;
+        Some((i % self.width, i / self.width))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos).unwrap_or_else(|| {
+            panic!("{:?} outside a {}x{} grid", pos, self.width, self.height)
+        })
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos).unwrap_or_else(|| {
+            panic!("{:?} outside a {}x{} grid", pos, width, height)
+        })
+    }
+}
+
+/// One row per line, like the maps the grids are parsed from.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.rows()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    const MAP: &str = "ab.\n.cd\n";
+
+    #[test]
+    fn test_parse_and_display() {
+        let grid = Grid::parse(MAP, |c| c).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 1)], 'c');
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.position(|&c| c == 'd'), Some((2, 1)));
+        assert_eq!(grid.to_string(), MAP.trim_end());
+        assert_eq!(
+            Grid::parse("ab\nc\n", |c| c).unwrap_err(),
+            "row 2 has 1 cells, expected 2"
+        );
+        assert_eq!(Grid::parse("", |c| c).unwrap().width(), 0);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::new(3, 2, 0);
+        let around = |it: &mut dyn Iterator<Item = Pos>| it.collect::<Vec<_>>();
+        assert_eq!(around(&mut grid.neighbors((0, 0))), [(1, 0), (0, 1)]);
+        assert_eq!(
+            around(&mut grid.neighbors8((0, 0))),
+            [(1, 0), (0, 1), (1, 1)]
+        );
+        assert_eq!(
+            around(&mut grid.wrapping_neighbors((0, 0))),
+            [(0, 1), (2, 0), (1, 0), (0, 1)]
+        );
+        assert_eq!(grid.wrapping_neighbors8((2, 1)).count(), 8);
+    }
+
+    #[test]
+    fn test_rows_columns_and_rays() {
+        let grid = Grid::from_fn(3, 2, |(x, y)| x + 10 * y);
+        assert_eq!(grid.row(1), [10, 11, 12]);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), [2, 12]);
+        assert_eq!(grid.ray((0, 0), (1, 1)).collect::<Vec<_>>(), [(1, 1)]);
+        assert_eq!(
+            grid.ray((0, 1), (1, 0)).map(|p| grid[p]).sum::<usize>(),
+            23
+        );
+        assert_eq!(grid.ray((0, 1), (-1, 0)).count(), 0);
+        assert_eq!(grid.iter().nth(4), Some(((1, 1), &11)));
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 out of a grid 3 wide")]
+    fn test_column_out_of_grid() {
+        let grid = Grid::from_fn(3, 2, |(x, y)| x + 10 * y);
+        let _ = grid.column(3);
+    }
+}
@@ -0,0 +1,504 @@
+//! Raster images of grids, written as PPM, PGM or PNG, and animations of
+//! simulations written as GIF. The encoders are written out here to keep the
+//! crate free of dependencies: PNG data is stored uncompressed, GIF frames
+//! are LZW-compressed over a palette of at most 256 colors.
+
+use crate::grid::Grid;
+use crate::simulation::Simulation;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::fmt;
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+
+    /// Perceived brightness, for grayscale output.
+    pub fn luma(self) -> u8 {
+        let Rgb(r, g, b) = self;
+        ((299 * r as u32 + 587 * g as u32 + 114 * b as u32) / 1000) as u8
+    }
+
+    /// The color `num / den` of the way from `self` to `other`.
+    pub fn blend(self, other: Rgb, num: u32, den: u32) -> Rgb {
+        let mix = |a: u8, b: u8| {
+            ((a as u32 * (den - num) + b as u32 * num) / den.max(1)) as u8
+        };
+        Rgb(
+            mix(self.0, other.0),
+            mix(self.1, other.1),
+            mix(self.2, other.2),
+        )
+    }
+}
+
+/// `#rrggbb`, as in CSS and SVG.
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// An RGB image, row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, fill: Rgb) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![fill; width * height],
+        }
+    }
+
+    /// One pixel per cell, colored by `palette`.
+    pub fn from_grid<T>(
+        grid: &Grid<T>,
+        palette: impl FnMut(&T) -> Rgb,
+    ) -> Self {
+        Self {
+            width: grid.width(),
+            height: grid.height(),
+            pixels: grid.iter().map(|(_, cell)| cell).map(palette).collect(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<Rgb> {
+        (x < self.width && y < self.height)
+            .then(|| self.pixels[y * self.width + x])
+    }
+
+    /// Colors a pixel, ignoring positions outside the image.
+    pub fn set(&mut self, x: usize, y: usize, color: Rgb) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = color;
+        }
+    }
+
+    /// Every pixel blown up to a `factor` by `factor` square.
+    pub fn scaled(&self, factor: usize) -> Self {
+        let (width, height) = (self.width * factor, self.height * factor);
+        let pixels = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| self.pixels[y / factor * self.width + x / factor])
+            .collect();
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Binary PPM (`P6`).
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut out =
+            format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        out.extend(self.pixels.iter().flat_map(|&Rgb(r, g, b)| [r, g, b]));
+        out
+    }
+
+    /// Binary PGM (`P5`), in shades of gray.
+    pub fn to_pgm(&self) -> Vec<u8> {
+        let mut out =
+            format!("P5\n{} {}\n255\n", self.width, self.height).into_bytes();
+        out.extend(self.pixels.iter().map(|p| p.luma()));
+        out
+    }
+
+    /// 8-bit RGB PNG.
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut raw = Vec::with_capacity((self.width * 3 + 1) * self.height);
+        for row in self.pixels.chunks_exact(self.width.max(1)) {
+            raw.push(0); // No filter.
+            raw.extend(row.iter().flat_map(|&Rgb(r, g, b)| [r, g, b]));
+        }
+        let mut header = Vec::with_capacity(13);
+        header.extend((self.width as u32).to_be_bytes());
+        header.extend((self.height as u32).to_be_bytes());
+        // Bit depth 8, truecolor, deflate, no filter, no interlace.
+        header.extend([8, 2, 0, 0, 0]);
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        png_chunk(&mut png, b"IHDR", &header);
+        png_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+        png_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+}
+
+fn png_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let crc = bytes.iter().fold(!0u32, |crc, &byte| {
+        (0..8).fold(crc ^ byte as u32, |crc, _| {
+            (crc >> 1) ^ (0xEDB8_8320 & (crc & 1).wrapping_neg())
+        })
+    });
+    !crc
+}
+
+/// A zlib stream of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(u16::MAX as usize).peekable();
+    if blocks.peek().is_none() {
+        out.extend([1, 0, 0, 0xFF, 0xFF]);
+    }
+    while let Some(block) = blocks.next() {
+        out.push(u8::from(blocks.peek().is_none()));
+        let len = block.len() as u16;
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend(block);
+    }
+    let (a, b) = data.iter().fold((1u32, 0u32), |(a, b), &byte| {
+        let a = (a + byte as u32) % 65521;
+        (a, (b + a) % 65521)
+    });
+    out.extend(((b << 16) | a).to_be_bytes());
+    out
+}
+
+/// Frames of the same size, shown `delay` hundredths of a second each.
+#[derive(Debug, Clone)]
+pub struct Animation {
+    frames: Vec<Image>,
+    delay: u16,
+}
+
+impl Animation {
+    pub fn new(delay: u16) -> Self {
+        Self {
+            frames: Vec::new(),
+            delay,
+        }
+    }
+
+    /// Renders the simulation every `every` steps, from its current state
+    /// to its end, the final state included.
+    pub fn record<S: Simulation>(
+        sim: &mut S,
+        every: usize,
+        delay: u16,
+        mut render: impl FnMut(&S) -> Image,
+    ) -> Result<Self, String> {
+        let mut animation = Self::new(delay);
+        let mut since = 0;
+        animation.push(render(sim))?;
+        while sim.step() {
+            since += 1;
+            if since == every.max(1) {
+                animation.push(render(sim))?;
+                since = 0;
+            }
+        }
+        if since > 0 {
+            animation.push(render(sim))?;
+        }
+        Ok(animation)
+    }
+
+    pub fn push(&mut self, frame: Image) -> Result<(), String> {
+        if let Some(first) = self.frames.first() {
+            if (first.width, first.height) != (frame.width, frame.height) {
+                return Err(format!(
+                    "frame is {}x{}, the animation {}x{}",
+                    frame.width, frame.height, first.width, first.height
+                ));
+            }
+        }
+        self.frames.push(frame);
+        Ok(())
+    }
+
+    pub fn frames(&self) -> &[Image] {
+        &self.frames
+    }
+
+    /// Every frame scaled, see [`Image::scaled`].
+    pub fn scaled(&self, factor: usize) -> Self {
+        Self {
+            frames: self.frames.iter().map(|f| f.scaled(factor)).collect(),
+            delay: self.delay,
+        }
+    }
+
+    /// A looping GIF. Fails if the frames use more than 256 colors.
+    pub fn to_gif(&self) -> Result<Vec<u8>, String> {
+        let first = self.frames.first().ok_or("no frames to animate")?;
+        let (width, height) = (first.width as u16, first.height as u16);
+        let mut palette = BTreeMap::new();
+        for frame in &self.frames {
+            for &pixel in &frame.pixels {
+                let next = palette.len();
+                palette.entry(pixel).or_insert(next);
+            }
+        }
+        if palette.len() > 256 {
+            return Err(format!("{} colors, GIF allows 256", palette.len()));
+        }
+        let bits = (1..=8).find(|&b| palette.len() <= 1 << b).unwrap();
+        let mut colors = vec![Rgb::BLACK; 1 << bits];
+        palette.iter().for_each(|(&color, &i)| colors[i] = color);
+
+        let mut gif = b"GIF89a".to_vec();
+        gif.extend(width.to_le_bytes());
+        gif.extend(height.to_le_bytes());
+        gif.extend([0x80 | (bits - 1) << 4 | (bits - 1), 0, 0]);
+        gif.extend(colors.iter().flat_map(|&Rgb(r, g, b)| [r, g, b]));
+        // Loop forever.
+        gif.extend(b"\x21\xFF\x0BNETSCAPE2.0\x03\x01\x00\x00\x00");
+        let min_code_size = bits.max(2);
+        for frame in &self.frames {
+            gif.extend([0x21, 0xF9, 0x04, 0x04]);
+            gif.extend(self.delay.to_le_bytes());
+            gif.extend([0, 0, 0x2C, 0, 0, 0, 0]);
+            gif.extend(width.to_le_bytes());
+            gif.extend(height.to_le_bytes());
+            gif.extend([0, min_code_size]);
+            let indices = frame
+                .pixels
+                .iter()
+                .map(|p| palette[p] as u8)
+                .collect::<Vec<_>>();
+            for block in lzw(min_code_size, &indices).chunks(255) {
+                gif.push(block.len() as u8);
+                gif.extend(block);
+            }
+            gif.push(0);
+        }
+        gif.push(0x3B);
+        Ok(gif)
+    }
+}
+
+/// GIF's variable-width LZW, codes packed least significant bit first.
+fn lzw(min_code_size: u8, indices: &[u8]) -> Vec<u8> {
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+    let mut out = Vec::new();
+    let (mut bits, mut pending) = (0u32, 0u32);
+    let mut emit = |code: u16, size: u8| {
+        pending |= (code as u32) << bits;
+        bits += size as u32;
+        while bits >= 8 {
+            out.push(pending as u8);
+            pending >>= 8;
+            bits -= 8;
+        }
+    };
+
+    let mut table = BTreeMap::new();
+    let mut next = end + 1;
+    let mut size = min_code_size + 1;
+    emit(clear, size);
+    let mut prefix = None;
+    for &index in indices {
+        let Some(code) = prefix else {
+            prefix = Some(index as u16);
+            continue;
+        };
+        if let Some(&longer) = table.get(&(code, index)) {
+            prefix = Some(longer);
+            continue;
+        }
+        emit(code, size);
+        if next == 4096 {
+            emit(clear, size);
+            table.clear();
+            next = end + 1;
+            size = min_code_size + 1;
+        } else {
+            table.insert((code, index), next);
+            // The decoder adds this code only after reading the next one.
+            if next == 1 << size {
+                size += 1;
+            }
+            next += 1;
+        }
+        prefix = Some(index as u16);
+    }
+    if let Some(code) = prefix {
+        emit(code, size);
+    }
+    emit(end, size);
+    if bits > 0 {
+        out.push(pending as u8);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Decodes the codes of [`lzw`] back into indices.
+    fn unlzw(min_code_size: u8, data: &[u8]) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let mut size = min_code_size + 1;
+        let (mut bits, mut pending, mut bytes) = (0, 0u32, data.iter());
+        let mut out = Vec::new();
+        let mut prev: Option<Vec<u8>> = None;
+        loop {
+            while bits < size {
+                pending |= (*bytes.next().unwrap() as u32) << bits;
+                bits += 8;
+            }
+            let code = (pending & ((1 << size) - 1)) as usize;
+            pending >>= size;
+            bits -= size;
+            if code == clear {
+                table = (0..clear).map(|i| vec![i as u8]).collect();
+                table.extend([vec![], vec![]]);
+                size = min_code_size + 1;
+                prev = None;
+                continue;
+            }
+            if code == clear + 1 {
+                return out;
+            }
+            let entry = match (table.get(code), &prev) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(prev)) => [&prev[..], &prev[..1]].concat(),
+                (None, None) => panic!("invalid code {}", code),
+            };
+            if let Some(prev) = prev {
+                table.push([&prev[..], &entry[..1]].concat());
+                if table.len() == 1 << size && size < 12 {
+                    size += 1;
+                }
+            }
+            out.extend(&entry);
+            prev = Some(entry);
+        }
+    }
+
+    #[test]
+    fn test_lzw_round_trip() {
+        let mut rng = crate::rng::Rng::new(7);
+        let noisy = (0..20_000)
+            .map(|_| rng.next_u64() as u8 % 5)
+            .collect::<Vec<_>>();
+        let runs = (0..20_000).map(|i| (i / 300 % 4) as u8).collect::<Vec<_>>();
+        for indices in [&noisy[..], &runs, &[3], &[]] {
+            assert_eq!(unlzw(3, &lzw(3, indices)), indices);
+        }
+        let bytes =
+            (0..50_000).map(|i| (i * 7 % 251) as u8).collect::<Vec<_>>();
+        assert_eq!(unlzw(8, &lzw(8, &bytes)), bytes);
+    }
+
+    #[test]
+    fn test_netpbm() {
+        let mut image = Image::new(2, 1, Rgb::WHITE);
+        image.set(1, 0, Rgb(255, 0, 0));
+        image.set(5, 5, Rgb::BLACK);
+        assert_eq!(image.to_ppm(), b"P6\n2 1\n255\n\xFF\xFF\xFF\xFF\x00\x00");
+        assert_eq!(image.to_pgm(), b"P5\n2 1\n255\n\xFF\x4C");
+        let scaled = image.scaled(2);
+        assert_eq!((scaled.width(), scaled.height()), (4, 2));
+        assert_eq!(scaled.get(3, 1), Some(Rgb(255, 0, 0)));
+        assert_eq!(scaled.get(1, 1), Some(Rgb::WHITE));
+    }
+
+    #[test]
+    fn test_png() {
+        let grid = Grid::from_fn(3, 2, |(x, y)| x + y);
+        let image = Image::from_grid(&grid, |&v| {
+            Rgb::BLACK.blend(Rgb::WHITE, v as u32, 3)
+        });
+        assert_eq!(image.get(2, 1), Some(Rgb::WHITE));
+        let png = image.to_png();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[16..24], [0, 0, 0, 3, 0, 0, 0, 2]);
+        assert_eq!(&png[png.len() - 12..], b"\0\0\0\0IEND\xAE\x42\x60\x82");
+        // The scanlines, stored after the zlib and block headers.
+        let idat = &png[33 + 8..];
+        assert_eq!(idat[..7], [0x78, 0x01, 1, 20, 0, !20, !0]);
+        assert_eq!(idat[7..11], [0, 0, 0, 0]);
+        assert_eq!(zlib_stored(&[])[2..], [1, 0, 0, 0xFF, 0xFF, 0, 0, 0, 1]);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    /// Counts up to `end`, one step at a time.
+    struct Counter {
+        n: usize,
+        end: usize,
+    }
+
+    impl Simulation for Counter {
+        type Snapshot = usize;
+
+        fn step(&mut self) -> bool {
+            if self.n == self.end {
+                return false;
+            }
+            self.n += 1;
+            true
+        }
+
+        fn steps(&self) -> usize {
+            self.n
+        }
+
+        fn snapshot(&self) -> usize {
+            self.n
+        }
+
+        fn restore(&mut self, n: &usize) {
+            self.n = *n;
+        }
+    }
+
+    #[test]
+    fn test_gif() {
+        let mut counter = Counter { n: 0, end: 5 };
+        let render = |c: &Counter| {
+            let mut frame = Image::new(6, 1, Rgb::BLACK);
+            frame.set(c.n, 0, Rgb::WHITE);
+            frame
+        };
+        let animation = Animation::record(&mut counter, 2, 10, render).unwrap();
+        let lit = |f: &Image| (0..6).find(|&x| f.get(x, 0) == Some(Rgb::WHITE));
+        let lit = animation.frames().iter().map(lit).collect::<Vec<_>>();
+        assert_eq!(lit, [Some(0), Some(2), Some(4), Some(5)]);
+
+        let gif = animation.scaled(2).to_gif().unwrap();
+        assert_eq!(&gif[..13], b"GIF89a\x0C\x00\x02\x00\x80\x00\x00");
+        assert_eq!(gif.last(), Some(&0x3B));
+        let frames = gif.windows(2).filter(|w| w == b"\x21\xF9").count();
+        assert_eq!(frames, 4);
+
+        let mut mismatched = Animation::new(10);
+        mismatched.push(Image::new(1, 1, Rgb::BLACK)).unwrap();
+        assert!(mismatched.push(Image::new(2, 1, Rgb::BLACK)).is_err());
+        assert!(Animation::new(1).to_gif().is_err());
+    }
+}
@@ -0,0 +1,218 @@
+use crate::Day;
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::{format, vec, vec::Vec};
+#[cfg(feature = "std")]
+use std::io::ErrorKind;
+#[cfg(feature = "std")]
+use std::path::Path;
+#[cfg(feature = "std")]
+use std::{fs, sync::OnceLock};
+
+/// Puts a puzzle input in the form every parser expects: no UTF-8 BOM, LF
+/// line endings, no trailing whitespace on a line, no trailing blank lines
+/// and a single final newline. Leading whitespace is kept, day 5 aligns its
+/// stacks with it.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    if is_normalized(input) {
+        return Cow::Borrowed(input);
+    }
+    let mut normalized = String::with_capacity(input.len() + 1);
+    for line in input.split('\n') {
+        normalized.push_str(line.trim_end());
+        normalized.push('\n');
+    }
+    normalized.truncate(normalized.trim_end().len());
+    if !normalized.is_empty() {
+        normalized.push('\n');
+    }
+    Cow::Owned(normalized)
+}
+
+fn is_normalized(input: &str) -> bool {
+    input.is_empty()
+        || (input.ends_with('\n')
+            && !input.ends_with("\n\n")
+            && !input.contains('\r')
+            && input.lines().all(|line| line.trim_end() == line))
+}
+
+/// Feeds the lines of `reader` to `f` one at a time, normalized like
+/// [`normalize`], stopping at the first error `f` returns. Only the current
+/// line is kept in memory, blank lines are counted and replayed once a
+/// non-blank line follows them.
+#[cfg(feature = "std")]
+pub fn for_each_line(
+    mut reader: impl std::io::BufRead,
+    mut f: impl FnMut(&str) -> std::io::Result<()>,
+) -> std::io::Result<()> {
+    let mut line = String::new();
+    let mut blank = 0;
+    let mut first = true;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        let mut text = line.trim_end();
+        if first {
+            text = text.strip_prefix('\u{feff}').unwrap_or(text).trim_end();
+            first = false;
+        }
+        if text.is_empty() {
+            blank += 1;
+            continue;
+        }
+        for _ in 0..blank {
+            f("")?;
+        }
+        blank = 0;
+        f(text)?;
+    }
+}
+
+/// The ways a copy of `input` can come back from a browser, an editor or
+/// another OS, for testing that parsers only ever see the normalized form.
+pub fn variants(input: &str) -> Vec<String> {
+    let crlf = input.replace('\n', "\r\n");
+    vec![
+        format!("\u{feff}{}", crlf),
+        crlf.clone(),
+        input.trim_end().to_string(),
+        format!("{}\n\n\n", input),
+        format!("{}\r\n \r\n", crlf.trim_end()),
+        input.replace('\n', " \t\n"),
+    ]
+}
+
+/// Checks that every implementation of `day` gives the same answer on each
+/// of the [`variants`] of `input` as on `input` itself.
+pub fn assert_variants(day: &Day, input: &str) {
+    for (i, variant) in variants(input).iter().enumerate() {
+        for implementation in day.part_one.iter().chain(day.part_two) {
+            assert_eq!(
+                implementation.solve(variant),
+                implementation.solve(input),
+                "day {}, {} on variant {}",
+                day.day,
+                implementation.name,
+                i
+            );
+        }
+    }
+}
+
+/// A day's personal input, in its directory.
+pub const INPUT: &str = "input.TXT";
+/// A day's personal input encrypted, see `aoc encrypt`.
+pub const ENCRYPTED_INPUT: &str = "input.TXT.enc";
+
+/// A day's personal input from the day's directory: its `input.TXT`, else
+/// its `input.TXT.enc` decrypted, `None` if it has neither. Decrypting takes
+/// the `crypt` feature, without it an encrypted input is an error.
+#[cfg(feature = "std")]
+pub fn read_personal(dir: &Path) -> Result<Option<String>, String> {
+    let plain = dir.join(INPUT);
+    match fs::read_to_string(&plain) {
+        Ok(input) => return Ok(Some(input)),
+        Err(err) if err.kind() != ErrorKind::NotFound => {
+            return Err(format!("{}: {}", plain.display(), err))
+        }
+        Err(_) => {}
+    }
+    #[cfg(feature = "crypt")]
+    return crate::crypt::read_encrypted(dir);
+    #[cfg(not(feature = "crypt"))]
+    match dir.join(ENCRYPTED_INPUT) {
+        path if path.exists() => Err(format!(
+            "{}: encrypted, decrypt it with `aoc decrypt` or solve with `aoc`",
+            path.display()
+        )),
+        _ => Ok(None),
+    }
+}
+
+/// The input of a day's `solve()`, tests and benches, read when first used:
+/// the sample in debug builds, the personal input in release builds.
+#[cfg(feature = "std")]
+pub struct PuzzleInput {
+    source: Source,
+    personal: OnceLock<Result<String, String>>,
+}
+
+#[cfg(feature = "std")]
+enum Source {
+    Sample(&'static str),
+    Personal(&'static str),
+}
+
+#[cfg(feature = "std")]
+impl PuzzleInput {
+    pub const fn sample(sample: &'static str) -> Self {
+        Self {
+            source: Source::Sample(sample),
+            personal: OnceLock::new(),
+        }
+    }
+
+    /// The personal input of the day in `dir`, see [`read_personal`].
+    pub const fn personal(dir: &'static str) -> Self {
+        Self {
+            source: Source::Personal(dir),
+            personal: OnceLock::new(),
+        }
+    }
+
+    /// The input, or why there is none.
+    pub fn get(&self) -> Result<&str, String> {
+        let dir = match self.source {
+            Source::Sample(sample) => return Ok(sample),
+            Source::Personal(dir) => dir,
+        };
+        let personal = self.personal.get_or_init(|| {
+            read_personal(Path::new(dir))?.ok_or_else(|| {
+                format!("no {} or {} in {}", INPUT, ENCRYPTED_INPUT, dir)
+            })
+        });
+        personal.as_deref().map_err(Clone::clone)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert!(matches!(normalize("a\n b\n"), Cow::Borrowed("a\n b\n")));
+        assert_eq!(normalize("\u{feff}a\r\n\r\n b \r\n\r\n"), "a\n\n b\n");
+        assert_eq!(normalize("a\n\t\nb"), "a\n\nb\n");
+        assert_eq!(normalize("\n\n"), "");
+        assert_eq!(normalize(""), "");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_for_each_line() {
+        let input = "    [D]\n[N] [C]\n\nmove 1 from 2 to 1\n";
+        for variant in variants(input) {
+            let mut lines = String::new();
+            for_each_line(variant.as_bytes(), |line| {
+                lines.push_str(line);
+                lines.push('\n');
+                Ok(())
+            })
+            .unwrap();
+            assert_eq!(lines, input);
+        }
+    }
+
+    #[test]
+    fn test_variants_normalize_back() {
+        let input = "    [D]\n[N] [C]\n\nmove 1 from 2 to 1\n";
+        for variant in variants(input) {
+            assert_eq!(normalize(&variant), input);
+        }
+    }
+}
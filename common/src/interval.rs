@@ -0,0 +1,284 @@
+//! Inclusive integer ranges and sets of them, kept as few sorted disjoint
+//! ranges so sets spanning millions of values stay small.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+/// The integer types intervals can be over.
+pub trait Integer: Copy + Ord + fmt::Debug {
+    fn succ(self) -> Self;
+    fn pred(self) -> Self;
+    /// The number of values in `start..=end`, saturating at `u64::MAX`.
+    fn count(start: Self, end: Self) -> u64;
+}
+
+macro_rules! integer {
+    ($($t:ty),*) => {$(
+        impl Integer for $t {
+            fn succ(self) -> Self {
+                self + 1
+            }
+
+            fn pred(self) -> Self {
+                self - 1
+            }
+
+            fn count(start: Self, end: Self) -> u64 {
+                (end as i128 - start as i128 + 1).min(u64::MAX as i128) as u64
+            }
+        }
+    )*};
+}
+
+integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// The values from `start` to `end`, both included.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+// Never empty, `new` rejects `start > end`.
+#[allow(clippy::len_without_is_empty)]
+impl<T: Integer> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        assert!(start <= end, "empty interval {:?}..={:?}", start, end);
+        Self { start, end }
+    }
+
+    pub fn point(value: T) -> Self {
+        Self::new(value, value)
+    }
+
+    pub fn len(&self) -> u64 {
+        T::count(self.start, self.end)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    /// Whether every value of `other` is in this interval.
+    pub fn contains_interval(&self, other: &Self) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        self.overlaps(other).then(|| {
+            Self::new(self.start.max(other.start), self.end.min(other.end))
+        })
+    }
+}
+
+/// A set of integers as sorted, disjoint and non-adjacent intervals.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for RangeSet<T> {
+    fn default() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+}
+
+impl<T: Integer> RangeSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        // Intervals overlapping or touching the new one are merged into it.
+        let first = self.intervals.partition_point(|i| {
+            i.end < interval.start && !touch(*i, interval)
+        });
+        let last = self.intervals.partition_point(|i| {
+            i.start <= interval.end || touch(interval, *i)
+        });
+        let merged =
+            self.intervals[first..last]
+                .iter()
+                .fold(interval, |merged, i| {
+                    Interval::new(
+                        merged.start.min(i.start),
+                        merged.end.max(i.end),
+                    )
+                });
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The number of values in the set.
+    pub fn len(&self) -> u64 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.intervals.partition_point(|i| i.end < value);
+        self.intervals.get(i).is_some_and(|i| i.contains(value))
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        other.intervals.iter().for_each(|&i| union.insert(i));
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intersection = Self::new();
+        let (mut a, mut b) = (self.intervals.iter(), other.intervals.iter());
+        let (mut x, mut y) = (a.next(), b.next());
+        while let (Some(i), Some(j)) = (x, y) {
+            if let Some(common) = i.intersection(j) {
+                intersection.intervals.push(common);
+            }
+            if i.end < j.end {
+                x = a.next();
+            } else {
+                y = b.next();
+            }
+        }
+        intersection
+    }
+
+    /// The values of this set that are not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = Self::new();
+        for &interval in &self.intervals {
+            let mut start = Some(interval.start);
+            for hole in &other.intervals {
+                let Some(from) = start else { break };
+                if hole.end < from || hole.start > interval.end {
+                    continue;
+                }
+                if hole.start > from {
+                    difference
+                        .intervals
+                        .push(Interval::new(from, hole.start.pred()));
+                }
+                start = (hole.end < interval.end).then(|| hole.end.succ());
+            }
+            if let Some(start) = start {
+                difference
+                    .intervals
+                    .push(Interval::new(start, interval.end));
+            }
+        }
+        difference
+    }
+
+    /// The intervals missing between the first and the last value.
+    pub fn gaps(&self) -> impl Iterator<Item = Interval<T>> + '_ {
+        self.intervals
+            .windows(2)
+            .map(|w| Interval::new(w[0].end.succ(), w[1].start.pred()))
+    }
+}
+
+/// Whether `b` starts right after `a` ends, `a` ending before `b` starts.
+fn touch<T: Integer>(a: Interval<T>, b: Interval<T>) -> bool {
+    b.start.pred() == a.end
+}
+
+impl<T: Integer> From<Interval<T>> for RangeSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        Self {
+            intervals: alloc::vec![interval],
+        }
+    }
+}
+
+impl<T: Integer> FromIterator<Interval<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut intervals = iter.into_iter().collect::<Vec<_>>();
+        intervals.sort_unstable();
+        let mut set = Self::new();
+        for interval in intervals {
+            match set.intervals.last_mut() {
+                Some(last)
+                    if interval.start <= last.end || touch(*last, interval) =>
+                {
+                    last.end = last.end.max(interval.end)
+                }
+                _ => set.intervals.push(interval),
+            }
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn set(intervals: &[(i32, i32)]) -> RangeSet<i32> {
+        intervals
+            .iter()
+            .map(|&(s, e)| Interval::new(s, e))
+            .collect()
+    }
+
+    #[test]
+    fn test_interval() {
+        let a = Interval::new(2, 8);
+        assert_eq!(a.len(), 7);
+        assert!(a.contains(8) && !a.contains(9));
+        assert!(a.contains_interval(&Interval::new(3, 8)));
+        assert!(!a.contains_interval(&Interval::new(3, 9)));
+        assert_eq!(
+            a.intersection(&Interval::new(6, 10)),
+            Some(Interval::new(6, 8))
+        );
+        assert_eq!(a.intersection(&Interval::point(9)), None);
+        assert_eq!(Interval::new(i64::MIN, i64::MAX).len(), u64::MAX);
+    }
+
+    #[test]
+    fn test_insert_merges() {
+        let mut s = set(&[(0, 2), (10, 12), (20, 22)]);
+        let mut full = set(&[(i8::MIN as i32, -1)]);
+        full.insert(Interval::new(0, 0));
+        assert_eq!(full.intervals().len(), 1);
+        let mut edge = RangeSet::from(Interval::point(u8::MAX));
+        edge.insert(Interval::new(0, u8::MAX - 1));
+        assert_eq!(edge.len(), 256);
+        assert_eq!(s, set(&[(20, 22), (0, 2), (10, 12)]));
+        s.insert(Interval::new(3, 9));
+        assert_eq!(s.intervals(), set(&[(0, 12), (20, 22)]).intervals());
+        s.insert(Interval::new(14, 15));
+        s.insert(Interval::new(-5, -5));
+        assert_eq!(s, set(&[(-5, -5), (0, 12), (14, 15), (20, 22)]));
+        s.insert(Interval::new(13, 25));
+        assert_eq!(s, set(&[(-5, -5), (0, 25)]));
+        assert_eq!(s.len(), 27);
+        assert!(s.contains(-5) && s.contains(25) && !s.contains(-1));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[(0, 10), (20, 30)]);
+        let b = set(&[(5, 25), (40, 40)]);
+        assert_eq!(a.union(&b), set(&[(0, 30), (40, 40)]));
+        assert_eq!(a.intersection(&b), set(&[(5, 10), (20, 25)]));
+        assert_eq!(a.difference(&b), set(&[(0, 4), (26, 30)]));
+        assert_eq!(b.difference(&a), set(&[(11, 19), (40, 40)]));
+        assert_eq!(a.difference(&a), RangeSet::new());
+        assert_eq!(a.gaps().collect::<Vec<_>>(), vec![Interval::new(11, 19)]);
+        assert!(RangeSet::<i32>::new().is_empty());
+    }
+}
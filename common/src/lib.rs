@@ -0,0 +1,177 @@
+//! Shared by every day. `no_std` unless the `std` feature is enabled, so the
+//! days can be built without `std`.
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
+#![cfg_attr(test, feature(test))]
+extern crate alloc;
+#[cfg(test)]
+extern crate test;
+
+pub mod answer;
+pub mod context;
+#[cfg(feature = "crypt")]
+pub mod crypt;
+pub mod geometry;
+pub mod grid;
+#[cfg(feature = "visualize")]
+pub mod image;
+pub mod input;
+pub mod interval;
+pub mod parse;
+#[cfg(feature = "std")]
+pub mod plugin;
+pub mod rng;
+pub mod search;
+pub mod simulation;
+#[cfg(feature = "visualize")]
+pub mod svg;
+
+use alloc::string::String;
+use answer::Answer;
+use context::{Cancelled, Context};
+
+/// Entry points a day exposes to the `aoc` runner. They all take the raw
+/// input and run it through [`input::normalize`] before parsing.
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+    pub day: u8,
+    pub sample: &'static str,
+    /// Implementations of each part. The first one is the reference the
+    /// others are checked against.
+    pub part_one: &'static [Implementation],
+    pub part_two: &'static [Implementation],
+    /// Builds a valid puzzle input from a seed and a day-specific size.
+    pub generate: fn(seed: u64, size: usize) -> String,
+    /// Pretty-prints the parsed model of an input.
+    pub inspect: fn(&str) -> String,
+    /// Day-specific commands for exploring an input in the REPL.
+    pub commands: &'static [Command],
+    /// The day's input played step by step, for the runner's tooling.
+    pub simulations: &'static [simulation::Simulator],
+}
+
+/// A named solver for one part, taking the raw puzzle input.
+#[derive(Debug, Clone, Copy)]
+pub struct Implementation {
+    pub name: &'static str,
+    /// Stops early if `context` is cancelled, for the solvers that check
+    /// it, and reports their progress to it.
+    pub run: fn(&str, &Context) -> Result<Answer, Cancelled>,
+}
+
+impl Implementation {
+    /// Runs under `context`. The panic of a plugin's solver, handed over
+    /// through `context`, is raised again here where it can be caught.
+    pub fn run_in(
+        &self,
+        input: &str,
+        context: &Context,
+    ) -> Result<Answer, Cancelled> {
+        let result = (self.run)(input, context);
+        match context.take_panic() {
+            Some(message) => panic!("{}", message),
+            None => result,
+        }
+    }
+
+    /// Runs to the end, nothing can cancel it.
+    pub fn solve(&self, input: &str) -> Answer {
+        self.run_in(input, &Context::new()).unwrap()
+    }
+}
+
+/// A REPL command run against the raw puzzle input.
+#[derive(Debug, Clone, Copy)]
+pub struct Command {
+    pub name: &'static str,
+    pub usage: &'static str,
+    pub help: &'static str,
+    pub run: fn(input: &str, args: &[&str]) -> Result<String, String>,
+}
+
+/// Bumped whenever `Day` or a type it refers to changes, so the runner
+/// refuses plugins built against another layout.
+pub const PLUGIN_ABI_VERSION: u32 = 5;
+
+/// Exported by a day built as a plugin under the `AOC_PLUGIN` symbol. The
+/// version comes first so it can be checked before the rest is touched.
+#[cfg(feature = "std")]
+#[repr(C)]
+pub struct Plugin {
+    pub abi_version: u32,
+    /// The day, its solvers guarded by [`plugin::Guarded`].
+    pub day: &'static Day,
+    /// To be called with the runner's allocator before anything else.
+    pub init: fn(plugin::Allocator),
+}
+
+/// Exports a day for the runner to load from its plugins directory when the
+/// crate is built with the `plugin` feature, which also makes the runner's
+/// allocator the crate's global one, see [`plugin`].
+///
+/// ```ignore
+/// common::export_plugin!(DAY);
+/// ```
+#[macro_export]
+macro_rules! export_plugin {
+    ($day:path) => {
+        #[cfg(feature = "plugin")]
+        #[no_mangle]
+        pub static AOC_PLUGIN: $crate::Plugin = {
+            struct Exported;
+            impl $crate::plugin::Exported for Exported {
+                const DAY: $crate::Day = $day;
+            }
+            $crate::Plugin {
+                abi_version: $crate::PLUGIN_ABI_VERSION,
+                day: &$crate::plugin::Guarded::<Exported>::DAY,
+                init: $crate::plugin::init,
+            }
+        };
+
+        #[cfg(feature = "plugin")]
+        #[global_allocator]
+        static PLUGIN_ALLOCATOR: $crate::plugin::RunnerAllocator =
+            $crate::plugin::RunnerAllocator;
+    };
+}
+
+/// Builds the implementation list of a part from a parser and solvers.
+/// Solvers listed after `with_context` also take a [`Context`] and return
+/// a `Result<_, Cancelled>`.
+///
+/// ```ignore
+/// part_one: implementations!(parse_input, solve_part_one),
+/// part_two: implementations!(parse_input;
+///     "scan" => solve_part_two,
+///     "sweep" => solve_part_two_sweep,
+/// ),
+/// part_two: implementations!(parse_input, with_context solve_part_two),
+/// ```
+#[macro_export]
+macro_rules! implementations {
+    ($parse:ident, with_context $solve:path) => {
+        $crate::implementations!($parse; with_context "default" => $solve)
+    };
+    ($parse:ident, $solve:path) => {
+        $crate::implementations!($parse; "default" => $solve)
+    };
+    ($parse:ident; with_context $($name:literal => $solve:path),+ $(,)?) => {
+        &[$($crate::Implementation {
+            name: $name,
+            run: |input, context| {
+                $solve(&$parse(&$crate::input::normalize(input)), context)
+                    .map($crate::answer::Answer::from)
+            },
+        }),+]
+    };
+    ($parse:ident; $($name:literal => $solve:path),+ $(,)?) => {
+        &[$($crate::Implementation {
+            name: $name,
+            run: |input, _| {
+                Ok($crate::answer::Answer::from($solve(&$parse(
+                    &$crate::input::normalize(input),
+                ))))
+            },
+        }),+]
+    };
+}
@@ -0,0 +1,285 @@
+//! Allocation-free helpers for pulling numbers and fields out of puzzle
+//! lines, instead of splitting into temporary `Vec`s.
+
+use alloc::format;
+use alloc::string::String;
+use core::marker::PhantomData;
+
+/// Integers that can be read digit by digit.
+pub trait FromDigits: Copy {
+    /// Whether a `-` right before the digits makes the number negative.
+    const SIGNED: bool;
+    const ZERO: Self;
+    /// `self * 10 ± digit`, `None` on overflow.
+    fn push_digit(self, digit: u8, negative: bool) -> Option<Self>;
+}
+
+macro_rules! from_digits {
+    ($signed:literal: $($t:ty),*) => {$(
+        impl FromDigits for $t {
+            const SIGNED: bool = $signed;
+            const ZERO: Self = 0;
+
+            fn push_digit(self, digit: u8, negative: bool) -> Option<Self> {
+                let shifted = self.checked_mul(10)?;
+                match negative {
+                    true => shifted.checked_sub(digit as Self),
+                    false => shifted.checked_add(digit as Self),
+                }
+            }
+        }
+    )*};
+}
+
+from_digits!(false: u8, u16, u32, u64, u128, usize);
+from_digits!(true: i8, i16, i32, i64, i128, isize);
+
+/// The integers of a line, in order. See [`numbers`].
+#[derive(Debug, Clone)]
+pub struct Numbers<'a, T> {
+    bytes: &'a [u8],
+    pos: usize,
+    number: PhantomData<T>,
+}
+
+impl<T: FromDigits> Numbers<'_, T> {
+    fn next_number(&mut self) -> Option<Result<T, String>> {
+        let start = self.pos
+            + self.bytes[self.pos..].iter().position(u8::is_ascii_digit)?;
+        let negative = T::SIGNED && start > 0 && self.bytes[start - 1] == b'-';
+        let mut value = T::ZERO;
+        self.pos = start;
+        while let Some(&b @ b'0'..=b'9') = self.bytes.get(self.pos) {
+            self.pos += 1;
+            match value.push_digit(b - b'0', negative) {
+                Some(next) => value = next,
+                None => {
+                    let digits = &self.bytes[start..self.pos];
+                    return Some(Err(format!(
+                        "{} overflows its type",
+                        String::from_utf8_lossy(digits)
+                    )));
+                }
+            }
+        }
+        Some(Ok(value))
+    }
+}
+
+impl<T: FromDigits> Iterator for Numbers<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.next_number().map(|number| number.unwrap())
+    }
+}
+
+/// Every run of digits in `line` as a `T`, skipping whatever separates
+/// them. For signed types a `-` directly before the digits is a sign, so
+/// read ranges like `2-4` as unsigned.
+///
+/// Panics if a number does not fit in `T`, see [`extract`] for errors.
+pub fn numbers<T: FromDigits>(line: &str) -> Numbers<'_, T> {
+    Numbers {
+        bytes: line.as_bytes(),
+        pos: 0,
+        number: PhantomData,
+    }
+}
+
+/// Exactly `N` integers from `line`, as read by [`numbers`].
+pub fn extract<T: FromDigits, const N: usize>(
+    line: &str,
+) -> Result<[T; N], String> {
+    let mut numbers = numbers(line);
+    let mut values = [T::ZERO; N];
+    for (i, value) in values.iter_mut().enumerate() {
+        *value = numbers.next_number().ok_or_else(|| {
+            format!("expected {} numbers, found {} in {:?}", N, i, line)
+        })??;
+    }
+    match numbers.next_number() {
+        None => Ok(values),
+        Some(_) => {
+            Err(format!("expected {} numbers, found more in {:?}", N, line))
+        }
+    }
+}
+
+/// Matches `line` against `pattern`, where each `{}` captures the text up
+/// to the next literal part of the pattern (or the end of the line).
+/// Returns the `N` captures, borrowed from `line`.
+///
+/// ```
+/// # use common::parse::fields;
+/// let [x, y] = fields("x={}, y={}", "x=-2, y=15").unwrap();
+/// assert_eq!((x, y), ("-2", "15"));
+/// ```
+pub fn fields<'a, const N: usize>(
+    pattern: &str,
+    line: &'a str,
+) -> Result<[&'a str; N], String> {
+    let mismatch = || format!("{:?} does not match {:?}", line, pattern);
+    let mut literals = pattern.split("{}");
+    let mut rest = line
+        .strip_prefix(literals.next().unwrap_or_default())
+        .ok_or_else(mismatch)?;
+    let mut captures = [""; N];
+    for capture in captures.iter_mut() {
+        let literal = literals.next().ok_or_else(|| {
+            format!("{:?} has fewer than {} captures", pattern, N)
+        })?;
+        let end = match literal {
+            "" => rest.len(),
+            literal => rest.find(literal).ok_or_else(mismatch)?,
+        };
+        *capture = &rest[..end];
+        rest = &rest[end + literal.len()..];
+    }
+    if literals.next().is_some() {
+        return Err(format!("{:?} has more than {} captures", pattern, N));
+    }
+    match rest.is_empty() {
+        true => Ok(captures),
+        false => Err(mismatch()),
+    }
+}
+
+/// Matches a line against a [`fields`] pattern and parses each capture
+/// with `FromStr`, giving a tuple of the listed types.
+///
+/// ```
+/// # use common::scan;
+/// let line = "Sensor at x=2, y=-18: closest beacon is at x=-2, y=15";
+/// let (sx, sy, bx, by) = scan!(
+///     line,
+///     "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+///     i64, i64, i64, i64
+/// )
+/// .unwrap();
+/// assert_eq!((sx, sy, bx, by), (2, -18, -2, 15));
+/// ```
+#[macro_export]
+macro_rules! scan {
+    ($line:expr, $pattern:literal, $($t:ty),+ $(,)?) => {
+        $crate::parse::fields::<{ [$(stringify!($t)),+].len() }>(
+            $pattern, $line,
+        )
+        .and_then(|fields| {
+            let mut fields = fields.into_iter();
+            Ok(($({
+                let field = fields.next().unwrap();
+                field.parse::<$t>().map_err(|_| {
+                    $crate::parse::__format_field_error(
+                        field,
+                        stringify!($t),
+                    )
+                })?
+            },)+))
+        })
+    };
+}
+
+#[doc(hidden)]
+pub fn __format_field_error(field: &str, ty: &str) -> String {
+    format!("invalid {}: {:?}", ty, field)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_numbers() {
+        let line = "move 13 from -2 to 3-4, x=-7";
+        assert_eq!(numbers::<u32>(line).collect::<Vec<_>>(), [13, 2, 3, 4, 7]);
+        assert_eq!(
+            numbers::<i32>(line).collect::<Vec<_>>(),
+            [13, -2, 3, -4, -7]
+        );
+        assert_eq!(numbers::<i8>("-128 127").collect::<Vec<_>>(), [-128, 127]);
+        assert_eq!(numbers::<u8>("no digits").count(), 0);
+    }
+
+    #[test]
+    fn test_extract() {
+        assert_eq!(extract("2-4,6-8"), Ok([2usize, 4, 6, 8]));
+        assert_eq!(
+            extract::<u8, 3>("1 2"),
+            Err("expected 3 numbers, found 2 in \"1 2\"".into())
+        );
+        assert!(extract::<u8, 1>("1 2").is_err());
+        assert_eq!(
+            extract::<u8, 1>("256"),
+            Err("256 overflows its type".into())
+        );
+    }
+
+    #[test]
+    fn test_fields_and_scan() {
+        assert_eq!(fields("{} -> {}", "a -> b"), Ok(["a", "b"]));
+        assert_eq!(fields("[{}]", "[x]"), Ok(["x"]));
+        assert!(fields::<1>("[{}]", "[x]!").is_err());
+        assert!(fields::<1>("[{}]", "(x)").is_err());
+        assert!(fields::<2>("[{}]", "[x]").is_err());
+        assert!(fields::<1>("{}{}", "x").is_err());
+
+        let parsed = scan!("addx -3", "{} {}", String, i32);
+        assert_eq!(parsed, Ok(("addx".into(), -3)));
+        let invalid = scan!("addx three", "{} {}", String, i32);
+        assert_eq!(invalid, Err("invalid i32: \"three\"".into()));
+    }
+
+    const SENSORS: &str = "Sensor at x=2302110, y=2237242: closest beacon \
+                           is at x=2348729, y=1239977";
+
+    #[bench]
+    fn bench_sensor_split_punctuation(b: &mut test::Bencher) {
+        b.iter(|| {
+            test::black_box(SENSORS)
+                .split(|c: char| c.is_ascii_punctuation() && c != '-')
+                .filter_map(|s| s.parse::<i64>().ok())
+                .collect::<Vec<_>>()
+        });
+    }
+
+    #[bench]
+    fn bench_sensor_numbers(b: &mut test::Bencher) {
+        b.iter(|| extract::<i64, 4>(test::black_box(SENSORS)).unwrap());
+    }
+
+    #[bench]
+    fn bench_sensor_scan(b: &mut test::Bencher) {
+        b.iter(|| {
+            scan!(
+                test::black_box(SENSORS),
+                "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+                i64,
+                i64,
+                i64,
+                i64
+            )
+            .unwrap()
+        });
+    }
+
+    #[bench]
+    fn bench_ranges_nested_split(b: &mut test::Bencher) {
+        b.iter(|| {
+            test::black_box("21-82,22-81")
+                .split(',')
+                .map(|p| {
+                    p.split('-')
+                        .map(|i| i.parse::<usize>().unwrap())
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        });
+    }
+
+    #[bench]
+    fn bench_ranges_extract(b: &mut test::Bencher) {
+        b.iter(|| extract::<usize, 4>(test::black_box("21-82,22-81")).unwrap());
+    }
+}
@@ -0,0 +1,205 @@
+//! The library side of a day built as a plugin, set up by
+//! [`export_plugin!`](crate::export_plugin).
+//!
+//! The runner and the plugin each link their own `std`. A panic cannot
+//! unwind from one into the other, so the plugin's solvers are run behind a
+//! `catch_unwind` on its side and their panic is handed to the runner
+//! through the [`Context`]. Memory does cross the boundary, answers and
+//! panic messages for example, so the plugin allocates with the runner's
+//! allocator, which also keeps `aoc scale` and `aoc stream` counting it.
+//!
+//! Only the solvers are guarded: the runner still aborts if a plugin's
+//! generator, REPL commands or simulations panic.
+
+use crate::answer::Answer;
+use crate::context::{Cancelled, Context};
+use crate::{Day, Implementation};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::marker::PhantomData;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::OnceLock;
+
+/// The most implementations a part of a plugin can have.
+pub const MAX_IMPLEMENTATIONS: usize = 8;
+
+type Run = fn(&str, &Context) -> Result<Answer, Cancelled>;
+
+/// Implemented by `export_plugin!` for the day it exports.
+pub trait Exported {
+    const DAY: Day;
+}
+
+/// The exported day, with every solver guarded.
+pub struct Guarded<D>(PhantomData<D>);
+
+impl<D: Exported> Guarded<D> {
+    const PART_ONE: [Implementation; MAX_IMPLEMENTATIONS] =
+        guard::<D, 0>(D::DAY.part_one);
+    const PART_TWO: [Implementation; MAX_IMPLEMENTATIONS] =
+        guard::<D, 1>(D::DAY.part_two);
+
+    pub const DAY: Day = Day {
+        part_one: Self::PART_ONE.split_at(D::DAY.part_one.len()).0,
+        part_two: Self::PART_TWO.split_at(D::DAY.part_two.len()).0,
+        ..D::DAY
+    };
+}
+
+/// The implementations of a part with their solvers replaced by
+/// [`run_guarded`], padded to [`MAX_IMPLEMENTATIONS`].
+const fn guard<D: Exported, const PART: usize>(
+    implementations: &[Implementation],
+) -> [Implementation; MAX_IMPLEMENTATIONS] {
+    let runs: [Run; MAX_IMPLEMENTATIONS] = [
+        run_guarded::<D, PART, 0>,
+        run_guarded::<D, PART, 1>,
+        run_guarded::<D, PART, 2>,
+        run_guarded::<D, PART, 3>,
+        run_guarded::<D, PART, 4>,
+        run_guarded::<D, PART, 5>,
+        run_guarded::<D, PART, 6>,
+        run_guarded::<D, PART, 7>,
+    ];
+    assert!(
+        implementations.len() <= MAX_IMPLEMENTATIONS,
+        "too many implementations for a plugin"
+    );
+    let mut guarded = [Implementation {
+        name: "",
+        run: runs[0],
+    }; MAX_IMPLEMENTATIONS];
+    let mut i = 0;
+    while i < implementations.len() {
+        guarded[i] = Implementation {
+            name: implementations[i].name,
+            run: runs[i],
+        };
+        i += 1;
+    }
+    guarded
+}
+
+/// Runs implementation `I` of a part, a panic ending in `context`.
+fn run_guarded<D: Exported, const PART: usize, const I: usize>(
+    input: &str,
+    context: &Context,
+) -> Result<Answer, Cancelled> {
+    let implementation = [D::DAY.part_one, D::DAY.part_two][PART][I];
+    let run = || (implementation.run)(input, context);
+    panic::catch_unwind(AssertUnwindSafe(run)).unwrap_or_else(|err| {
+        let message = err
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| err.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        context.set_panic(message);
+        Err(Cancelled)
+    })
+}
+
+/// An allocator as the `std::alloc` functions of the `std` it belongs to.
+#[derive(Debug, Clone, Copy)]
+pub struct Allocator {
+    pub alloc: unsafe fn(Layout) -> *mut u8,
+    pub alloc_zeroed: unsafe fn(Layout) -> *mut u8,
+    pub dealloc: unsafe fn(*mut u8, Layout),
+    pub realloc: unsafe fn(*mut u8, Layout, usize) -> *mut u8,
+}
+
+impl Allocator {
+    /// The global allocator of the caller.
+    pub fn global() -> Self {
+        Self {
+            alloc: std::alloc::alloc,
+            alloc_zeroed: std::alloc::alloc_zeroed,
+            dealloc: std::alloc::dealloc,
+            realloc: std::alloc::realloc,
+        }
+    }
+}
+
+static RUNNER: OnceLock<Allocator> = OnceLock::new();
+
+/// Called by the runner as soon as the plugin is loaded. From then on the
+/// plugin allocates with the runner's allocator and leaves printing its
+/// solvers' panics to the runner.
+pub fn init(allocator: Allocator) {
+    let _ = RUNNER.set(allocator);
+    panic::set_hook(Box::new(|_| {}));
+}
+
+/// The global allocator of a plugin: the runner's once [`init`] gave it,
+/// the system allocator until then.
+pub struct RunnerAllocator;
+
+unsafe impl GlobalAlloc for RunnerAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        match RUNNER.get() {
+            Some(runner) => (runner.alloc)(layout),
+            None => System.alloc(layout),
+        }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        match RUNNER.get() {
+            Some(runner) => (runner.alloc_zeroed)(layout),
+            None => System.alloc_zeroed(layout),
+        }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        match RUNNER.get() {
+            Some(runner) => (runner.dealloc)(ptr, layout),
+            None => System.dealloc(ptr, layout),
+        }
+    }
+
+    unsafe fn realloc(
+        &self,
+        ptr: *mut u8,
+        layout: Layout,
+        new_size: usize,
+    ) -> *mut u8 {
+        match RUNNER.get() {
+            Some(runner) => (runner.realloc)(ptr, layout, new_size),
+            None => System.realloc(ptr, layout, new_size),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn panics(_: &str, _: &Context) -> Result<Answer, Cancelled> {
+        panic!("bad input")
+    }
+
+    struct Panicking;
+
+    impl Exported for Panicking {
+        const DAY: Day = Day {
+            day: 1,
+            sample: "",
+            part_one: &[Implementation {
+                name: "panics",
+                run: panics,
+            }],
+            part_two: &[],
+            generate: |_, _| String::new(),
+            inspect: |_| String::new(),
+            commands: &[],
+            simulations: &[],
+        };
+    }
+
+    #[test]
+    fn test_panic_is_handed_over() {
+        let day = Guarded::<Panicking>::DAY;
+        assert_eq!((day.part_one.len(), day.part_two.len()), (1, 0));
+        assert_eq!(day.part_one[0].name, "panics");
+        let context = Context::new();
+        assert_eq!((day.part_one[0].run)("", &context), Err(Cancelled));
+        assert_eq!(context.take_panic().as_deref(), Some("bad input"));
+    }
+}
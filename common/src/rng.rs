@@ -0,0 +1,103 @@
+use core::ops::RangeInclusive;
+
+/// Small, seedable SplitMix64 generator.
+///
+/// Input generators only need reproducibility, not statistical quality, so
+/// this keeps the solutions free of an external `rand` dependency.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in `0..n`. `n` must be non-zero.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "empty range");
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+
+    pub fn range<T: Uniform>(&mut self, range: RangeInclusive<T>) -> T {
+        T::sample(self, range)
+    }
+
+    /// `true` with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64) < p * (1u64 << 53) as f64
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i as u64 + 1) as usize);
+        }
+    }
+}
+
+pub trait Uniform: Copy {
+    fn sample(rng: &mut Rng, range: RangeInclusive<Self>) -> Self;
+}
+
+macro_rules! impl_uniform {
+    ($($t:ty),*) => {$(
+        impl Uniform for $t {
+            fn sample(rng: &mut Rng, range: RangeInclusive<Self>) -> Self {
+                let (lo, hi) = range.into_inner();
+                assert!(lo <= hi, "empty range");
+                let span = (hi as i128 - lo as i128 + 1) as u128;
+                if span > u64::MAX as u128 {
+                    return rng.next_u64() as $t;
+                }
+                (lo as i128 + rng.below(span as u64) as i128) as $t
+            }
+        }
+    )*};
+}
+
+impl_uniform!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        assert!((0..100).all(|_| a.next_u64() == b.next_u64()));
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+    }
+
+    #[test]
+    fn test_range_is_inclusive_and_bounded() {
+        let mut rng = Rng::new(7);
+        let values = (0..1000).map(|_| rng.range(-2..=2)).collect::<Vec<i32>>();
+        assert!(values.iter().all(|v| (-2..=2).contains(v)));
+        assert!(values.contains(&-2) && values.contains(&2));
+        assert_ne!(
+            rng.range(u64::MIN..=u64::MAX),
+            rng.range(u64::MIN..=u64::MAX)
+        );
+    }
+
+    #[test]
+    fn test_shuffle_is_a_permutation() {
+        let mut rng = Rng::new(3);
+        let mut items = (0..50).collect::<Vec<_>>();
+        rng.shuffle(&mut items);
+        items.sort_unstable();
+        assert_eq!(items, (0..50).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,222 @@
+//! Shortest paths over graphs given as neighbor functions, so a grid, a
+//! state space or an explicit graph can be searched alike. Nodes only need
+//! to be `Ord`, the bookkeeping is done in `BTreeMap`s.
+
+use alloc::collections::{BTreeMap, BinaryHeap, VecDeque};
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+use core::ops::Add;
+
+/// The outcome of a search: the distance to every settled node, how each
+/// was reached, and the goal if one was found.
+#[derive(Debug, Clone)]
+pub struct Search<N, C = usize> {
+    distances: BTreeMap<N, C>,
+    predecessors: BTreeMap<N, N>,
+    goal: Option<N>,
+}
+
+impl<N: Ord + Clone, C: Copy> Search<N, C> {
+    /// Distance from the nearest start, `None` if `node` was not reached
+    /// before the search stopped.
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.distances.get(node).copied()
+    }
+
+    /// Every settled node with its distance. Without a goal that is every
+    /// reachable node.
+    pub fn distances(&self) -> &BTreeMap<N, C> {
+        &self.distances
+    }
+
+    /// The first goal settled, which is the nearest one.
+    pub fn goal(&self) -> Option<&N> {
+        self.goal.as_ref()
+    }
+
+    pub fn goal_distance(&self) -> Option<C> {
+        self.distance(self.goal.as_ref()?)
+    }
+
+    /// A shortest path from one of the starts to `node`, both included.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        self.distances.get(node)?;
+        let mut path = alloc::vec![node.clone()];
+        while let Some(prev) = self.predecessors.get(path.last().unwrap()) {
+            path.push(prev.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    pub fn goal_path(&self) -> Option<Vec<N>> {
+        self.path_to(self.goal.as_ref()?)
+    }
+}
+
+/// Breadth-first search from every node of `starts` at once, with unit
+/// edge weights. Stops at the first node satisfying `is_goal`; pass
+/// `|_| false` for a full distance map.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Search<N>
+where
+    N: Ord + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut search = Search {
+        distances: BTreeMap::new(),
+        predecessors: BTreeMap::new(),
+        goal: None,
+    };
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if !search.distances.contains_key(&start) {
+            search.distances.insert(start.clone(), 0);
+            queue.push_back((start, 0));
+        }
+    }
+    while let Some((node, distance)) = queue.pop_front() {
+        if is_goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+        for next in neighbors(&node) {
+            if !search.distances.contains_key(&next) {
+                search.distances.insert(next.clone(), distance + 1);
+                search.predecessors.insert(next.clone(), node.clone());
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    search
+}
+
+/// Dijkstra's algorithm from every node of `starts` at once, `neighbors`
+/// giving each edge's weight. Weights must not be negative.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Ord + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, neighbors, |_| C::default(), is_goal)
+}
+
+/// A* search: Dijkstra's algorithm ordered by distance plus `heuristic`.
+/// The heuristic must never overestimate the distance left to a goal, and
+/// must be consistent (it cannot drop by more than an edge's weight) for
+/// the distances of settled nodes to be exact.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Ord + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut search = Search {
+        distances: BTreeMap::new(),
+        predecessors: BTreeMap::new(),
+        goal: None,
+    };
+    let mut best = BTreeMap::new();
+    let mut heap = BinaryHeap::new();
+    for start in starts {
+        best.insert(start.clone(), C::default());
+        heap.push(Reverse((heuristic(&start), C::default(), start)));
+    }
+    while let Some(Reverse((_, cost, node))) = heap.pop() {
+        if search.distances.contains_key(&node) || best[&node] < cost {
+            continue;
+        }
+        search.distances.insert(node.clone(), cost);
+        if is_goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+        for (next, weight) in neighbors(&node) {
+            let cost = cost + weight;
+            if search.distances.contains_key(&next)
+                || best.get(&next).is_some_and(|&known| known <= cost)
+            {
+                continue;
+            }
+            best.insert(next.clone(), cost);
+            search.predecessors.insert(next.clone(), node.clone());
+            heap.push(Reverse((cost + heuristic(&next), cost, next)));
+        }
+    }
+    search
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    /// 0 -> 1 -> 2 -> 3 with a costly shortcut 0 -> 3, and an unreachable 4.
+    fn edges(node: &u32) -> Vec<(u32, u32)> {
+        match node {
+            0 => vec![(1, 1), (3, 10)],
+            1 => vec![(2, 1)],
+            2 => vec![(3, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_bfs() {
+        let neighbors = |n: &u32| edges(n).into_iter().map(|(n, _)| n);
+        let search = bfs([0], neighbors, |&n| n == 3);
+        assert_eq!(search.goal_distance(), Some(1));
+        assert_eq!(search.goal_path(), Some(vec![0, 3]));
+
+        let search = bfs([0], neighbors, |_| false);
+        assert_eq!(search.distances().len(), 4);
+        assert_eq!(search.distance(&2), Some(2));
+        assert_eq!(search.distance(&4), None);
+        assert_eq!(bfs([0], neighbors, |&n| n == 4).goal(), None);
+
+        let search = bfs([1, 2], neighbors, |&n| n == 3);
+        assert_eq!(search.goal_path(), Some(vec![2, 3]));
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let search = dijkstra([0], edges, |&n| n == 3);
+        assert_eq!(search.goal_distance(), Some(3));
+        assert_eq!(search.goal_path(), Some(vec![0, 1, 2, 3]));
+        assert_eq!(search.path_to(&4), None);
+        assert_eq!(dijkstra([0], edges, |&n| n == 4).goal_distance(), None);
+    }
+
+    #[test]
+    fn test_astar_on_a_grid() {
+        // A wall at x = 2 with a gap at y = 3, from (0, 0) to (4, 0).
+        let neighbors = |&(x, y): &(i32, i32)| {
+            [(1, 0), (-1, 0), (0, 1), (0, -1)]
+                .map(|(dx, dy)| (x + dx, y + dy))
+                .into_iter()
+                .filter(|&(x, y)| (0..5).contains(&x) && (0..5).contains(&y))
+                .filter(|&(x, y)| x != 2 || y == 3)
+                .map(|next| (next, 1))
+        };
+        let heuristic = |&(x, y): &(i32, i32)| (4 - x).abs() + y.abs();
+        let search = astar([(0, 0)], neighbors, heuristic, |&n| n == (4, 0));
+        assert_eq!(search.goal_distance(), Some(10));
+        assert_eq!(search.goal_path().unwrap().len(), 11);
+        let full = dijkstra([(0, 0)], neighbors, |&n| n == (4, 0));
+        assert_eq!(full.goal_distance(), Some(10));
+        assert!(search.distances().len() <= full.distances().len());
+    }
+}
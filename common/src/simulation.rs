@@ -0,0 +1,265 @@
+//! Step-based puzzles as values that can be advanced one step at a time,
+//! inspected, snapshot and replayed, instead of opaque loops.
+
+use crate::context::{Cancelled, Context};
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// A deterministic process advanced in discrete steps. Stepping from a
+/// restored snapshot must give the same states as it did the first time.
+pub trait Simulation {
+    /// Everything needed to resume from a given step. Usually smaller than
+    /// the simulation itself, which may own inputs that never change.
+    type Snapshot: Clone;
+
+    /// Advances by one step. Returns `false`, leaving the state untouched,
+    /// once the simulation is over.
+    fn step(&mut self) -> bool;
+
+    /// Steps taken so far.
+    fn steps(&self) -> usize;
+
+    fn snapshot(&self) -> Self::Snapshot;
+
+    fn restore(&mut self, snapshot: &Self::Snapshot);
+
+    /// Steps until the simulation is over, returning the number of steps.
+    fn run(&mut self) -> usize {
+        while self.step() {}
+        self.steps()
+    }
+
+    /// Like [`run`](Self::run), but stops early once `context` is
+    /// cancelled, and reports the steps taken to it.
+    fn run_in(&mut self, context: &Context) -> Result<usize, Cancelled> {
+        loop {
+            context.check()?;
+            if !self.step() {
+                return Ok(self.steps());
+            }
+            context.set_done(self.steps() as u64);
+        }
+    }
+
+    /// Steps until `n` steps have been taken. Returns `false` if the
+    /// simulation ended earlier or is already past `n`.
+    fn run_to(&mut self, n: usize) -> bool {
+        while self.steps() < n {
+            if !self.step() {
+                return false;
+            }
+        }
+        self.steps() == n
+    }
+}
+
+/// A simulation that can go back in time. Snapshots are kept every
+/// `interval` steps, seeking restores the closest one before the target and
+/// replays from there.
+pub struct Replay<S: Simulation> {
+    sim: S,
+    /// The step each snapshot was taken at, in increasing order.
+    checkpoints: Vec<(usize, S::Snapshot)>,
+    interval: usize,
+}
+
+impl<S: Simulation> Replay<S> {
+    pub fn new(sim: S, interval: usize) -> Self {
+        assert!(interval > 0, "snapshot interval must be positive");
+        let checkpoints = alloc::vec![(sim.steps(), sim.snapshot())];
+        Self {
+            sim,
+            checkpoints,
+            interval,
+        }
+    }
+
+    pub fn sim(&self) -> &S {
+        &self.sim
+    }
+
+    pub fn step(&mut self) -> bool {
+        if !self.sim.step() {
+            return false;
+        }
+        let steps = self.sim.steps();
+        let last = self.checkpoints.last().map_or(0, |&(step, _)| step);
+        if steps >= last + self.interval {
+            self.checkpoints.push((steps, self.sim.snapshot()));
+        }
+        true
+    }
+
+    /// Moves to step `n`, backwards or forwards. Returns `false` if it is
+    /// before the simulation started or after it ended, leaving it at the
+    /// closest step it could reach.
+    pub fn seek(&mut self, n: usize) -> bool {
+        let i = self.checkpoints.partition_point(|&(step, _)| step <= n);
+        let (step, snapshot) = &self.checkpoints[i.saturating_sub(1)];
+        if i == 0 || n < self.sim.steps() || *step > self.sim.steps() {
+            self.sim.restore(snapshot);
+        }
+        if i == 0 {
+            return false;
+        }
+        while self.sim.steps() < n {
+            if !self.step() {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// What the runner drives: a [`Replay`] with the simulation rendered as
+/// text, behind a trait object.
+pub trait Playback {
+    fn step(&mut self) -> bool;
+    fn steps(&self) -> usize;
+    fn seek(&mut self, n: usize) -> bool;
+    fn render(&self) -> String;
+}
+
+impl<S: Simulation + fmt::Display> Playback for Replay<S> {
+    fn step(&mut self) -> bool {
+        Replay::step(self)
+    }
+
+    fn steps(&self) -> usize {
+        self.sim.steps()
+    }
+
+    fn seek(&mut self, n: usize) -> bool {
+        Replay::seek(self, n)
+    }
+
+    fn render(&self) -> String {
+        self.sim.to_string()
+    }
+}
+
+/// A named way to play a day's input as a simulation.
+#[derive(Debug, Clone, Copy)]
+pub struct Simulator {
+    pub name: &'static str,
+    pub help: &'static str,
+    /// Builds the simulation from the raw puzzle input.
+    pub start: fn(&str) -> Result<Box<dyn Playback>, String>,
+}
+
+/// Snapshots kept by the replays the [`Simulator`]s build.
+pub const SNAPSHOT_INTERVAL: usize = 64;
+
+/// Wraps a simulation for a [`Simulator`].
+pub fn playback<S>(sim: S) -> Box<dyn Playback>
+where
+    S: Simulation + fmt::Display + 'static,
+{
+    Box::new(Replay::new(sim, SNAPSHOT_INTERVAL))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Collatz sequence, which ends at 1.
+    #[derive(Debug, Clone)]
+    struct Collatz {
+        n: u64,
+        steps: usize,
+    }
+
+    impl Simulation for Collatz {
+        type Snapshot = Self;
+
+        fn step(&mut self) -> bool {
+            if self.n == 1 {
+                return false;
+            }
+            self.n = if self.n.is_multiple_of(2) {
+                self.n / 2
+            } else {
+                3 * self.n + 1
+            };
+            self.steps += 1;
+            true
+        }
+
+        fn steps(&self) -> usize {
+            self.steps
+        }
+
+        fn snapshot(&self) -> Self {
+            self.clone()
+        }
+
+        fn restore(&mut self, snapshot: &Self) {
+            *self = snapshot.clone();
+        }
+    }
+
+    impl fmt::Display for Collatz {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "{}", self.n)
+        }
+    }
+
+    fn collatz(n: u64) -> Collatz {
+        Collatz { n, steps: 0 }
+    }
+
+    #[test]
+    fn test_run() {
+        let mut sim = collatz(27);
+        assert!(sim.run_to(2));
+        assert_eq!(sim.n, 41);
+        assert!(!sim.run_to(1));
+        assert_eq!(sim.run(), 111);
+        assert!(!sim.step());
+        assert_eq!((sim.n, sim.steps()), (1, 111));
+    }
+
+    #[test]
+    fn test_run_in_context() {
+        let context = Context::new();
+        assert_eq!(collatz(27).run_in(&context), Ok(111));
+        context.set_total(111);
+        assert_eq!(context.progress(), Some((111, 111)));
+        context.cancel();
+        let mut sim = collatz(27);
+        assert_eq!(sim.run_in(&context), Err(Cancelled));
+        assert_eq!(sim.steps(), 0);
+    }
+
+    #[test]
+    fn test_replay_seeks_both_ways() {
+        let states = (0..=111)
+            .map(|n| {
+                let mut sim = collatz(27);
+                sim.run_to(n);
+                sim.n
+            })
+            .collect::<Vec<_>>();
+        let mut replay = Replay::new(collatz(27), 10);
+        for n in [50, 3, 111, 0, 49, 50, 10, 9, 100] {
+            assert!(replay.seek(n));
+            assert_eq!((replay.sim().n, replay.sim().steps()), (states[n], n));
+        }
+        assert!(!replay.seek(500));
+        assert_eq!(replay.sim().steps(), 111);
+        assert_eq!(replay.checkpoints.len(), 12);
+    }
+
+    #[test]
+    fn test_playback() {
+        let mut playback = playback(collatz(6));
+        assert!(playback.step() && playback.step());
+        assert_eq!(playback.render(), "10");
+        assert!(playback.seek(8) && !playback.step());
+        assert_eq!((playback.steps(), playback.render()), (8, "1".into()));
+        assert!(playback.seek(0));
+        assert_eq!(playback.render(), "6");
+    }
+}
@@ -0,0 +1,311 @@
+//! Vector drawings as SVG documents: shapes grouped in layers, each layer
+//! with one style. Coordinates are puzzle coordinates, each point the
+//! center of a unit cell, and strokes keep their width however far the
+//! drawing is zoomed.
+
+use crate::geometry::{BoundingBox, Point};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Shape {
+    Rect {
+        x: f64,
+        y: f64,
+        width: i64,
+        height: i64,
+    },
+    Circle {
+        center: Point,
+        radius: f64,
+    },
+    Polyline(Vec<Point>),
+    Polygon(Vec<Point>),
+    Text {
+        at: Point,
+        text: String,
+    },
+}
+
+/// A named group of shapes sharing a style, drawn in the order the layers
+/// were added.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layer {
+    id: String,
+    fill: String,
+    stroke: String,
+    stroke_width: f64,
+    opacity: f64,
+    font_size: f64,
+    shapes: Vec<Shape>,
+}
+
+impl Layer {
+    /// `none` for no fill.
+    pub fn fill(&mut self, color: &str) -> &mut Self {
+        self.fill = color.into();
+        self
+    }
+
+    /// Width in pixels on the page, whatever the scale.
+    pub fn stroke(&mut self, color: &str, width: f64) -> &mut Self {
+        self.stroke = color.into();
+        self.stroke_width = width;
+        self
+    }
+
+    pub fn opacity(&mut self, opacity: f64) -> &mut Self {
+        self.opacity = opacity;
+        self
+    }
+
+    /// The height of the labels, in puzzle units.
+    pub fn font_size(&mut self, size: f64) -> &mut Self {
+        self.font_size = size;
+        self
+    }
+
+    /// The cells from `min` to `max`, both included.
+    pub fn rect(&mut self, min: Point, max: Point) -> &mut Self {
+        self.shapes.push(Shape::Rect {
+            x: min.x as f64 - 0.5,
+            y: min.y as f64 - 0.5,
+            width: max.x - min.x + 1,
+            height: max.y - min.y + 1,
+        });
+        self
+    }
+
+    pub fn cell(&mut self, cell: Point) -> &mut Self {
+        self.rect(cell, cell)
+    }
+
+    pub fn circle(&mut self, center: Point, radius: f64) -> &mut Self {
+        self.shapes.push(Shape::Circle { center, radius });
+        self
+    }
+
+    pub fn line(&mut self, from: Point, to: Point) -> &mut Self {
+        self.polyline([from, to])
+    }
+
+    pub fn polyline(
+        &mut self,
+        points: impl IntoIterator<Item = Point>,
+    ) -> &mut Self {
+        self.shapes
+            .push(Shape::Polyline(points.into_iter().collect()));
+        self
+    }
+
+    /// A closed polyline.
+    pub fn polygon(
+        &mut self,
+        points: impl IntoIterator<Item = Point>,
+    ) -> &mut Self {
+        self.shapes
+            .push(Shape::Polygon(points.into_iter().collect()));
+        self
+    }
+
+    /// A label with its baseline starting at `at`.
+    pub fn text(&mut self, at: Point, text: &str) -> &mut Self {
+        self.shapes.push(Shape::Text {
+            at,
+            text: text.into(),
+        });
+        self
+    }
+}
+
+/// An SVG document showing the area `view`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Svg {
+    view: BoundingBox,
+    layers: Vec<Layer>,
+}
+
+impl Svg {
+    /// A drawing of the cells in `view`, grown by `margin` all around.
+    pub fn new(view: BoundingBox, margin: i64) -> Self {
+        Self {
+            view: view.expand(margin),
+            layers: Vec::new(),
+        }
+    }
+
+    /// Adds a layer on top of the others. Shapes are unfilled black lines
+    /// until styled otherwise.
+    pub fn layer(&mut self, id: &str) -> &mut Layer {
+        self.layers.push(Layer {
+            id: id.into(),
+            fill: "none".into(),
+            stroke: "black".into(),
+            stroke_width: 1.0,
+            opacity: 1.0,
+            font_size: 1.0,
+            shapes: Vec::new(),
+        });
+        self.layers.last_mut().unwrap()
+    }
+}
+
+impl fmt::Display for Svg {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (min, width, height) =
+            (self.view.min, self.view.width(), self.view.height());
+        // Keep the longest side around a thousand pixels.
+        let scale = 1000.0 / width.max(height) as f64;
+        writeln!(
+            f,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" \
+             viewBox=\"{} {} {} {}\" width=\"{:.0}\" height=\"{:.0}\">",
+            min.x as f64 - 0.5,
+            min.y as f64 - 0.5,
+            width,
+            height,
+            width as f64 * scale,
+            height as f64 * scale
+        )?;
+        for layer in &self.layers {
+            writeln!(
+                f,
+                "<g id=\"{}\" fill=\"{}\" stroke=\"{}\" stroke-width=\"{}\" \
+                 opacity=\"{}\" font-size=\"{}\" font-family=\"monospace\">",
+                Escaped(&layer.id),
+                Escaped(&layer.fill),
+                Escaped(&layer.stroke),
+                layer.stroke_width,
+                layer.opacity,
+                layer.font_size
+            )?;
+            for shape in &layer.shapes {
+                write_shape(f, shape)?;
+            }
+            writeln!(f, "</g>")?;
+        }
+        write!(f, "</svg>")
+    }
+}
+
+fn write_shape(f: &mut fmt::Formatter, shape: &Shape) -> fmt::Result {
+    const SCALING: &str = "vector-effect=\"non-scaling-stroke\"";
+    match shape {
+        Shape::Rect {
+            x,
+            y,
+            width,
+            height,
+        } => writeln!(
+            f,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" {}/>",
+            x, y, width, height, SCALING
+        ),
+        Shape::Circle { center, radius } => writeln!(
+            f,
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" {}/>",
+            center.x, center.y, radius, SCALING
+        ),
+        Shape::Polyline(points) | Shape::Polygon(points) => {
+            let tag = match shape {
+                Shape::Polygon(_) => "polygon",
+                _ => "polyline",
+            };
+            write!(f, "<{} points=\"", tag)?;
+            for (i, p) in points.iter().enumerate() {
+                let sep = if i > 0 { " " } else { "" };
+                write!(f, "{}{},{}", sep, p.x, p.y)?;
+            }
+            writeln!(f, "\" {}/>", SCALING)
+        }
+        Shape::Text { at, text } => writeln!(
+            f,
+            "<text x=\"{}\" y=\"{}\" stroke=\"none\">{}</text>",
+            at.x,
+            at.y,
+            Escaped(text)
+        ),
+    }
+}
+
+/// Text with the characters XML reserves escaped.
+struct Escaped<'a>(&'a str);
+
+impl fmt::Display for Escaped<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '<' => f.write_str("&lt;")?,
+                '>' => f.write_str("&gt;")?,
+                '&' => f.write_str("&amp;")?,
+                '"' => f.write_str("&quot;")?,
+                c => f.write_char(c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes the drawing to the path given with `--svg PATH` (or
+/// `--svg=PATH`) on the command line, if any. Panics if it cannot.
+#[cfg(feature = "std")]
+pub fn export_from_args(draw: impl FnOnce() -> Result<Svg, String>) {
+    if let Some(path) = path_from_args() {
+        let svg = draw().unwrap_or_else(|e| panic!("cannot draw: {}", e));
+        std::fs::write(&path, svg.to_string()).unwrap_or_else(|e| {
+            panic!("cannot write {}: {}", path.display(), e)
+        });
+        println!("Drawing written to {}", path.display());
+    }
+}
+
+#[cfg(feature = "std")]
+fn path_from_args() -> Option<std::path::PathBuf> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.strip_prefix("--svg") {
+            Some("") => return args.next().map(Into::into),
+            Some(path) => return path.strip_prefix('=').map(Into::into),
+            None => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_document() {
+        let view = BoundingBox::new(Point::ZERO).including(Point::new(9, 4));
+        let mut svg = Svg::new(view, 1);
+        svg.layer("rock")
+            .stroke("#888", 2.0)
+            .polyline([Point::new(0, 0), Point::new(0, 4), Point::new(5, 4)])
+            .cell(Point::new(9, 4));
+        svg.layer("labels <&>")
+            .fill(&crate::image::Rgb(255, 0, 0).to_string())
+            .font_size(0.5)
+            .text(Point::new(1, 1), "a < b")
+            .circle(Point::new(2, 2), 0.5);
+        let svg = svg.to_string();
+        let lines = svg.lines().collect::<Vec<_>>();
+        assert!(lines[0].contains("viewBox=\"-1.5 -1.5 12 7\""));
+        assert!(lines[0].contains("width=\"1000\" height=\"583\""));
+        assert!(lines[1].starts_with(
+            "<g id=\"rock\" fill=\"none\" stroke=\"#888\" stroke-width=\"2\""
+        ));
+        assert!(lines[2].starts_with("<polyline points=\"0,0 0,4 5,4\""));
+        assert!(lines[3].starts_with("<rect x=\"8.5\" y=\"3.5\" width=\"1\""));
+        assert!(
+            lines[5].contains("id=\"labels &lt;&amp;&gt;\" fill=\"#ff0000\"")
+        );
+        assert!(lines[6].ends_with(">a &lt; b</text>"));
+        assert!(lines[7].starts_with("<circle cx=\"2\" cy=\"2\" r=\"0.5\""));
+        assert_eq!(lines.last(), Some(&"</svg>"));
+        assert_eq!(lines.len(), 10);
+    }
+}
@@ -0,0 +1,55 @@
+/* Generated from ffi/src/lib.rs by `AOC_BLESS=1 cargo test -p aoc-ffi`. */
+#ifndef AOC_H
+#define AOC_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#define AOC_ABI_VERSION 1
+
+/* success */
+#define AOC_OK 0
+/* a required pointer is null */
+#define AOC_NULL_POINTER 1
+/* the year is not solved */
+#define AOC_UNKNOWN_YEAR 2
+/* the day is not solved */
+#define AOC_UNKNOWN_DAY 3
+/* the part is not 1 or 2 */
+#define AOC_UNKNOWN_PART 4
+/* the input is not valid UTF-8 */
+#define AOC_INVALID_UTF8 5
+/* the solver panicked, the buffer holds its message */
+#define AOC_SOLVER_PANICKED 6
+/* the buffer is too small, len holds the required length */
+#define AOC_BUFFER_TOO_SMALL 7
+/* the solver was cancelled after the timeout */
+#define AOC_SOLVER_TIMED_OUT 8
+
+/* Caller-owned output buffer. On return `len` is the length of the
+   answer (or error message) without its NUL terminator, even when it
+   did not fit. */
+typedef struct {
+    uint8_t *ptr;
+    size_t cap;
+    size_t len;
+} AocBuffer;
+
+uint32_t aoc_abi_version(void);
+
+/* A static description of a status code, or NULL for unknown codes. */
+const char *aoc_status_message(int32_t status);
+
+/* Solves a part of a day on `len` bytes of input and writes the answer
+   to `out` as a NUL-terminated string. Returns one of the AOC_ codes. */
+int32_t aoc_solve(uint16_t year, uint8_t day, uint8_t part,
+                  const uint8_t *input, size_t len, AocBuffer *out);
+
+/* Like aoc_solve, cancelling the solver after `timeout_ms`
+   milliseconds, 0 for no limit. A solver that does not stop when
+   cancelled is left running in the background. */
+int32_t aoc_solve_timeout(uint16_t year, uint8_t day, uint8_t part,
+                          const uint8_t *input, size_t len, uint64_t timeout_ms,
+                          AocBuffer *out);
+
+#endif
@@ -0,0 +1,279 @@
+//! Stable C interface to the solvers. The header in `include/aoc.h` is
+//! generated from this file by [`header`].
+
+use aoc::RunError;
+use std::ffi::{c_char, CStr};
+use std::fmt::Write;
+use std::time::Duration;
+use std::{ptr, slice};
+
+/// Bumped on any incompatible change to the functions or types below.
+pub const ABI_VERSION: u32 = 1;
+
+/// Caller-owned output buffer. On return `len` is the length of the answer
+/// (or error message) without its NUL terminator, even when it did not fit.
+#[repr(C)]
+#[derive(Debug)]
+pub struct AocBuffer {
+    pub ptr: *mut u8,
+    pub cap: usize,
+    pub len: usize,
+}
+
+macro_rules! statuses {
+    ($($name:ident = $code:literal => $message:literal,)+) => {
+        $(pub const $name: i32 = $code;)+
+
+        /// Name, code and description of every status.
+        pub const STATUSES: &[(&str, i32, &CStr)] =
+            &[$((stringify!($name), $code, $message)),+];
+    };
+}
+
+statuses! {
+    AOC_OK = 0 => c"success",
+    AOC_NULL_POINTER = 1 => c"a required pointer is null",
+    AOC_UNKNOWN_YEAR = 2 => c"the year is not solved",
+    AOC_UNKNOWN_DAY = 3 => c"the day is not solved",
+    AOC_UNKNOWN_PART = 4 => c"the part is not 1 or 2",
+    AOC_INVALID_UTF8 = 5 => c"the input is not valid UTF-8",
+    AOC_SOLVER_PANICKED = 6 =>
+        c"the solver panicked, the buffer holds its message",
+    AOC_BUFFER_TOO_SMALL = 7 =>
+        c"the buffer is too small, len holds the required length",
+    AOC_SOLVER_TIMED_OUT = 8 =>
+        c"the solver was cancelled after the timeout",
+}
+
+#[no_mangle]
+pub extern "C" fn aoc_abi_version() -> u32 {
+    ABI_VERSION
+}
+
+/// A static description of a status code, or NULL for unknown codes.
+#[no_mangle]
+pub extern "C" fn aoc_status_message(status: i32) -> *const c_char {
+    STATUSES
+        .iter()
+        .find(|(_, code, _)| *code == status)
+        .map_or(ptr::null(), |(_, _, message)| message.as_ptr())
+}
+
+/// Solves a part of a day on `len` bytes of input and writes the answer to
+/// `out` as a NUL-terminated string.
+///
+/// # Safety
+///
+/// `input` must point to `len` readable bytes (it may be NULL when `len` is
+/// 0) and `out` to a buffer whose `ptr` has `cap` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    year: u16,
+    day: u8,
+    part: u8,
+    input: *const u8,
+    len: usize,
+    out: *mut AocBuffer,
+) -> i32 {
+    aoc_solve_timeout(year, day, part, input, len, 0, out)
+}
+
+/// Like [`aoc_solve`], cancelling the solver after `timeout_ms`
+/// milliseconds, 0 for no limit. A solver that does not stop when cancelled
+/// is left running in the background.
+///
+/// # Safety
+///
+/// As for [`aoc_solve`].
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve_timeout(
+    year: u16,
+    day: u8,
+    part: u8,
+    input: *const u8,
+    len: usize,
+    timeout_ms: u64,
+    out: *mut AocBuffer,
+) -> i32 {
+    let Some(out) = out.as_mut() else {
+        return AOC_NULL_POINTER;
+    };
+    if (input.is_null() && len > 0) || (out.ptr.is_null() && out.cap > 0) {
+        return AOC_NULL_POINTER;
+    }
+    if year != 2022 {
+        return AOC_UNKNOWN_YEAR;
+    }
+    let Some(day) = aoc::find_day(day) else {
+        return AOC_UNKNOWN_DAY;
+    };
+    let implementations = match part {
+        1 => day.part_one,
+        2 => day.part_two,
+        _ => return AOC_UNKNOWN_PART,
+    };
+    let input = match len {
+        0 => &[],
+        _ => slice::from_raw_parts(input, len),
+    };
+    let Ok(input) = std::str::from_utf8(input) else {
+        return AOC_INVALID_UTF8;
+    };
+    let timeout = (timeout_ms > 0).then(|| Duration::from_millis(timeout_ms));
+    match aoc::run_with_timeout(&implementations[0], input, timeout, |_| {}) {
+        Ok(answer) => write_out(out, &answer.to_string(), AOC_OK),
+        Err(RunError::Panicked(panic)) => {
+            write_out(out, &panic, AOC_SOLVER_PANICKED)
+        }
+        Err(timed_out) => {
+            write_out(out, &timed_out.to_string(), AOC_SOLVER_TIMED_OUT)
+        }
+    }
+}
+
+unsafe fn write_out(out: &mut AocBuffer, text: &str, status: i32) -> i32 {
+    out.len = text.len();
+    if out.cap <= text.len() {
+        return AOC_BUFFER_TOO_SMALL;
+    }
+    ptr::copy_nonoverlapping(text.as_ptr(), out.ptr, text.len());
+    *out.ptr.add(text.len()) = 0;
+    status
+}
+
+/// Renders `include/aoc.h`.
+pub fn header() -> String {
+    let mut header = String::from(
+        "/* Generated from ffi/src/lib.rs by `AOC_BLESS=1 cargo test -p \
+         aoc-ffi`. */\n\
+         #ifndef AOC_H\n\
+         #define AOC_H\n\n\
+         #include <stddef.h>\n\
+         #include <stdint.h>\n\n",
+    );
+    let _ = writeln!(header, "#define AOC_ABI_VERSION {}\n", ABI_VERSION);
+    for (name, code, message) in STATUSES {
+        let _ = writeln!(
+            header,
+            "/* {} */\n#define {} {}",
+            message.to_str().unwrap(),
+            name,
+            code
+        );
+    }
+    header.push_str(
+        "\n\
+         /* Caller-owned output buffer. On return `len` is the length of the\n   \
+         answer (or error message) without its NUL terminator, even when it\n   \
+         did not fit. */\n\
+         typedef struct {\n    \
+         uint8_t *ptr;\n    \
+         size_t cap;\n    \
+         size_t len;\n\
+         } AocBuffer;\n\n\
+         uint32_t aoc_abi_version(void);\n\n\
+         /* A static description of a status code, or NULL for unknown codes. \
+         */\n\
+         const char *aoc_status_message(int32_t status);\n\n\
+         /* Solves a part of a day on `len` bytes of input and writes the \
+         answer\n   \
+         to `out` as a NUL-terminated string. Returns one of the AOC_ \
+         codes. */\n\
+         int32_t aoc_solve(uint16_t year, uint8_t day, uint8_t part,\n                  \
+         const uint8_t *input, size_t len, AocBuffer *out);\n\n\
+         /* Like aoc_solve, cancelling the solver after `timeout_ms`\n   \
+         milliseconds, 0 for no limit. A solver that does not stop when\n   \
+         cancelled is left running in the background. */\n\
+         int32_t aoc_solve_timeout(uint16_t year, uint8_t day, uint8_t part,\n                          \
+         const uint8_t *input, size_t len, uint64_t timeout_ms,\n                          \
+         AocBuffer *out);\n\n\
+         #endif\n",
+    );
+    header
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solve(day: u8, part: u8, input: &str, cap: usize) -> (i32, String) {
+        let mut buf = vec![0xff; cap];
+        let mut out = AocBuffer {
+            ptr: buf.as_mut_ptr(),
+            cap,
+            len: 0,
+        };
+        let status = unsafe {
+            aoc_solve(2022, day, part, input.as_ptr(), input.len(), &mut out)
+        };
+        match status {
+            AOC_OK | AOC_SOLVER_PANICKED | AOC_SOLVER_TIMED_OUT => {
+                assert_eq!(buf[out.len], 0);
+                buf.truncate(out.len);
+                (status, String::from_utf8(buf).unwrap())
+            }
+            _ => (status, out.len.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        let sample = aoc::DAYS[6].sample;
+        assert_eq!(solve(7, 2, sample, 64), (AOC_OK, "24933642".to_string()));
+        assert_eq!(solve(7, 2, sample, 8), (AOC_BUFFER_TOO_SMALL, "8".into()));
+        assert_eq!(solve(7, 3, sample, 64).0, AOC_UNKNOWN_PART);
+        assert_eq!(solve(16, 1, sample, 64).0, AOC_UNKNOWN_DAY);
+        assert_eq!(solve(1, 1, "x", 256).0, AOC_SOLVER_PANICKED);
+        let status =
+            unsafe { aoc_solve(2022, 1, 1, ptr::null(), 1, ptr::null_mut()) };
+        assert_eq!(status, AOC_NULL_POINTER);
+    }
+
+    #[test]
+    fn test_solve_timeout() {
+        let input = (aoc::DAYS[13].generate)(0, 200);
+        let mut buf = vec![0; 64];
+        let mut out = AocBuffer {
+            ptr: buf.as_mut_ptr(),
+            cap: buf.len(),
+            len: 0,
+        };
+        let status = unsafe {
+            aoc_solve_timeout(
+                2022,
+                14,
+                2,
+                input.as_ptr(),
+                input.len(),
+                1,
+                &mut out,
+            )
+        };
+        assert_eq!(status, AOC_SOLVER_TIMED_OUT);
+        buf.truncate(out.len);
+        assert_eq!(String::from_utf8(buf).unwrap(), "timed out after 1ms");
+    }
+
+    #[test]
+    fn test_status_messages() {
+        for (_, code, message) in STATUSES {
+            let got = unsafe { CStr::from_ptr(aoc_status_message(*code)) };
+            assert_eq!(got, *message);
+        }
+        assert!(aoc_status_message(-1).is_null());
+    }
+
+    #[test]
+    fn test_header_is_up_to_date() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/include/aoc.h");
+        if std::env::var_os("AOC_BLESS").is_some() {
+            std::fs::write(path, header()).unwrap();
+        }
+        assert_eq!(
+            std::fs::read_to_string(path).unwrap_or_default(),
+            header(),
+            "include/aoc.h is stale, regenerate it with \
+             `AOC_BLESS=1 cargo test -p aoc-ffi`"
+        );
+    }
+}
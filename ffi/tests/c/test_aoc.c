@@ -0,0 +1,113 @@
+/* Solves every day's sample through the C interface and checks the error
+ * codes. Usage: test_aoc <repository root> */
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "aoc.h"
+
+static const char *ANSWERS[15][2] = {
+    {"24000", "45000"},
+    {"15", "12"},
+    {"157", "70"},
+    {"2", "4"},
+    {"CMZ", "MCD"},
+    {"7", "19"},
+    {"95437", "24933642"},
+    {"21", "8"},
+    {"88", "36"},
+    {"13140", "##..##..##..##..##..##..##..##..##..##..\n"
+              "###...###...###...###...###...###...###.\n"
+              "####....####....####....####....####....\n"
+              "#####.....#####.....#####.....#####.....\n"
+              "######......######......######......####\n"
+              "#######.......#######.......#######....."},
+    {"10605", "2713310158"},
+    {"31", "29"},
+    {"13", "140"},
+    {"24", "93"},
+    {"26", "56000011"},
+};
+
+static int failures = 0;
+
+static void check(int ok, const char *what, int day, int part) {
+    if (!ok) {
+        fprintf(stderr, "FAILED: %s (day %d part %d)\n", what, day, part);
+        failures++;
+    }
+}
+
+static char *read_sample(const char *root, int day, size_t *len) {
+    char path[4096];
+    snprintf(path, sizeof path, "%s/%d/sample.TXT", root, day);
+    FILE *file = fopen(path, "rb");
+    if (!file) {
+        perror(path);
+        exit(1);
+    }
+    fseek(file, 0, SEEK_END);
+    *len = (size_t)ftell(file);
+    rewind(file);
+    char *input = malloc(*len);
+    if (fread(input, 1, *len, file) != *len) {
+        perror(path);
+        exit(1);
+    }
+    fclose(file);
+    return input;
+}
+
+int main(int argc, char **argv) {
+    if (argc != 2) {
+        fprintf(stderr, "usage: %s <repository root>\n", argv[0]);
+        return 2;
+    }
+    check(aoc_abi_version() == AOC_ABI_VERSION, "abi version", 0, 0);
+
+    uint8_t storage[512];
+    AocBuffer out = {storage, sizeof storage, 0};
+    for (int day = 1; day <= 15; day++) {
+        size_t len;
+        char *input = read_sample(argv[1], day, &len);
+        for (int part = 1; part <= 2; part++) {
+            int32_t status = aoc_solve(2022, day, part, (const uint8_t *)input,
+                                       len, &out);
+            check(status == AOC_OK, aoc_status_message(status), day, part);
+            check(status == AOC_OK &&
+                      strcmp((char *)out.ptr, ANSWERS[day - 1][part - 1]) == 0,
+                  "answer", day, part);
+        }
+        free(input);
+    }
+
+    const uint8_t *sample = (const uint8_t *)"1000\n\n2000\n";
+    check(aoc_solve(2021, 1, 1, sample, 11, &out) == AOC_UNKNOWN_YEAR,
+          "unknown year", 1, 1);
+    check(aoc_solve(2022, 26, 1, sample, 11, &out) == AOC_UNKNOWN_DAY,
+          "unknown day", 26, 1);
+    check(aoc_solve(2022, 1, 3, sample, 11, &out) == AOC_UNKNOWN_PART,
+          "unknown part", 1, 3);
+    check(aoc_solve(2022, 1, 1, NULL, 11, &out) == AOC_NULL_POINTER,
+          "null input", 1, 1);
+    check(aoc_solve(2022, 1, 1, (const uint8_t *)"\xff", 1, &out) ==
+              AOC_INVALID_UTF8,
+          "invalid utf-8", 1, 1);
+    check(aoc_solve(2022, 1, 1, (const uint8_t *)"x", 1, &out) ==
+              AOC_SOLVER_PANICKED,
+          "panic", 1, 1);
+    check(aoc_solve_timeout(2022, 1, 1, sample, 11, 60000, &out) == AOC_OK &&
+              strcmp((char *)out.ptr, "2000") == 0,
+          "timeout", 1, 1);
+
+    AocBuffer small = {storage, 2, 0};
+    check(aoc_solve(2022, 1, 1, sample, 11, &small) == AOC_BUFFER_TOO_SMALL &&
+              small.len == 4,
+          "buffer too small", 1, 1);
+    check(aoc_status_message(-1) == NULL, "unknown status", 0, 0);
+
+    if (failures == 0) {
+        printf("all days solved through the C interface\n");
+    }
+    return failures == 0 ? 0 : 1;
+}
@@ -0,0 +1,36 @@
+use std::path::Path;
+use std::process::Command;
+
+/// Builds `tests/c/test_aoc.c` against the cdylib and runs it on every
+/// day's sample.
+#[test]
+fn test_c_program() {
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR"));
+    // Cargo builds the cdylib next to this test, in target/<profile>/deps.
+    let exe = std::env::current_exe().unwrap();
+    let lib_dir = exe.parent().unwrap();
+    let program = lib_dir.join("test_aoc");
+
+    let status = Command::new(std::env::var("CC").unwrap_or("cc".into()))
+        .arg(manifest.join("tests/c/test_aoc.c"))
+        .arg("-I")
+        .arg(manifest.join("include"))
+        .arg("-L")
+        .arg(lib_dir)
+        .arg(format!("-Wl,-rpath,{}", lib_dir.display()))
+        .args(["-laoc_ffi", "-Wall", "-Werror", "-o"])
+        .arg(&program)
+        .status()
+        .expect("a C compiler is needed, set CC to use another one");
+    assert!(status.success(), "failed to compile the C test program");
+
+    let output = Command::new(&program)
+        .arg(manifest.join(".."))
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
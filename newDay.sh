@@ -4,6 +4,8 @@ day=$1
 
 mkdir $day
 cp -r ./.template/* $day
+sed -i "s/dayXX/day$(printf '%02d' $day)/" $day/Cargo.toml $day/src/main.rs
+sed -i "s/day: 0,/day: $day,/" $day/src/lib.rs
 
 set -a; 
 . ./.env; 
@@ -0,0 +1,313 @@
+use common::context::{Cancelled, Context};
+use std::fmt::Write as _;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const MAX_HEADER: usize = 8 * 1024;
+const DRAIN_LIMIT: u64 = 64 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Config {
+    /// Largest accepted puzzle input, in bytes.
+    pub max_body: usize,
+    /// How long a solver may run before the request fails with a 504. The
+    /// solver is then cancelled, the ones that do not check for it are left
+    /// to finish on their own.
+    pub timeout: Duration,
+    /// How long a client may take to send its request.
+    pub read_timeout: Duration,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            max_body: 1024 * 1024,
+            timeout: Duration::from_secs(10),
+            read_timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Request {
+    method: String,
+    target: String,
+    body: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Response {
+    fn error(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            body: format!("{{\"error\":{}}}", json_string(message)),
+        }
+    }
+}
+
+/// Serves requests on `listener` until it fails, one thread per
+/// connection.
+pub fn serve(listener: TcpListener, config: Config) -> io::Result<()> {
+    let config = Arc::new(config);
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else { continue };
+        let config = Arc::clone(&config);
+        thread::spawn(move || handle(stream, &config));
+    }
+    Ok(())
+}
+
+fn handle(stream: TcpStream, config: &Config) -> io::Result<()> {
+    let start = Instant::now();
+    stream.set_read_timeout(Some(config.read_timeout))?;
+    let mut reader = BufReader::new(&stream);
+    let response = match read_request(&mut reader, config.max_body) {
+        Ok(request) => route(&request, config, start),
+        Err(response) => response,
+    };
+    write_response(&stream, &response)?;
+    // Closing with unread input makes the client see a reset instead of the
+    // response, so discard what is left of a rejected body.
+    stream.shutdown(Shutdown::Write)?;
+    stream.set_read_timeout(Some(Duration::from_millis(100)))?;
+    let _ = io::copy(&mut reader.take(DRAIN_LIMIT), &mut io::sink());
+    Ok(())
+}
+
+fn read_request(
+    reader: &mut impl BufRead,
+    max_body: usize,
+) -> Result<Request, Response> {
+    let mut header = String::new();
+    let mut lines = Vec::new();
+    loop {
+        header.clear();
+        let read = reader
+            .take((MAX_HEADER + 1) as u64)
+            .read_line(&mut header)
+            .map_err(|_| Response::error(408, "request timed out"))?;
+        if read > MAX_HEADER || lines.len() > 100 {
+            return Err(Response::error(431, "request header too large"));
+        }
+        match header.trim_end() {
+            "" if read == 0 => {
+                return Err(Response::error(400, "incomplete request"))
+            }
+            "" => break,
+            line => lines.push(line.to_string()),
+        }
+    }
+
+    let request_line = lines.first().map(String::as_str).unwrap_or_default();
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err(Response::error(400, "malformed request line"));
+    };
+    let mut content_length = None;
+    for line in &lines[1..] {
+        let Some((name, value)) = line.split_once(':') else {
+            return Err(Response::error(400, "malformed header"));
+        };
+        match name.trim().to_ascii_lowercase().as_str() {
+            "content-length" => {
+                content_length = Some(value.trim().parse().map_err(|_| {
+                    Response::error(400, "invalid content-length")
+                })?)
+            }
+            "transfer-encoding" => {
+                return Err(Response::error(411, "content-length required"))
+            }
+            _ => {}
+        }
+    }
+
+    let length: usize = content_length.unwrap_or(0);
+    if length > max_body {
+        return Err(Response::error(
+            413,
+            &format!("input larger than {} bytes", max_body),
+        ));
+    }
+    let mut body = vec![0; length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|_| Response::error(408, "request timed out"))?;
+    Ok(Request {
+        method: method.to_string(),
+        target: target.to_string(),
+        body,
+    })
+}
+
+/// `POST /2022/day/<day>/part/<part>[?impl=<name>]` with the puzzle input
+/// as the body.
+fn route(request: &Request, config: &Config, start: Instant) -> Response {
+    let (path, query) = match request.target.split_once('?') {
+        Some((path, query)) => (path, Some(query)),
+        None => (request.target.as_str(), None),
+    };
+    let segments = path.trim_matches('/').split('/').collect::<Vec<_>>();
+    let ["2022", "day", day, "part", part] = segments[..] else {
+        return Response::error(404, "not found");
+    };
+    if request.method != "POST" {
+        return Response::error(405, "use POST with the input as the body");
+    }
+    let Some(day) = day.parse().ok().and_then(aoc::find_day) else {
+        return Response::error(404, &format!("day {} is not solved", day));
+    };
+    let implementations = match part {
+        "1" => day.part_one,
+        "2" => day.part_two,
+        _ => return Response::error(404, &format!("no part {}", part)),
+    };
+    let name = query
+        .into_iter()
+        .flat_map(|query| query.split('&'))
+        .find_map(|pair| pair.strip_prefix("impl="));
+    let implementation = match name {
+        Some(name) => match implementations.iter().find(|i| i.name == name) {
+            Some(implementation) => implementation,
+            None => {
+                return Response::error(
+                    404,
+                    &format!("unknown implementation: {}", name),
+                )
+            }
+        },
+        None => &implementations[0],
+    };
+    let Ok(input) = String::from_utf8(request.body.clone()) else {
+        return Response::error(400, "input is not valid UTF-8");
+    };
+
+    let (tx, rx) = mpsc::channel();
+    let solver = *implementation;
+    let context = Arc::new(Context::new());
+    let solver_context = Arc::clone(&context);
+    thread::spawn(move || {
+        let start = Instant::now();
+        let answer =
+            aoc::catch_panic(|| solver.run_in(&input, &solver_context));
+        let _ = tx.send((answer, start.elapsed()));
+    });
+    match rx.recv_timeout(config.timeout) {
+        Ok((Ok(Ok(answer)), solve)) => {
+            let mut body = String::new();
+            let _ = write!(
+                body,
+                "{{\"day\":{},\"part\":{},\"implementation\":{},\
+                 \"answer\":{},\"timings\":{{\"solve_us\":{},\
+                 \"total_us\":{}}}}}",
+                day.day,
+                part,
+                json_string(implementation.name),
+                json_string(&answer.to_string()),
+                solve.as_micros(),
+                start.elapsed().as_micros()
+            );
+            Response { status: 200, body }
+        }
+        Ok((Err(panic), _)) => {
+            Response::error(422, &format!("solver panicked: {}", panic))
+        }
+        Ok((Ok(Err(Cancelled)), _)) | Err(_) => {
+            context.cancel();
+            Response::error(
+                504,
+                &format!("solver timed out after {:?}", config.timeout),
+            )
+        }
+    }
+}
+
+fn write_response(
+    mut stream: &TcpStream,
+    response: &Response,
+) -> io::Result<()> {
+    let reason = match response.status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        411 => "Length Required",
+        413 => "Payload Too Large",
+        422 => "Unprocessable Entity",
+        431 => "Request Header Fields Too Large",
+        _ => "Gateway Timeout",
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        reason,
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()
+}
+
+fn json_string(s: &str) -> String {
+    let mut json = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(raw: &str, max_body: usize) -> Result<Request, Response> {
+        read_request(&mut raw.as_bytes(), max_body)
+    }
+
+    #[test]
+    fn test_read_request() {
+        let request = parse(
+            "POST /2022/day/1/part/1 HTTP/1.1\r\nContent-Length: 4\r\n\r\n1\n\n2",
+            10,
+        )
+        .unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.target, "/2022/day/1/part/1");
+        assert_eq!(request.body, b"1\n\n2");
+
+        let too_large = "POST / HTTP/1.1\r\ncontent-length: 11\r\n\r\n";
+        assert_eq!(parse(too_large, 10).unwrap_err().status, 413);
+        let truncated = "POST / HTTP/1.1\r\nContent-Length: 4\r\n\r\n1";
+        assert_eq!(parse(truncated, 10).unwrap_err().status, 408);
+        let chunked = "POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n";
+        assert_eq!(parse(chunked, 10).unwrap_err().status, 411);
+        let long = format!("GET /{} HTTP/1.1\r\n\r\n", "a".repeat(MAX_HEADER));
+        assert_eq!(parse(&long, 10).unwrap_err().status, 431);
+    }
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("#.\n\"a\"\\"), r##""#.\n\"a\"\\""##);
+        assert_eq!(json_string("\u{1}"), r#""\u0001""#);
+    }
+}
@@ -0,0 +1,54 @@
+use aoc::args::Args;
+use aoc_server::Config;
+use std::io::Write;
+use std::net::TcpListener;
+use std::process::ExitCode;
+use std::time::Duration;
+
+const USAGE: &str = "\
+Usage: aoc-server [--addr HOST:PORT] [--max-body BYTES] [--timeout-ms MS]
+
+Endpoints:
+    POST /2022/day/<day>/part/<part>[?impl=<name>]
+                                      Solve the input sent as the body
+";
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            eprint!("{}", USAGE);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run() -> Result<(), String> {
+    let args = Args::parse(
+        std::env::args().skip(1),
+        &["addr", "max-body", "timeout-ms"],
+        &[],
+    )?;
+    let defaults = Config::default();
+    let config = Config {
+        max_body: args.option("max-body")?.unwrap_or(defaults.max_body),
+        timeout: match args.option("timeout-ms")? {
+            Some(ms) => Duration::from_millis(ms),
+            None => defaults.timeout,
+        },
+        ..defaults
+    };
+    let addr = args
+        .option::<String>("addr")?
+        .unwrap_or_else(|| "127.0.0.1:8022".to_string());
+    let listener =
+        TcpListener::bind(&addr).map_err(|err| format!("{}: {}", addr, err))?;
+    let addr = listener.local_addr().map_err(|err| err.to_string())?;
+    // Solver panics are reported in the responses.
+    std::panic::set_hook(Box::new(|_| {}));
+
+    println!("listening on http://{}", addr);
+    std::io::stdout().flush().map_err(|err| err.to_string())?;
+    aoc_server::serve(listener, config).map_err(|err| err.to_string())
+}
@@ -0,0 +1,120 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::process::{Child, Command, Stdio};
+
+/// A server started on a free port, killed when dropped.
+struct Server {
+    child: Child,
+    addr: String,
+}
+
+impl Server {
+    fn start(args: &[&str]) -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_aoc-server"))
+            .args(["--addr", "127.0.0.1:0"])
+            .args(args)
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let mut line = String::new();
+        BufReader::new(child.stdout.as_mut().unwrap())
+            .read_line(&mut line)
+            .unwrap();
+        let addr = line.trim().strip_prefix("listening on http://").unwrap();
+        Self {
+            addr: addr.to_string(),
+            child,
+        }
+    }
+
+    fn request(&self, method: &str, target: &str, body: &str) -> (u16, String) {
+        let mut stream = TcpStream::connect(&self.addr).unwrap();
+        write!(
+            stream,
+            "{} {} HTTP/1.1\r\nHost: {}\r\nContent-Length: {}\r\n\r\n{}",
+            method,
+            target,
+            self.addr,
+            body.len(),
+            body
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let status = response[9..12].parse().unwrap();
+        let (_, body) = response.split_once("\r\n\r\n").unwrap();
+        (status, body.to_string())
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[test]
+fn test_solves_posted_input() {
+    let server = Server::start(&[]);
+    let (status, body) =
+        server.request("POST", "/2022/day/7/part/2", day07::DAY.sample);
+    assert_eq!(status, 200);
+    assert!(body.starts_with(
+        r#"{"day":7,"part":2,"implementation":"default","answer":"24933642","timings":{"solve_us":"#
+    ));
+
+    let (status, body) =
+        server.request("POST", "/2022/day/10/part/2", day10::DAY.sample);
+    assert_eq!(status, 200);
+    assert!(body.contains(
+        r###""answer":"##..##..##..##..##..##..##..##..##..##..\n###"###
+    ));
+
+    let (status, body) = server.request(
+        "POST",
+        "/2022/day/8/part/1?impl=sweep",
+        day08::DAY.sample,
+    );
+    assert_eq!(status, 200);
+    assert!(body.contains(r#""implementation":"sweep","answer":"21""#));
+}
+
+#[test]
+fn test_rejects_bad_requests() {
+    let server = Server::start(&["--max-body", "100"]);
+    let (status, _) = server.request("GET", "/2022/day/1/part/1", "");
+    assert_eq!(status, 405);
+    let (status, body) = server.request("POST", "/2022/day/16/part/1", "");
+    assert_eq!(
+        (status, body.as_str()),
+        (404, r#"{"error":"day 16 is not solved"}"#)
+    );
+    let (status, _) = server.request("POST", "/2022/day/1/part/3", "");
+    assert_eq!(status, 404);
+    let (status, _) = server.request("POST", "/2022/day/8/part/1?impl=x", "");
+    assert_eq!(status, 404);
+    let (status, _) = server.request("POST", "/", "");
+    assert_eq!(status, 404);
+
+    let (status, body) =
+        server.request("POST", "/2022/day/1/part/1", &"1\n".repeat(51));
+    assert_eq!(
+        (status, body.as_str()),
+        (413, r#"{"error":"input larger than 100 bytes"}"#)
+    );
+    let (status, body) = server.request("POST", "/2022/day/1/part/1", "x");
+    assert_eq!(status, 422);
+    assert!(body.starts_with(r#"{"error":"solver panicked: "#));
+}
+
+#[test]
+fn test_times_out_slow_solvers() {
+    let server = Server::start(&["--timeout-ms", "1"]);
+    let (status, body) =
+        server.request("POST", "/2022/day/11/part/2", day11::DAY.sample);
+    assert_eq!(
+        (status, body.as_str()),
+        (504, r#"{"error":"solver timed out after 1ms"}"#)
+    );
+}